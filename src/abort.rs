@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io;
+use std::net::{Shutdown, TcpStream};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A handle for cancelling in-flight fetches from another thread.
+///
+/// Attach it to a [`Config`](crate::Config) with [`Config::abort_handle`](crate::Config::abort_handle);
+/// every connection opened with that configuration is registered with the handle.
+/// Calling [`abort`](AbortHandle::abort) shuts those sockets down, so a caller (e.g. a
+/// TUI reacting to a keypress) doesn't have to wait for the socket to finish or
+/// time out. Aborted fetches fail with [`FetchError::Aborted`](crate::FetchError::Aborted),
+/// and so does every later fetch using the same handle.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{crate_data_with, block_on, AbortHandle, Config};
+/// use std::{thread, time::Duration};
+///
+/// let handle = AbortHandle::new();
+/// let config = Config::new().abort_handle(&handle);
+///
+/// let canceller = handle.clone();
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(50));
+///     canceller.abort();
+/// });
+///
+/// match block_on(crate_data_with("serde", &config)) {
+///     Ok(info) => println!("Latest: v{}", info.latest),
+///     Err(e) => eprintln!("❌ Error: {}", e), // "request aborted" if cancelled in time
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct AbortHandle {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    aborted: AtomicBool,
+    #[cfg(not(target_arch = "wasm32"))]
+    next_id: AtomicU64,
+    sockets: Mutex<HashMap<u64, TcpStream>>,
+}
+
+impl AbortHandle {
+    /// Creates a handle that has not been aborted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every fetch using this handle, interrupting any blocked socket I/O.
+    pub fn abort(&self) {
+        self.inner.aborted.store(true, Ordering::SeqCst);
+        for socket in self.sockets().values() {
+            let _ = socket.shutdown(Shutdown::Both);
+        }
+    }
+
+    /// Returns `true` once [`abort`](AbortHandle::abort) has been called.
+    pub fn is_aborted(&self) -> bool {
+        self.inner.aborted.load(Ordering::SeqCst)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    /// Tracks `stream` until the returned registration is dropped.
+    pub(crate) fn register(&self, stream: &TcpStream) -> io::Result<Registration> {
+        if self.is_aborted() { return Err(io::Error::new(io::ErrorKind::Interrupted, "request aborted")); }
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        self.sockets().insert(id, stream.try_clone()?);
+        // An abort racing with the insert above must still reach this socket
+        if self.is_aborted() { let _ = stream.shutdown(Shutdown::Both); }
+        Ok(Registration { handle: self.clone(), id })
+    }
+
+    fn sockets(&self) -> std::sync::MutexGuard<'_, HashMap<u64, TcpStream>> {
+        self.inner.sockets.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Keeps a socket registered with an [`AbortHandle`] for as long as it is alive.
+pub(crate) struct Registration {
+    handle: AbortHandle,
+    id: u64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Registration {
+    /// Returns `true` once the owning handle has been aborted.
+    pub(crate) fn is_aborted(&self) -> bool {
+        self.handle.is_aborted()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.handle.sockets().remove(&self.id);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::coalesce::duplicate;
+use crate::executor::map_bounded;
+use crate::{http, Config, Json, Lockfile, Request, Toml, TomlError, Value, Version, VersionReq};
+use std::error::Error;
+use std::fmt;
+
+/// Lists every file of the RustSec advisory database in one GitHub API request.
+const ADVISORY_INDEX: (&str, &str) = ("api.github.com", "/repos/rustsec/advisory-db/git/trees/main?recursive=1");
+
+/// Serves the raw advisory files, outside the GitHub API rate limit.
+const ADVISORY_FILES: (&str, &str) = ("raw.githubusercontent.com", "/rustsec/advisory-db/main/");
+
+/// A RustSec security advisory, as published in the
+/// [advisory database](https://github.com/rustsec/advisory-db).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Advisory {
+    /// The advisory ID (e.g. "RUSTSEC-2023-0071").
+    pub id: String,
+    /// The affected crate.
+    pub package: String,
+    /// The one-line summary.
+    pub title: String,
+    /// The date the advisory was reported (`YYYY-MM-DD`).
+    pub date: String,
+    /// A link to more details, if any.
+    pub url: Option<String>,
+    /// Other IDs for the same issue (e.g. CVE or GHSA).
+    pub aliases: Vec<String>,
+    /// Set for advisories that aren't vulnerabilities: `"unmaintained"`,
+    /// `"unsound"` or `"notice"`.
+    pub informational: Option<String>,
+    /// Whether the advisory was withdrawn as invalid.
+    pub withdrawn: bool,
+    /// The requirements of the versions with a fix (e.g. `">= 1.2.3"`).
+    pub patched: Vec<String>,
+    /// The requirements of the versions never affected.
+    pub unaffected: Vec<String>,
+}
+
+impl Advisory {
+    /// Parses an advisory file: Markdown with its metadata in a leading ```` ```toml ````
+    /// block and the title as the first `#` heading.
+    ///
+    /// # Errors
+    /// Fails if the TOML block is missing, invalid, or lacks `[advisory] id`.
+    ///
+    /// # Example
+    /// ````rust
+    /// use crator::{Advisory, Version};
+    ///
+    /// let advisory = Advisory::parse(r#"```toml
+    /// [advisory]
+    /// id = "RUSTSEC-2020-0071"
+    /// package = "time"
+    /// date = "2020-11-18"
+    /// aliases = ["CVE-2020-26235"]
+    ///
+    /// [versions]
+    /// patched = [">= 0.2.23"]
+    /// unaffected = ["= 0.2.0", "= 0.2.1", "= 0.2.2", "= 0.2.3", "= 0.2.4", "= 0.2.5", "= 0.2.6"]
+    /// ```
+    ///
+    /// ## Potential segfault in the time crate
+    /// "#).expect("Invalid advisory");
+    ///
+    /// assert_eq!(advisory.title, "Potential segfault in the time crate");
+    /// assert!(advisory.affects(&Version::new(0, 1, 43)));
+    /// assert!(!advisory.affects(&Version::new(0, 2, 23)));
+    /// assert!(!advisory.affects(&Version::new(0, 2, 4)));
+    /// ````
+    pub fn parse(text: &str) -> Result<Self, TomlError> {
+        let missing = |reason| TomlError { line: 1, reason };
+        let body = text.trim_start().strip_prefix("```toml").ok_or(missing("missing ```toml front matter"))?;
+        let (front, rest) = body.split_once("\n```").ok_or(missing("unterminated ```toml front matter"))?;
+        let document = Toml::parse(front)?;
+        let field = |path: &str| document.at(path).and_then(Value::as_str).map(str::to_string);
+        let list = |path: &str| -> Vec<String> {
+            document.at(path).and_then(Value::as_array).unwrap_or_default().iter().filter_map(|item| item.as_str().map(str::to_string)).collect()
+        };
+        Ok(Advisory {
+            id: field("advisory.id").ok_or(missing("missing [advisory] id"))?,
+            package: field("advisory.package").unwrap_or_default(),
+            title: rest.lines().find_map(|line| line.strip_prefix("# ")).unwrap_or_default().trim().to_string(),
+            date: field("advisory.date").unwrap_or_default(),
+            url: field("advisory.url"),
+            aliases: list("advisory.aliases"),
+            informational: field("advisory.informational"),
+            withdrawn: document.at("advisory.withdrawn").is_some(),
+            patched: list("versions.patched"),
+            unaffected: list("versions.unaffected"),
+        })
+    }
+
+    /// Whether `version` is neither patched nor unaffected.
+    pub fn affects(&self, version: &Version) -> bool {
+        let matches = |reqs: &[String]| reqs.iter().filter_map(|req| VersionReq::parse(req)).any(|req| req.matches(version));
+        !matches(&self.patched) && !matches(&self.unaffected)
+    }
+}
+
+/// A locked package affected by an advisory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditFinding {
+    /// The crate name.
+    pub name: String,
+    /// The locked version.
+    pub version: String,
+    /// The advisory affecting it.
+    pub advisory: Advisory,
+    /// The workspace members (or other local packages) depending on it, directly or
+    /// transitively (see [`Lockfile::pulled_in_by`]).
+    pub pulled_in_by: Vec<String>,
+}
+
+/// The result of [`audit_report`].
+#[derive(Debug, Default)]
+pub struct AuditReport {
+    /// The locked packages with a known vulnerability, in lockfile order.
+    pub vulnerabilities: Vec<AuditFinding>,
+    /// The locked packages with an informational advisory (unmaintained, unsound,
+    /// or a notice), in lockfile order.
+    pub warnings: Vec<AuditFinding>,
+    /// How many advisories were checked against the lockfile.
+    pub advisories: usize,
+    /// The files that couldn't be fetched or parsed, with the reason.
+    pub failed: Vec<(String, Box<dyn Error>)>,
+}
+
+impl fmt::Display for AuditReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for finding in self.vulnerabilities.iter().chain(&self.warnings) {
+            let advisory = &finding.advisory;
+            write!(f, "{:<18} {} {}: {}", advisory.id, finding.name, finding.version, advisory.title)?;
+            if let Some(kind) = &advisory.informational { write!(f, " ({})", kind)?; }
+            if advisory.patched.is_empty() {
+                write!(f, "  no patched version")?;
+            } else {
+                write!(f, "  patched: {}", advisory.patched.join("; "))?;
+            }
+            if !finding.pulled_in_by.is_empty() { write!(f, "  via {}", finding.pulled_in_by.join(", "))?; }
+            writeln!(f)?;
+        }
+        for (path, e) in &self.failed {
+            writeln!(f, "{} error: {}", path, e)?;
+        }
+        writeln!(f, "{} vulnerabilities, {} warnings ({} advisories checked)", self.vulnerabilities.len(), self.warnings.len(), self.advisories)
+    }
+}
+
+/// Fetches a file over HTTPS, failing on non-2xx responses.
+async fn get(host: &str, path: &str, config: &Config) -> Result<String, Box<dyn Error>> {
+    let (head, body) = http::fetch_async(host, &Request::new("GET", path), config).await?;
+    http::ensure_success(&head)?;
+    Ok(body)
+}
+
+/// Checks every crates.io package of `lockfile` against the RustSec advisory
+/// database, like `cargo audit`.
+///
+/// The database is read straight from its GitHub repository: one API request lists
+/// the advisories, then only those for crates in the lockfile are downloaded.
+/// Withdrawn advisories are skipped; see [`audit_report_with`] for a custom
+/// [`Config`], e.g. with a [`ResponseCache`](crate::ResponseCache) to avoid
+/// downloading the advisories again on every run.
+///
+/// # Errors
+/// Fails if the advisory list can't be fetched; advisory files that fail are listed
+/// in [`AuditReport::failed`].
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{audit_report, block_on, Lockfile};
+///
+/// let lockfile = Lockfile::from_path("Cargo.lock").expect("Failed to read lockfile");
+/// let report = block_on(audit_report(&lockfile)).expect("Failed to fetch advisories");
+/// print!("{}", report);
+/// if !report.vulnerabilities.is_empty() {
+///     std::process::exit(1);
+/// }
+/// ```
+pub async fn audit_report(lockfile: &Lockfile) -> Result<AuditReport, Box<dyn Error>> {
+    audit_report_with(lockfile, crate::default_config()).await
+}
+
+/// Same as [`audit_report`], but driven by a custom [`Config`]; advisory files are
+/// fetched with [`Config::max_concurrency`] requests in flight.
+pub async fn audit_report_with(lockfile: &Lockfile, config: &Config) -> Result<AuditReport, Box<dyn Error>> {
+    let index = get(ADVISORY_INDEX.0, ADVISORY_INDEX.1, config).await?;
+    // Advisories live at `crates/<name>/<id>.md`
+    let paths: Vec<String> = Json::iter_array(&index, "tree")
+        .filter_map(|entry| Json::extract_opt(&entry, "path"))
+        .filter(|path| {
+            let mut parts = path.split('/');
+            parts.next() == Some("crates") && parts.next().is_some_and(|name| lockfile.crates_io().any(|package| package.name == name))
+                && parts.next().is_some_and(|file| file.ends_with(".md")) && parts.next().is_none()
+        })
+        .collect();
+    let fetched = map_bounded(&paths, config.concurrency(), |path| async move {
+        let text = get(ADVISORY_FILES.0, &format!("{}{}", ADVISORY_FILES.1, path), config).await.map_err(http::sendable)?;
+        Advisory::parse(&text).map_err(Box::<dyn Error + Send + Sync>::from)
+    }).await;
+
+    let mut report = AuditReport::default();
+    let mut advisories = Vec::new();
+    for (path, result) in paths.into_iter().zip(fetched) {
+        match result {
+            Ok(advisory) if advisory.withdrawn => {}
+            Ok(advisory) => advisories.push(advisory),
+            Err(e) => report.failed.push((path, duplicate(e.as_ref()))),
+        }
+    }
+    report.advisories = advisories.len();
+
+    let pulled_in_by = lockfile.pulled_in_by();
+    for (position, package) in lockfile.packages.iter().enumerate().filter(|(_, package)| package.is_crates_io()) {
+        let Some(version) = Version::parse(&package.version) else { continue };
+        for advisory in advisories.iter().filter(|advisory| advisory.package == package.name && advisory.affects(&version)) {
+            let finding = AuditFinding {
+                name: package.name.clone(),
+                version: package.version.clone(),
+                advisory: advisory.clone(),
+                pulled_in_by: pulled_in_by[position].clone(),
+            };
+            match advisory.informational {
+                Some(_) => report.warnings.push(finding),
+                None => report.vulnerabilities.push(finding),
+            }
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,408 @@
+use crate::http;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A response held by a [`CacheStore`], with the time it stops being fresh.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    /// The raw status line and headers.
+    pub head: String,
+    /// The buffered body.
+    pub body: String,
+    /// When the response expires; stores keep expired entries until told otherwise.
+    pub expires: SystemTime,
+}
+
+impl CachedResponse {
+    /// Whether the entry can be renewed with a conditional request once expired.
+    fn revalidatable(&self) -> bool {
+        http::header(&self.head, "etag").is_some()
+    }
+}
+
+/// Storage behind a [`ResponseCache`].
+///
+/// Keys are the request host and target separated by a space (e.g.
+/// `"crates.io /api/v1/crates/serde"`). Freshness is decided by the cache from
+/// [`CachedResponse::expires`], so a store only has to keep what it's given;
+/// failures should read as misses rather than errors. crator ships
+/// [`MemoryStore`] and [`DiskStore`]; services can plug in Redis, sled and the like
+/// through [`ResponseCache::with_store`].
+///
+/// # Example
+/// ```rust
+/// use crator::{CacheStore, CachedResponse, ResponseCache};
+/// use std::collections::BTreeMap;
+/// use std::sync::Mutex;
+/// use std::time::Duration;
+///
+/// #[derive(Default)]
+/// struct SortedStore(Mutex<BTreeMap<String, CachedResponse>>);
+///
+/// impl CacheStore for SortedStore {
+///     fn get(&self, key: &str) -> Option<CachedResponse> {
+///         self.0.lock().unwrap().get(key).cloned()
+///     }
+///     fn put(&self, key: &str, response: CachedResponse) {
+///         self.0.lock().unwrap().insert(key.to_string(), response);
+///     }
+///     fn invalidate(&self, key: &str) {
+///         self.0.lock().unwrap().remove(key);
+///     }
+///     fn keys(&self) -> Vec<String> {
+///         self.0.lock().unwrap().keys().cloned().collect()
+///     }
+/// }
+///
+/// let cache = ResponseCache::with_store(SortedStore::default(), Duration::from_secs(300));
+/// assert!(cache.is_empty());
+/// ```
+pub trait CacheStore: Send + Sync {
+    /// Returns the response stored under `key`, expired or not.
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Stores `response` under `key`, replacing any previous one.
+    fn put(&self, key: &str, response: CachedResponse);
+
+    /// Removes the response stored under `key`, if any.
+    fn invalidate(&self, key: &str);
+
+    /// Lists the keys of every stored response.
+    fn keys(&self) -> Vec<String>;
+}
+
+/// The outcome of a cache lookup.
+pub(crate) enum Lookup {
+    /// A response within its time to live: `(head, body)`.
+    Fresh(String, String),
+    /// An expired response, to revalidate with `If-None-Match` when it has an `ETag`.
+    /// `serve` is set while it is within the stale-while-revalidate window.
+    Stale { etag: Option<String>, head: String, body: String, serve: bool },
+    Miss,
+}
+
+/// A [`CacheStore`] keeping responses in memory for the life of the process.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl MemoryStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, CachedResponse>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.entries().get(key).cloned()
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        self.entries().insert(key.to_string(), response);
+    }
+
+    fn invalidate(&self, key: &str) {
+        self.entries().remove(key);
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.entries().keys().cloned().collect()
+    }
+}
+
+/// A [`CacheStore`] keeping one file per response in a directory, so separate
+/// processes share entries. Writes are best effort: a file that can't be written
+/// just misses next time.
+#[derive(Debug, Clone)]
+pub struct DiskStore {
+    dir: PathBuf,
+}
+
+impl DiskStore {
+    /// Opens (creating if needed) a store in `dir`.
+    ///
+    /// # Errors
+    /// Fails if the directory can't be created.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(DiskStore { dir })
+    }
+
+    /// Opens the store in the user's cache directory: `$XDG_CACHE_HOME/crator`,
+    /// falling back to `~/.cache/crator` (`%LOCALAPPDATA%\crator` on Windows).
+    ///
+    /// # Errors
+    /// Fails if no cache directory is known or it can't be created.
+    pub fn user_cache() -> io::Result<Self> {
+        let dir = default_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no cache directory (set XDG_CACHE_HOME or HOME)"))?;
+        Self::new(dir)
+    }
+
+    /// The directory holding the entry files.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn files(&self) -> impl Iterator<Item = PathBuf> {
+        fs::read_dir(&self.dir).into_iter().flatten().flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "entry"))
+    }
+}
+
+impl CacheStore for DiskStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        // A hash collision reads as a miss
+        read_file(&entry_path(&self.dir, key)).filter(|(stored_key, _)| stored_key == key).map(|(_, response)| response)
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let _ = write_file(&entry_path(&self.dir, key), key, &response);
+    }
+
+    fn invalidate(&self, key: &str) {
+        let _ = fs::remove_file(entry_path(&self.dir, key));
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.files().filter_map(|path| read_file(&path)).map(|(key, _)| key).collect()
+    }
+}
+
+/// A cache of successful `GET` responses, kept for a fixed time to live.
+///
+/// Attach it to a [`Config`](crate::Config) with [`Config::cache`](crate::Config::cache):
+/// every `GET` made with that configuration is first looked up by host and request
+/// target (e.g. `/api/v1/crates/serde`, or its `/owners` endpoint), and 2xx responses
+/// are stored. Repeated [`crate_data`](crate::crate_data) calls then don't hit
+/// crates.io again until the entry expires. Clones share the same entries.
+/// Requests with an `Authorization` header (e.g. from [`Config::token`](crate::Config::token))
+/// bypass the cache, so authenticated responses are never stored or served to others.
+///
+/// Once an entry that came with an `ETag` expires, the next request revalidates it
+/// with `If-None-Match`: a `304 Not Modified` answer renews the entry and serves the
+/// cached body, so staying current costs a header exchange rather than a download.
+/// With [`stale_while_revalidate`](ResponseCache::stale_while_revalidate), recently
+/// expired entries are even served right away while they refresh in the background.
+///
+/// [`ResponseCache::new`] keeps entries in memory for the life of the process;
+/// [`ResponseCache::persistent`] writes them to disk, so short-lived CLI invocations
+/// reuse each other's responses. Other backends plug in through [`CacheStore`].
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, crate_data_with, Config, ResponseCache};
+/// use std::time::Duration;
+///
+/// let cache = ResponseCache::new(Duration::from_secs(300));
+/// let config = Config::new().cache(&cache);
+///
+/// let first = block_on(crate_data_with("serde", &config)).expect("Failed to fetch crate data");
+/// // Served from memory for the next five minutes
+/// let second = block_on(crate_data_with("serde", &config)).expect("Failed to fetch crate data");
+/// assert_eq!(first, second);
+/// assert_eq!(cache.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    /// The directory of a [`DiskStore`], for [`ResponseCache::dir`].
+    dir: Option<PathBuf>,
+    ttl: Duration,
+    stale_window: Duration,
+    /// Keys with a background refresh in flight.
+    refreshing: Arc<Mutex<HashSet<String>>>,
+}
+
+impl ResponseCache {
+    /// Creates an empty in-memory cache whose entries stay fresh for `ttl`
+    /// (`Duration::MAX` for entries that never expire).
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_store(MemoryStore::new(), ttl)
+    }
+
+    /// Opens the on-disk cache in the user's cache directory (see
+    /// [`DiskStore::user_cache`]).
+    ///
+    /// Each entry records when it expires, so a later process honors the `ttl` it was
+    /// stored with.
+    ///
+    /// # Errors
+    /// Fails if no cache directory is known or it can't be created.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, crate_data_with, Config, ResponseCache};
+    /// use std::time::Duration;
+    ///
+    /// let cache = ResponseCache::persistent(Duration::from_secs(3600)).expect("No cache directory");
+    /// // Fresh for an hour across runs of the program
+    /// let info = block_on(crate_data_with("serde", &Config::new().cache(&cache))).expect("Failed to fetch crate data");
+    /// println!("serde v{}", info.latest);
+    /// ```
+    pub fn persistent(ttl: Duration) -> io::Result<Self> {
+        Ok(Self::on_disk(DiskStore::user_cache()?, ttl))
+    }
+
+    /// Opens (creating if needed) an on-disk cache in `dir`.
+    ///
+    /// # Errors
+    /// Fails if the directory can't be created.
+    pub fn in_dir(dir: impl Into<PathBuf>, ttl: Duration) -> io::Result<Self> {
+        Ok(Self::on_disk(DiskStore::new(dir)?, ttl))
+    }
+
+    /// Creates a cache over any [`CacheStore`].
+    pub fn with_store(store: impl CacheStore + 'static, ttl: Duration) -> Self {
+        ResponseCache { store: Arc::new(store), dir: None, ttl, stale_window: Duration::ZERO, refreshing: Arc::default() }
+    }
+
+    fn on_disk(store: DiskStore, ttl: Duration) -> Self {
+        let dir = store.dir().to_path_buf();
+        ResponseCache { dir: Some(dir), ..Self::with_store(store, ttl) }
+    }
+
+    /// Keeps serving entries for up to `window` past their expiry: the cached response
+    /// is returned immediately and refreshed in the background, so the next request
+    /// sees the new data. Suits dashboards that tolerate slightly stale numbers.
+    ///
+    /// The refresh runs on a helper thread (a local task on wasm32); a process that
+    /// exits right away may not see it finish.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, crate_data_with, Config, ResponseCache};
+    /// use std::time::Duration;
+    ///
+    /// let cache = ResponseCache::new(Duration::from_secs(60)).stale_while_revalidate(Duration::from_secs(600));
+    /// let config = Config::new().cache(&cache);
+    /// // Never waits on crates.io once the first response is cached, unless it's over 11 minutes old
+    /// let info = block_on(crate_data_with("serde", &config)).expect("Failed to fetch crate data");
+    /// println!("serde: {} downloads", info.downloads);
+    /// ```
+    pub fn stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.stale_window = window;
+        self
+    }
+
+    /// How long entries are served after being stored.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// The directory of an on-disk cache; `None` for any other store.
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// The number of stored entries, including expired ones kept for revalidation or
+    /// not yet evicted.
+    pub fn len(&self) -> usize {
+        self.store.keys().len()
+    }
+
+    /// Returns `true` if no entries are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops the entries for `target` (e.g. `/api/v1/crates/serde`) on every host.
+    pub fn invalidate(&self, target: &str) {
+        for key in self.store.keys() {
+            if key.split_once(' ').is_some_and(|(_, cached)| cached == target) { self.store.invalidate(&key); }
+        }
+    }
+
+    /// Drops every entry.
+    pub fn clear(&self) {
+        self.store.keys().iter().for_each(|key| self.store.invalidate(key));
+    }
+
+    /// Looks up the response stored for `target` on `host`. An expired entry that
+    /// carries an `ETag` or is within the stale window is kept; any other is evicted.
+    pub(crate) fn get(&self, host: &str, target: &str) -> Lookup {
+        let key = key(host, target);
+        let now = SystemTime::now();
+        let Some(entry) = self.store.get(&key) else { return Lookup::Miss };
+        // A window too large to add is never over
+        let within_window = entry.expires.checked_add(self.stale_window).is_none_or(|deadline| now < deadline);
+        if !within_window && !entry.revalidatable() {
+            self.store.invalidate(&key);
+            return Lookup::Miss;
+        }
+        if entry.expires > now { return Lookup::Fresh(entry.head, entry.body); }
+        let etag = http::header(&entry.head, "etag").map(str::to_string);
+        Lookup::Stale { etag, head: entry.head, body: entry.body, serve: within_window }
+    }
+
+    /// Claims the background refresh of `target` on `host`; `false` if one is already running.
+    pub(crate) fn begin_refresh(&self, host: &str, target: &str) -> bool {
+        self.refreshing.lock().unwrap_or_else(|e| e.into_inner()).insert(key(host, target))
+    }
+
+    /// Releases a claim taken with [`begin_refresh`](Self::begin_refresh).
+    pub(crate) fn end_refresh(&self, host: &str, target: &str) {
+        self.refreshing.lock().unwrap_or_else(|e| e.into_inner()).remove(&key(host, target));
+    }
+
+    /// Stores a response, or renews a revalidated one.
+    pub(crate) fn insert(&self, host: &str, target: &str, head: &str, body: &str) {
+        let response = CachedResponse { head: head.to_string(), body: body.to_string(), expires: SystemTime::now() + self.ttl.min(NEVER) };
+        self.store.put(&key(host, target), response);
+    }
+}
+
+/// The longest lifetime given to an entry, so a `ttl` of `Duration::MAX` ("never
+/// expire") still yields an expiry every platform's `SystemTime` can hold.
+const NEVER: Duration = Duration::from_secs(1000 * 365 * 86_400);
+
+fn key(host: &str, target: &str) -> String {
+    format!("{} {}", host, target)
+}
+
+fn default_dir() -> Option<PathBuf> {
+    let var = |name: &str| env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    if let Some(dir) = var("XDG_CACHE_HOME") { return Some(dir.join("crator")); }
+    #[cfg(windows)]
+    if let Some(dir) = var("LOCALAPPDATA") { return Some(dir.join("crator")); }
+    var("HOME").map(|home| home.join(".cache").join("crator"))
+}
+
+/// Names entry files after a stable (FNV-1a) hash of the key, since keys may hold
+/// characters and lengths that filesystems reject.
+fn entry_path(dir: &Path, key: &str) -> PathBuf {
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    dir.join(format!("{:016x}.entry", hash))
+}
+
+/// Writes an entry as three header lines (key, expiry in Unix seconds, head length)
+/// followed by the head and the body, replacing any previous file atomically.
+fn write_file(path: &Path, key: &str, entry: &CachedResponse) -> io::Result<()> {
+    let expires = entry.expires.duration_since(UNIX_EPOCH).map_or(0, |expires| expires.as_secs());
+    let contents = format!("{}\n{}\n{}\n{}{}", key, expires, entry.head.len(), entry.head, entry.body);
+    let partial = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&partial, contents)?;
+    fs::rename(&partial, path).inspect_err(|_| { let _ = fs::remove_file(&partial); })
+}
+
+fn read_file(path: &Path) -> Option<(String, CachedResponse)> {
+    let contents = fs::read_to_string(path).ok()?;
+    let mut header = contents.splitn(4, '\n');
+    let (key, expires, head_len, rest) = (header.next()?, header.next()?, header.next()?, header.next()?);
+    let expires = UNIX_EPOCH.checked_add(Duration::from_secs(expires.parse().ok()?))?;
+    let head_len = head_len.parse().ok()?;
+    let (head, body) = (rest.get(..head_len)?, rest.get(head_len..)?);
+    Some((key.to_string(), CachedResponse { head: head.to_string(), body: body.to_string(), expires }))
+}
@@ -0,0 +1,103 @@
+use crate::Cancelled;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+
+/// A cooperative cancellation signal shared between threads.
+///
+/// Futures wrapped with [`cancellable`], and every fetch made with a
+/// [`Config::cancel_token`](crate::Config::cancel_token), check the token each time
+/// they are polled and resolve to [`Cancelled`] once it is triggered. Cancelling also
+/// wakes them, so a pending batch stops right away instead of at its next I/O event.
+///
+/// Unlike an [`AbortHandle`](crate::AbortHandle), which shuts sockets down under
+/// blocking reads, a token never interrupts a poll in progress.
+///
+/// # Example
+/// ```rust
+/// use crator::{block_on, cancellable, CancelToken, Cancelled};
+/// use std::{thread, time::Duration};
+///
+/// let token = CancelToken::new();
+/// let canceller = token.clone();
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(20));
+///     canceller.cancel();
+/// });
+///
+/// let result = block_on(cancellable(std::future::pending::<()>(), &token));
+/// assert_eq!(result, Err(Cancelled));
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    next_id: AtomicU64,
+    /// The tasks polling a future tied to this token, to wake on cancellation.
+    wakers: Mutex<HashMap<u64, Waker>>,
+}
+
+impl CancelToken {
+    /// Creates a token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every future tied to this token and wakes their tasks.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        let wakers: Vec<Waker> = self.wakers().drain().map(|(_, waker)| waker).collect();
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    /// Returns `true` once [`cancel`](CancelToken::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn wakers(&self) -> MutexGuard<'_, HashMap<u64, Waker>> {
+        self.inner.wakers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Future returned by [`cancellable`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Cancellable<F: Future> {
+    future: Pin<Box<F>>,
+    token: CancelToken,
+    id: u64,
+}
+
+impl<F: Future> Future for Cancellable<F> {
+    type Output = Result<F::Output, Cancelled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.token.is_cancelled() { return Poll::Ready(Err(Cancelled)); }
+        if let Poll::Ready(output) = self.future.as_mut().poll(cx) { return Poll::Ready(Ok(output)); }
+        self.token.wakers().insert(self.id, cx.waker().clone());
+        // A cancel racing with the registration above must not be missed
+        if self.token.is_cancelled() { return Poll::Ready(Err(Cancelled)); }
+        Poll::Pending
+    }
+}
+
+impl<F: Future> Drop for Cancellable<F> {
+    fn drop(&mut self) {
+        self.token.wakers().remove(&self.id);
+    }
+}
+
+/// Ties a future to `token`: it resolves to `Err(Cancelled)` as soon as the token
+/// is cancelled, and the inner future is not polled again.
+pub fn cancellable<F: Future>(future: F, token: &CancelToken) -> Cancellable<F> {
+    let id = token.inner.next_id.fetch_add(1, Ordering::Relaxed);
+    Cancellable { future: Box::pin(future), token: token.clone(), id }
+}
@@ -0,0 +1,156 @@
+use crate::{Toml, TomlError, Value};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where the crates of a replaced source come from, per a `[source]` table of
+/// `.cargo/config.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceReplacement {
+    /// A remote registry index, as written (e.g. `"sparse+https://mirror.example.com/index/"`).
+    Registry(String),
+    /// A `cargo local-registry` directory.
+    LocalRegistry(PathBuf),
+    /// A `cargo vendor` directory.
+    Directory(PathBuf),
+}
+
+/// One `[source.<name>]` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SourceTable {
+    replace_with: Option<String>,
+    location: Option<SourceReplacement>,
+}
+
+/// The `[registries]` and `[source]` sections of Cargo's configuration, which
+/// [`Config::cargo_config`](crate::Config::cargo_config) follows to read from the same
+/// mirror, local registry or vendor directory as Cargo.
+///
+/// # Example
+/// ```rust
+/// use crator::{CargoConfig, SourceReplacement};
+/// use std::path::PathBuf;
+///
+/// let cargo = CargoConfig::parse(r#"
+/// [registries.my-company]
+/// index = "sparse+https://cargo.example.com/index/"
+///
+/// [source.crates-io]
+/// replace-with = "vendored-sources"
+///
+/// [source.vendored-sources]
+/// directory = "vendor"
+/// "#).expect("Invalid configuration");
+///
+/// assert_eq!(cargo.registry_index("my-company"), Some("sparse+https://cargo.example.com/index/"));
+/// assert_eq!(cargo.replacement("crates-io"), Some(SourceReplacement::Directory(PathBuf::from("vendor"))));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CargoConfig {
+    registries: Vec<(String, String)>,
+    sources: Vec<(String, SourceTable)>,
+}
+
+impl CargoConfig {
+    /// Parses the text of a `.cargo/config.toml`; relative paths are kept as written.
+    ///
+    /// # Errors
+    /// Fails if the text isn't valid TOML.
+    pub fn parse(text: &str) -> Result<Self, TomlError> {
+        let document = Toml::parse(text)?;
+        let field = |table: &Value, key: &str| table.get(key).and_then(Value::as_str).map(str::to_string);
+        let table = |name: &str| document.get(name).and_then(Value::as_object).unwrap_or_default();
+        Ok(CargoConfig {
+            registries: table("registries").iter().filter_map(|(name, registry)| Some((name.clone(), field(registry, "index")?))).collect(),
+            sources: table("source").iter().map(|(name, source)| {
+                let location = field(source, "registry").map(SourceReplacement::Registry)
+                    .or_else(|| field(source, "local-registry").map(|dir| SourceReplacement::LocalRegistry(dir.into())))
+                    .or_else(|| field(source, "directory").map(|dir| SourceReplacement::Directory(dir.into())));
+                (name.clone(), SourceTable { replace_with: field(source, "replace-with"), location })
+            }).collect(),
+        })
+    }
+
+    /// Reads the configuration Cargo would use when run in `dir`: every
+    /// `.cargo/config.toml` (or legacy `.cargo/config`) from `dir` up to the root,
+    /// then `$CARGO_HOME/config.toml`, the nearest file winning for each registry and
+    /// source. Relative paths resolve against the directory holding `.cargo`, as in
+    /// Cargo.
+    ///
+    /// # Errors
+    /// Fails if a configuration file exists but can't be read or parsed.
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let dir = dir.as_ref();
+        let dir = if dir.is_absolute() { dir.to_path_buf() } else { env::current_dir()?.join(dir) };
+        let mut directories: Vec<PathBuf> = dir.ancestors().map(|ancestor| ancestor.join(".cargo")).collect();
+        if let Some(home) = cargo_home() && !directories.contains(&home) { directories.push(home); }
+
+        let mut merged = CargoConfig::default();
+        for directory in directories {
+            for file in ["config.toml", "config"] {
+                let path = directory.join(file);
+                let text = match fs::read_to_string(&path) {
+                    Ok(text) => text,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound || e.kind() == io::ErrorKind::NotADirectory => continue,
+                    Err(e) => return Err(io::Error::new(e.kind(), format!("{}: {}", path.display(), e)).into()),
+                };
+                let config = CargoConfig::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+                merged.merge(config, directory.parent().unwrap_or(&directory));
+                break;
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Adds the registries and sources of a farther file, resolving its relative
+    /// paths against `base`.
+    fn merge(&mut self, farther: CargoConfig, base: &Path) {
+        for (name, index) in farther.registries {
+            if !self.registries.iter().any(|(known, _)| *known == name) { self.registries.push((name, index)); }
+        }
+        for (name, mut source) in farther.sources {
+            if self.sources.iter().any(|(known, _)| *known == name) { continue; }
+            source.location = source.location.map(|location| match location {
+                SourceReplacement::LocalRegistry(dir) => SourceReplacement::LocalRegistry(base.join(dir)),
+                SourceReplacement::Directory(dir) => SourceReplacement::Directory(base.join(dir)),
+                registry => registry,
+            });
+            self.sources.push((name, source));
+        }
+    }
+
+    /// The index URL of the registry named `name` in `[registries]`, as written.
+    pub fn registry_index(&self, name: &str) -> Option<&str> {
+        self.registries.iter().find(|(registry, _)| registry == name).map(|(_, index)| index.as_str())
+    }
+
+    /// Where the source named `name` (e.g. `"crates-io"`) is read from after
+    /// following its `replace-with` chain, or `None` if it isn't replaced. A
+    /// replacement may name a `[source]` or a `[registries]` entry.
+    pub fn replacement(&self, name: &str) -> Option<SourceReplacement> {
+        let source = |name: &str| self.sources.iter().find(|(source, _)| source == name).map(|(_, table)| table);
+        let mut current = source(name)?.replace_with.clone()?;
+        // A cycle is a Cargo error; give up instead of looping
+        for _ in 0..=self.sources.len() {
+            let Some(table) = source(&current) else {
+                return self.registry_index(&current).map(|index| SourceReplacement::Registry(index.to_string()));
+            };
+            match &table.replace_with {
+                Some(next) => current = next.clone(),
+                None => return table.location.clone(),
+            }
+        }
+        None
+    }
+}
+
+/// `$CARGO_HOME`, or `.cargo` in the home directory.
+pub(crate) fn cargo_home() -> Option<PathBuf> {
+    let var = |name: &str| env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    if let Some(dir) = var("CARGO_HOME") { return Some(dir); }
+    #[cfg(windows)]
+    if let Some(dir) = var("USERPROFILE") { return Some(dir.join(".cargo")); }
+    var("HOME").map(|home| home.join(".cargo"))
+}
@@ -0,0 +1,260 @@
+use crate::{http, Config, FetchError};
+use std::error::Error;
+
+/// A small HTTP client for calling crates.io endpoints that crator doesn't wrap yet.
+///
+/// It reuses crator's TLS plumbing and response parsing (including chunked
+/// transfer decoding and the [`Config::max_response_size`] guard), so users only
+/// describe *what* to request with a [`Request`].
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, HttpClient, Json};
+///
+/// let client = HttpClient::new();
+/// let request = client.get("/api/v1/crates")
+///     .query("q", "json parser")
+///     .query("per_page", "5");
+///
+/// let response = block_on(client.send(&request)).expect("Request failed");
+/// println!("HTTP {}: {}", response.status, Json::extract(&response.body, "meta.total"));
+/// ```
+#[derive(Clone, Default)]
+pub struct HttpClient {
+    config: Config,
+}
+
+impl HttpClient {
+    /// Creates a client with the default [`Config`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a client with a custom [`Config`].
+    pub fn with_config(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// The configuration used by this client.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Starts a `GET` request for the given path.
+    pub fn get(&self, path: &str) -> Request {
+        Request::new("GET", path)
+    }
+
+    /// Starts a request with an arbitrary method (e.g. `"HEAD"`).
+    pub fn request(&self, method: &str, path: &str) -> Request {
+        Request::new(method, path)
+    }
+
+    /// Sends a request and buffers the response.
+    ///
+    /// Unlike [`crate_data`](crate::crate_data), a non-2xx status is **not** an error here;
+    /// use [`Response::error_for_status`] to opt into that behavior.
+    pub async fn send(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let (head, body) = http::fetch_api(request, &self.config).await?;
+        Ok(Response::from_parts(&head, body)?)
+    }
+
+    /// Sends several requests over a single multiplexed HTTP/2 connection.
+    ///
+    /// The crates.io CDN speaks `h2`, so many metadata requests can share one TLS
+    /// handshake and be answered concurrently. Responses are returned in request
+    /// order. If the server doesn't negotiate HTTP/2, the requests are sent one by
+    /// one over HTTP/1.1 instead.
+    ///
+    /// Requires the `http2` feature.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, HttpClient, Json};
+    ///
+    /// let client = HttpClient::new();
+    /// let requests: Vec<_> = ["serde", "rand", "mathlab"].iter()
+    ///     .map(|name| client.get(&format!("/api/v1/crates/{}", name)))
+    ///     .collect();
+    ///
+    /// for response in block_on(client.send_many(&requests)).expect("Requests failed") {
+    ///     println!("{}", Json::extract(&response.body, "crate.max_version"));
+    /// }
+    /// ```
+    #[cfg(all(feature = "http2", not(target_arch = "wasm32")))]
+    pub async fn send_many(&self, requests: &[Request]) -> Result<Vec<Response>, Box<dyn Error>> {
+        // The multiplexed exchange blocks, so it runs on a helper thread
+        let owned: Vec<Request> = requests.iter().map(|request| http::api_request(request, &self.config).1).collect();
+        let (host, config) = (self.config.api_base().0.to_string(), self.config.clone());
+        let raw = crate::spawn_blocking(move || crate::h2::send_many(&host, &owned, &config).map_err(http::sendable)).await;
+        let Some(raw) = raw.map_err(|e| e as Box<dyn Error>)? else {
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.push(self.send(request).await?);
+            }
+            return Ok(responses);
+        };
+        raw.into_iter()
+            .map(|(head, body)| Ok(Response::from_parts(&head, body)?))
+            .collect()
+    }
+}
+
+/// Sends a single request to crates.io and returns its status, headers, and body.
+///
+/// A shortcut for power users who want raw access without building an
+/// [`HttpClient`]: it uses the process-wide default [`Config`] and, like
+/// [`HttpClient::send`], does not treat non-2xx statuses as errors.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{send_request, block_on};
+///
+/// let response = block_on(send_request("GET", "/api/v1/summary", &[("Accept", "application/json")]))
+///     .expect("Request failed");
+/// println!("HTTP {} ({} headers, {} bytes)", response.status, response.headers.len(), response.body.len());
+/// ```
+pub async fn send_request(method: &str, path: &str, headers: &[(&str, &str)]) -> Result<Response, Box<dyn Error>> {
+    let request = headers.iter().fold(Request::new(method, path), |request, (name, value)| request.header(name, value));
+    let (head, body) = http::fetch_api(&request, crate::default_config()).await?;
+    Ok(Response::from_parts(&head, body)?)
+}
+
+/// An outgoing HTTP request built with method, path, headers, and query parameters.
+///
+/// # Example
+/// ```rust
+/// use crator::Request;
+///
+/// let request = Request::new("GET", "/api/v1/crates")
+///     .query("q", "serde json")
+///     .header("Accept", "application/json");
+///
+/// assert_eq!(request.target(), "/api/v1/crates?q=serde%20json");
+/// assert_eq!(request.header_value("accept"), Some("application/json"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Creates a request with the given method (e.g. `"GET"`) and path (e.g. `"/api/v1/summary"`).
+    pub fn new(method: &str, path: &str) -> Self {
+        Self { method: method.to_ascii_uppercase(), path: path.to_string(), headers: Vec::new(), query: Vec::new() }
+    }
+
+    /// Adds a header. `Host` and `Connection` are managed by crator; a custom
+    /// `User-Agent` replaces crator's default one.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Appends a query parameter; both key and value are percent-encoded.
+    pub fn query(mut self, key: &str, value: &str) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// The request method.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// The request path, without the query string.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The custom headers attached to this request.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the value of the first header named `name` (case-insensitive).
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+
+    /// The request target sent on the request line: the path plus the encoded query string.
+    pub fn target(&self) -> String {
+        if self.query.is_empty() { return self.path.clone(); }
+        let query: Vec<String> = self.query.iter()
+            .map(|(k, v)| format!("{}={}", http::percent_encode(k), http::percent_encode(v)))
+            .collect();
+        let separator = if self.path.contains('?') { '&' } else { '?' };
+        format!("{}{}{}", self.path, separator, query.join("&"))
+    }
+
+    /// A copy of this request with `prefix` prepended to its path.
+    pub(crate) fn prefixed(&self, prefix: &str) -> Request {
+        Request { path: format!("{}{}", prefix, self.path), ..self.clone() }
+    }
+}
+
+/// A buffered HTTP response.
+///
+/// Besides the body, the parsed headers give access to rate-limit headers, dates,
+/// and caching metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Response {
+    /// The numeric HTTP status code.
+    pub status: u16,
+    /// The reason phrase (e.g. `"OK"`).
+    pub reason: String,
+    /// The response headers as `(name, value)` pairs, in the order they were received.
+    pub headers: Vec<(String, String)>,
+    /// The decoded response body.
+    pub body: String,
+}
+
+impl Response {
+    /// Builds a response from a raw head (status line + headers) and its body.
+    pub(crate) fn from_parts(head: &str, body: String) -> Result<Self, FetchError> {
+        let (status, reason) = http::parse_status_line(head)?;
+        Ok(Response { status, reason, headers: http::parse_headers(head), body })
+    }
+
+    /// Returns the value of the first header named `name` (case-insensitive).
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Response;
+    ///
+    /// let response = Response {
+    ///     status: 200,
+    ///     reason: "OK".into(),
+    ///     headers: vec![("ETag".into(), "\"abc\"".into())],
+    ///     body: String::new(),
+    /// };
+    /// assert_eq!(response.header("etag"), Some("\"abc\""));
+    /// assert_eq!(response.header("date"), None);
+    /// ```
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+
+    /// Returns `true` for 2xx status codes.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Deserializes the body into a user-defined type.
+    ///
+    /// Requires the `serde` feature; see [`Json::deserialize`](crate::Json::deserialize)
+    /// to deserialize only a sub-object.
+    #[cfg(feature = "serde")]
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, Box<dyn Error>> {
+        crate::Json::deserialize(&self.body, "")
+    }
+
+    /// Turns a non-2xx response into a [`FetchError::Status`].
+    pub fn error_for_status(self) -> Result<Self, FetchError> {
+        if self.is_success() { Ok(self) } else { Err(FetchError::Status { code: self.status, reason: self.reason }) }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{Cancelled, FetchError};
+use std::collections::HashMap;
+use std::error::Error;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+
+type Outcome = Result<(String, String), Box<dyn Error + Send + Sync>>;
+
+/// One in-flight request and the tasks waiting on its outcome.
+#[derive(Default)]
+struct Flight {
+    outcome: Option<Outcome>,
+    /// Set when the leading task gave up (dropped or cancelled) without an outcome.
+    abandoned: bool,
+    wakers: Vec<Waker>,
+}
+
+/// The requests currently in flight, shared by clones of a [`Config`](crate::Config)
+/// with [`Config::coalesce_requests`](crate::Config::coalesce_requests) enabled.
+#[derive(Clone, Default)]
+pub(crate) struct InFlight {
+    flights: Arc<Mutex<HashMap<String, Arc<Mutex<Flight>>>>>,
+}
+
+impl InFlight {
+    /// Runs `fetch` for `key` unless an identical request is already in flight, in
+    /// which case its outcome is shared instead. If the task running the request is
+    /// dropped or cancelled, one of the waiting tasks takes over.
+    pub(crate) async fn run<F>(&self, key: String, fetch: F) -> Result<(String, String), Box<dyn Error>>
+    where
+        F: Future<Output = Result<(String, String), Box<dyn Error>>>,
+    {
+        let flight = loop {
+            let (flight, leader) = {
+                let mut flights = lock(&self.flights);
+                match flights.get(&key) {
+                    Some(flight) => (Arc::clone(flight), false),
+                    None => {
+                        let flight = Arc::new(Mutex::new(Flight::default()));
+                        flights.insert(key.clone(), Arc::clone(&flight));
+                        (flight, true)
+                    }
+                }
+            };
+            if leader { break flight; }
+            if let Some(outcome) = (Wait { flight }).await { return outcome; }
+        };
+        let mut lead = Lead { in_flight: self, key, flight, outcome: None };
+        let result = fetch.await;
+        let abandoned = result.as_ref().is_err_and(|e| e.is::<Cancelled>() || e.downcast_ref::<io::Error>().is_some_and(|e| e.kind() == io::ErrorKind::Interrupted));
+        if !abandoned {
+            lead.outcome = Some(match &result {
+                Ok(response) => Ok(response.clone()),
+                Err(error) => Err(duplicate(error.as_ref())),
+            });
+        }
+        result
+    }
+}
+
+/// Publishes the leading task's outcome when it completes or is dropped.
+struct Lead<'a> {
+    in_flight: &'a InFlight,
+    key: String,
+    flight: Arc<Mutex<Flight>>,
+    outcome: Option<Outcome>,
+}
+
+impl Drop for Lead<'_> {
+    fn drop(&mut self) {
+        let mut flights = lock(&self.in_flight.flights);
+        if flights.get(&self.key).is_some_and(|flight| Arc::ptr_eq(flight, &self.flight)) { flights.remove(&self.key); }
+        drop(flights);
+        let mut flight = lock(&self.flight);
+        match self.outcome.take() {
+            Some(outcome) => flight.outcome = Some(outcome),
+            None => flight.abandoned = true,
+        }
+        let wakers = std::mem::take(&mut flight.wakers);
+        drop(flight);
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+/// Waits for a flight's outcome; `None` if it was abandoned.
+struct Wait {
+    flight: Arc<Mutex<Flight>>,
+}
+
+impl Future for Wait {
+    type Output = Option<Result<(String, String), Box<dyn Error>>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut flight = lock(&self.flight);
+        if let Some(outcome) = &flight.outcome {
+            return Poll::Ready(Some(match outcome {
+                Ok(response) => Ok(response.clone()),
+                Err(error) => Err(duplicate(error.as_ref())),
+            }));
+        }
+        if flight.abandoned { return Poll::Ready(None); }
+        if !flight.wakers.iter().any(|waker| waker.will_wake(cx.waker())) { flight.wakers.push(cx.waker().clone()); }
+        Poll::Pending
+    }
+}
+
+/// Copies an error for another task, keeping [`FetchError`]s intact and the kind of
+/// I/O errors; anything else becomes an I/O error with the same message.
+pub(crate) fn duplicate(error: &(dyn Error + 'static)) -> Box<dyn Error + Send + Sync> {
+    if let Some(error) = error.downcast_ref::<FetchError>() { return Box::new(error.clone()); }
+    let kind = error.downcast_ref::<io::Error>().map_or(io::ErrorKind::Other, io::Error::kind);
+    Box::new(io::Error::new(kind, error.to_string()))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,530 @@
+#[cfg(not(target_arch = "wasm32"))]
+use native_tls::{Certificate, TlsConnector, TlsConnectorBuilder};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::net::Bind;
+use crate::coalesce::InFlight;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ratelimit::RateLimiter;
+use crate::{AbortHandle, CancelToken, ResponseCache};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{CargoConfig, SourceReplacement};
+use std::io;
+#[cfg(feature = "local-address")]
+use std::net::IpAddr;
+use std::net::SocketAddr;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Lazily built TLS connectors shared between clones of a [`Config`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct Connectors {
+    plain: OnceLock<TlsConnector>,
+    #[cfg(feature = "http2")]
+    alpn: OnceLock<TlsConnector>,
+}
+
+/// A custom DNS resolver: maps a host and port to the socket addresses to connect to.
+pub type Resolver = Arc<dyn Fn(&str, u16) -> io::Result<Vec<SocketAddr>> + Send + Sync>;
+
+/// A registry implementing the crates.io web API, set with [`Config::registry`].
+#[derive(Clone, Debug)]
+pub(crate) struct Registry {
+    /// The host, with an explicit port if any.
+    pub(crate) host: String,
+    /// The path the API lives under, without a trailing slash (e.g. `/artifactory/api/cargo/main`).
+    pub(crate) prefix: String,
+}
+
+impl Registry {
+    /// Splits a URL (the scheme may be left out) into host and path prefix. Any
+    /// scheme is dropped: requests always go over TLS.
+    fn parse(url: &str) -> Self {
+        let url = url.trim();
+        let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let (host, prefix) = url.split_once('/').unwrap_or((url, ""));
+        let prefix = prefix.trim_end_matches('/');
+        let prefix = if prefix.is_empty() { String::new() } else { format!("/{}", prefix) };
+        Registry { host: host.to_string(), prefix }
+    }
+}
+
+/// Where crate metadata is read from instead of the crates.io web API and index.
+#[derive(Clone, Debug)]
+pub(crate) enum IndexSource {
+    /// A remote sparse index, set with [`Config::sparse_index`].
+    Sparse(Registry),
+    /// A local index or `cargo local-registry`, set with [`Config::local_registry`].
+    #[cfg(not(target_arch = "wasm32"))]
+    Local(PathBuf),
+    /// A `cargo vendor` directory, set with [`Config::directory_source`].
+    #[cfg(not(target_arch = "wasm32"))]
+    Directory(PathBuf),
+}
+
+/// Requests kept in flight by batch fetches unless [`Config::max_concurrency`] says otherwise.
+const DEFAULT_MAX_CONCURRENCY: usize = 8;
+
+/// Connection settings used by crator's network functions.
+///
+/// The default configuration trusts the operating system's certificate store and
+/// performs full certificate and hostname verification. Users behind TLS-intercepting
+/// proxies (corporate gateways, debugging tools) can register the proxy's root CA,
+/// or, as a last resort, disable verification entirely.
+///
+/// # Example
+/// ```rust
+/// use crator::Config;
+///
+/// // let pem = std::fs::read("corporate-ca.pem")?;
+/// // let config = Config::new().add_root_certificate(Certificate::from_pem(&pem)?);
+/// let config = Config::new()
+///     .danger_accept_invalid_certs(false)
+///     .max_response_size(16 * 1024 * 1024);
+/// assert!(config.tls_connector().is_ok());
+/// ```
+#[derive(Clone, Default)]
+pub struct Config {
+    #[cfg(not(target_arch = "wasm32"))]
+    root_certificates: Vec<Certificate>,
+    #[cfg(not(target_arch = "wasm32"))]
+    accept_invalid_certs: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    accept_invalid_hostnames: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    connectors: Arc<Connectors>,
+    pub(crate) max_response_size: Option<usize>,
+    pub(crate) attempt_delay: Option<Duration>,
+    pub(crate) dns_ttl: Option<Duration>,
+    pub(crate) resolver: Option<Resolver>,
+    pub(crate) headers: Vec<(String, String)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) bind: Bind,
+    pub(crate) rate_limit_retries: u32,
+    pub(crate) abort: Option<AbortHandle>,
+    pub(crate) cancel: Option<CancelToken>,
+    pub(crate) retain_body: bool,
+    pub(crate) cache: Option<ResponseCache>,
+    pub(crate) in_flight: Option<InFlight>,
+    pub(crate) max_concurrency: Option<usize>,
+    pub(crate) registry: Option<Registry>,
+    pub(crate) token: Option<String>,
+    pub(crate) index_source: Option<IndexSource>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) rate_limiter: Option<RateLimiter>,
+}
+
+impl Config {
+    /// Creates a configuration with secure defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a certificate to the set of trusted roots, in addition to the system store.
+    ///
+    /// Use [`Certificate::from_pem`] or [`Certificate::from_der`] to load the certificate.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_root_certificate(mut self, cert: Certificate) -> Self {
+        self.root_certificates.push(cert);
+        self.connectors = Arc::default();
+        self
+    }
+
+    /// Controls the use of certificate validation.
+    ///
+    /// # Warning
+    /// When set to `true`, **any** certificate is trusted, including expired and
+    /// self-signed ones. This exposes the connection to man-in-the-middle attacks
+    /// and should only be used as a last resort.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self.connectors = Arc::default();
+        self
+    }
+
+    /// Controls the use of hostname verification.
+    ///
+    /// # Warning
+    /// When set to `true`, a certificate issued for any host is accepted.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn danger_accept_invalid_hostnames(mut self, accept: bool) -> Self {
+        self.accept_invalid_hostnames = accept;
+        self.connectors = Arc::default();
+        self
+    }
+
+    /// Adds a header sent with every request (e.g. `Accept` or a tracing ID).
+    ///
+    /// A header of the same name set on an individual [`Request`](crate::Request)
+    /// takes precedence. Setting `User-Agent` replaces crator's default one, which
+    /// crates.io's crawling policy asks to identify your application.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Config;
+    ///
+    /// let config = Config::new()
+    ///     .header("User-Agent", "my-dashboard/0.1 (ops@example.com)")
+    ///     .header("Accept", "application/json");
+    /// # let _ = config;
+    /// ```
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Retries `429 Too Many Requests` responses up to `max_retries` times.
+    ///
+    /// Before each retry crator sleeps for the `Retry-After` delay sent by crates.io
+    /// (or backs off exponentially from one second when absent). Disabled by default,
+    /// in which case a 429 surfaces as [`FetchError::RateLimited`](crate::FetchError::RateLimited).
+    pub fn retry_rate_limited(mut self, max_retries: u32) -> Self {
+        self.rate_limit_retries = max_retries;
+        self
+    }
+
+    /// Registers every connection opened with this configuration on `handle`, so
+    /// in-flight fetches can be cancelled from another thread with [`AbortHandle::abort`].
+    pub fn abort_handle(mut self, handle: &AbortHandle) -> Self {
+        self.abort = Some(handle.clone());
+        self
+    }
+
+    /// Ties every fetch made with this configuration to `token`.
+    ///
+    /// Once the token is cancelled, pending fetches resolve to a [`Cancelled`](crate::Cancelled)
+    /// error the next time they are polled, and new ones fail immediately.
+    pub fn cancel_token(mut self, token: &CancelToken) -> Self {
+        self.cancel = Some(token.clone());
+        self
+    }
+
+    /// Serves `GET` requests from `cache` while its entries are fresh, and stores
+    /// successful responses in it.
+    ///
+    /// See [`ResponseCache`] for how entries are keyed and expire.
+    pub fn cache(mut self, cache: &ResponseCache) -> Self {
+        self.cache = Some(cache.clone());
+        self
+    }
+
+    /// Merges identical `GET` requests made at the same time with this configuration
+    /// (or its clones) into a single HTTP request whose response they all share, so
+    /// dependency scans that reach the same crate from many places fetch it once.
+    ///
+    /// A request only joins one that is still in flight; later ones go out again (or
+    /// hit the [`cache`](Config::cache), if set). Requests differing in headers or
+    /// query string aren't merged.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, crate_data_with, join_all, Config};
+    ///
+    /// let config = Config::new().coalesce_requests(true);
+    /// // One request to crates.io, three results
+    /// let results = block_on(join_all((0..3).map(|_| crate_data_with("serde", &config))));
+    /// assert!(results.iter().all(Result::is_ok));
+    /// ```
+    pub fn coalesce_requests(mut self, enabled: bool) -> Self {
+        self.in_flight = enabled.then(InFlight::default);
+        self
+    }
+
+    /// Sends crates.io API requests to another registry implementing the same web API
+    /// (`/api/v1/...`), such as a private Kellnr, Artifactory, or Cloudsmith registry,
+    /// or a mirror.
+    ///
+    /// `url` is the registry's `https://` base URL and may include a port and a path
+    /// prefix; the scheme may be left out. Only TLS is supported: the host of an
+    /// `http://` URL is still contacted over HTTPS. Requests to other hosts (e.g. the
+    /// RustSec advisory database) are unaffected.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, crate_data_with, Config};
+    ///
+    /// let config = Config::new().registry("https://artifactory.example.com:8443/artifactory/api/cargo/main");
+    /// let info = block_on(crate_data_with("internal-utils", &config)).expect("Failed to fetch crate data");
+    /// println!("Latest: v{}", info.latest);
+    /// ```
+    pub fn registry(mut self, url: &str) -> Self {
+        self.registry = Some(Registry::parse(url));
+        self
+    }
+
+    /// Sends `token` as the `Authorization` header of crates.io API requests (or those
+    /// to the [`Config::registry`]), for endpoints that need a logged-in user such as
+    /// owner management or `/api/v1/me`.
+    ///
+    /// Unlike a [`Config::header`], the token never reaches other hosts (GitHub, the
+    /// sparse index, the advisory database). Load the one saved by `cargo login` with
+    /// [`Credentials`](crate::Credentials) rather than pasting it into code; a request
+    /// carrying its own `Authorization` header keeps it.
+    pub fn token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// The host and path prefix crates.io API requests go to.
+    pub(crate) fn api_base(&self) -> (&str, &str) {
+        self.registry.as_ref().map_or(("crates.io", ""), |registry| (&registry.host, &registry.prefix))
+    }
+
+    /// Reads the sparse index from `url` (e.g. `"sparse+https://mirror.example.com/index/"`,
+    /// the `sparse+` prefix being optional) instead of `index.crates.io`, like a Cargo
+    /// source replacement pointing at a mirror. As with [`Config::registry`], the index
+    /// is always fetched over HTTPS.
+    ///
+    /// Version dependencies and the version listings behind the lockfile reports and
+    /// [`dependency_tree_with`](crate::dependency_tree_with) then come from the index
+    /// too, as with [`Config::local_registry`], so nothing is asked of crates.io.
+    pub fn sparse_index(mut self, url: &str) -> Self {
+        let url = url.trim();
+        self.index_source = Some(IndexSource::Sparse(Registry::parse(url.strip_prefix("sparse+").unwrap_or(url))));
+        self
+    }
+
+    /// Reads crate metadata from a local index instead of the network: a sparse-index
+    /// checkout (the directory with `config.json` and `se/rd/serde`-style files) or a
+    /// `cargo local-registry` directory (an `index/` subdirectory next to the `.crate`
+    /// files).
+    ///
+    /// Index reads ([`index_entries_with`](crate::index_entries_with)), version
+    /// dependencies ([`crate_dependencies_with`](crate::crate_dependencies_with)) and
+    /// the version listings behind [`outdated_report_with`](crate::outdated_report_with),
+    /// [`msrv_report_with`](crate::msrv_report_with),
+    /// [`license_report_with`](crate::license_report_with) and
+    /// [`dependency_tree_with`](crate::dependency_tree_with) then work fully offline.
+    /// The index doesn't record licenses, publication dates or download counts, so
+    /// those come out empty; archive sizes are read from the `.crate` files when present.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, outdated_report_with, Config, Lockfile};
+    ///
+    /// let config = Config::new().local_registry("/srv/crates-mirror");
+    /// let lockfile = Lockfile::from_path("Cargo.lock").expect("Failed to read lockfile");
+    /// print!("{}", block_on(outdated_report_with(&lockfile, &config)));
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn local_registry(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.index_source = Some(IndexSource::Local(dir.into()));
+        self
+    }
+
+    /// Reads crate metadata from a `cargo vendor` directory (one subdirectory per
+    /// crate version, named `name` or `name-version`), like [`Config::local_registry`].
+    ///
+    /// Only the vendored versions are known, so every locked crate looks up to date;
+    /// licenses and `rust-version`s come from the vendored manifests, which suits
+    /// [`license_report_with`](crate::license_report_with) and
+    /// [`dependency_tree_with`](crate::dependency_tree_with) in air-gapped builds.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn directory_source(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.index_source = Some(IndexSource::Directory(dir.into()));
+        self
+    }
+
+    /// Follows the source replacement for crates.io in a Cargo configuration (see
+    /// [`CargoConfig`](crate::CargoConfig)), so crator reads from the same mirror,
+    /// local registry or vendor directory as Cargo would. Replacements by a git index
+    /// or a plain-HTTP sparse index aren't supported and leave the configuration
+    /// unchanged.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, license_report_with, CargoConfig, Config, Lockfile};
+    ///
+    /// let cargo = CargoConfig::load(".").expect("Failed to read Cargo configuration");
+    /// let config = Config::new().cargo_config(&cargo);
+    /// let lockfile = Lockfile::from_path("Cargo.lock").expect("Failed to read lockfile");
+    /// print!("{}", block_on(license_report_with(&lockfile, &config)));
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cargo_config(self, cargo: &CargoConfig) -> Self {
+        match cargo.replacement("crates-io") {
+            Some(SourceReplacement::Registry(index)) if index.starts_with("sparse+https://") => self.sparse_index(&index),
+            Some(SourceReplacement::LocalRegistry(dir)) => self.local_registry(dir),
+            Some(SourceReplacement::Directory(dir)) => self.directory_source(dir),
+            _ => self,
+        }
+    }
+
+    /// Caps how many requests batch fetches such as [`crates_data_with`](crate::crates_data_with)
+    /// keep in flight at once. Defaults to 8; `0` is treated as 1.
+    pub fn max_concurrency(mut self, requests: usize) -> Self {
+        self.max_concurrency = Some(requests.max(1));
+        self
+    }
+
+    /// Spaces out the HTTP requests made with this configuration (and its clones) to
+    /// at most `requests` per `period`, in bursts of up to `requests`, so long scans
+    /// stay within crates.io's crawler policy (one request per second) on their own.
+    ///
+    /// Requests over the limit wait for their turn without blocking the executor.
+    /// Cache hits and [coalesced](Config::coalesce_requests) requests don't count, and
+    /// each retry of a rate-limited response does. A zero `period` disables the limit,
+    /// and `0` requests is treated as 1.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, crates_data_with, Config};
+    /// use std::time::Duration;
+    ///
+    /// let config = Config::new().rate_limit(1, Duration::from_secs(1));
+    /// // Takes about four seconds
+    /// let results = block_on(crates_data_with(&["serde", "rand", "tokio", "regex", "syn"], &config));
+    /// # let _ = results;
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn rate_limit(mut self, requests: u32, period: Duration) -> Self {
+        self.rate_limiter = (!period.is_zero()).then(|| RateLimiter::new(requests, period));
+        self
+    }
+
+    /// The in-flight limit of batch fetches.
+    pub(crate) fn concurrency(&self) -> usize {
+        self.max_concurrency.unwrap_or(DEFAULT_MAX_CONCURRENCY)
+    }
+
+    /// Keeps the raw JSON body on every [`CrateInfo`](crate::CrateInfo) fetched with this
+    /// configuration ([`CrateInfo::raw_body`](crate::CrateInfo::raw_body)).
+    ///
+    /// Lets callers run further [`Json`](crate::Json) queries for fields crator doesn't
+    /// model without fetching the crate again. Off by default, since crate payloads can
+    /// be large.
+    pub fn retain_body(mut self, retain: bool) -> Self {
+        self.retain_body = retain;
+        self
+    }
+
+    /// Caps how many body bytes crator will buffer from a single response.
+    ///
+    /// Responses exceeding the cap fail with [`FetchError::ResponseTooLarge`](crate::FetchError::ResponseTooLarge)
+    /// instead of growing memory without bound, which protects long-running services
+    /// from pathological payloads. Unlimited by default. Streaming APIs such as
+    /// [`fetch_stream`](crate::fetch_stream) do not buffer and are not affected.
+    pub fn max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Sets the head start each connection attempt gets before the next address is tried.
+    ///
+    /// crator resolves every address of the host and races them IPv6/IPv4-interleaved
+    /// ("Happy Eyeballs", RFC 8305). Defaults to 250 ms.
+    pub fn happy_eyeballs_delay(mut self, delay: Duration) -> Self {
+        self.attempt_delay = Some(delay);
+        self
+    }
+
+    /// Sets how long resolved addresses are cached and reused across requests.
+    ///
+    /// The cache is shared by the whole process, so batch fetches and watch mode don't
+    /// re-resolve the registry host on every request. Defaults to 60 seconds;
+    /// `Duration::ZERO` disables caching.
+    pub fn dns_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.dns_ttl = Some(ttl);
+        self
+    }
+
+    /// Replaces the system resolver with a custom function (e.g. DNS-over-HTTPS or a pinned IP).
+    ///
+    /// Useful in sandboxed and containerized environments where the system resolver is
+    /// unavailable or untrusted. Answers from a custom resolver bypass crator's DNS
+    /// cache; the resolver is responsible for its own caching.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Config;
+    /// use std::net::SocketAddr;
+    ///
+    /// // Pin crates.io to a known address
+    /// let config = Config::new().resolver(|_host, port| {
+    ///     Ok(vec![SocketAddr::from(([127, 0, 0, 1], port))])
+    /// });
+    /// # let _ = config;
+    /// ```
+    pub fn resolver<F>(mut self, resolve: F) -> Self
+    where
+        F: Fn(&str, u16) -> io::Result<Vec<SocketAddr>> + Send + Sync + 'static,
+    {
+        self.resolver = Some(Arc::new(resolve));
+        self
+    }
+
+    /// Binds outgoing sockets to the given local IP address before connecting.
+    ///
+    /// Needed on multi-homed hosts and VPN split-tunnel setups. Only remote
+    /// addresses of the same family (IPv4/IPv6) are tried.
+    ///
+    /// Requires the `local-address` feature.
+    #[cfg(all(feature = "local-address", not(target_arch = "wasm32")))]
+    pub fn local_address(mut self, address: IpAddr) -> Self {
+        self.bind.address = Some(address);
+        self
+    }
+
+    /// Binds outgoing sockets to a network interface (e.g. `"wg0"`) via `SO_BINDTODEVICE`.
+    ///
+    /// Requires the `local-address` feature; only available on Linux, Android and Fuchsia.
+    #[cfg(all(feature = "local-address", any(target_os = "android", target_os = "fuchsia", target_os = "linux")))]
+    pub fn interface(mut self, name: &str) -> Self {
+        self.bind.interface = Some(name.to_string());
+        self
+    }
+
+    /// Returns the [`TlsConnector`] reflecting this configuration.
+    ///
+    /// Building a connector (which loads the system certificate store) is expensive,
+    /// so it is built once and shared by this configuration and all of its clones,
+    /// including every [`HttpClient`](crate::HttpClient) created from it. Changing a
+    /// TLS option through the builder methods starts a fresh connector.
+    ///
+    /// TLS sessions aren't resumed: `native-tls` exposes no session cache, and with
+    /// its OpenSSL backend reusing a connector doesn't resume sessions by itself, so
+    /// every new connection still performs a full handshake. Resumption would need a
+    /// TLS library crator doesn't depend on. With the `http2` feature,
+    /// `HttpClient::send_many` avoids repeated handshakes by multiplexing requests
+    /// over one connection instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tls_connector(&self) -> Result<TlsConnector, native_tls::Error> {
+        Self::cached(&self.connectors.plain, || self.tls_builder().build())
+    }
+
+    /// Returns a [`TlsConnector`] that offers the given ALPN protocols during the handshake.
+    ///
+    /// The connector is cached like [`Config::tls_connector`]; the protocol list is
+    /// fixed per configuration.
+    #[cfg(all(feature = "http2", not(target_arch = "wasm32")))]
+    pub(crate) fn tls_connector_alpn(&self, protocols: &[&str]) -> Result<TlsConnector, native_tls::Error> {
+        Self::cached(&self.connectors.alpn, || self.tls_builder().request_alpns(protocols).build())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn cached(
+        cell: &OnceLock<TlsConnector>,
+        build: impl FnOnce() -> Result<TlsConnector, native_tls::Error>,
+    ) -> Result<TlsConnector, native_tls::Error> {
+        if let Some(connector) = cell.get() { return Ok(connector.clone()); }
+        let connector = build()?;
+        Ok(cell.get_or_init(|| connector).clone())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn tls_builder(&self) -> TlsConnectorBuilder {
+        let mut builder = TlsConnector::builder();
+        for cert in &self.root_certificates {
+            builder.add_root_certificate(cert.clone());
+        }
+        builder
+            .danger_accept_invalid_certs(self.accept_invalid_certs)
+            .danger_accept_invalid_hostnames(self.accept_invalid_hostnames);
+        builder
+    }
+}
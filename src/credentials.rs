@@ -0,0 +1,123 @@
+use crate::cargo_config::cargo_home;
+use crate::{Toml, TomlError, Value};
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+
+/// Registry tokens saved by `cargo login`, for [`Config::token`](crate::Config::token).
+///
+/// Debug output lists which registries have a token without printing the tokens.
+///
+/// # Example
+/// ```rust
+/// use crator::Credentials;
+///
+/// let credentials = Credentials::parse(r#"
+/// [registry]
+/// token = "cio-secret"
+///
+/// [registries.my-company]
+/// token = "Bearer kellnr-secret"
+/// "#).expect("Invalid credentials");
+///
+/// assert_eq!(credentials.token(), Some("cio-secret"));
+/// assert_eq!(credentials.registry_token("my-company"), Some("Bearer kellnr-secret"));
+/// assert_eq!(credentials.registry_token("other"), None);
+/// assert!(!format!("{:?}", credentials).contains("secret"));
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Credentials {
+    registry: Option<String>,
+    registries: Vec<(String, String)>,
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("registry", &self.registry.as_ref().map(|_| "<token>"))
+            .field("registries", &self.registries.iter().map(|(name, _)| name).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Compares registry names the way Cargo maps them to environment variables.
+fn env_name(name: &str) -> String {
+    name.to_ascii_uppercase().replace('-', "_")
+}
+
+impl Credentials {
+    /// Parses the text of a `credentials.toml` file.
+    ///
+    /// # Errors
+    /// Fails if the text isn't valid TOML.
+    pub fn parse(text: &str) -> Result<Self, TomlError> {
+        let document = Toml::parse(text)?;
+        let token = |table: &Value| table.get("token").and_then(Value::as_str).map(str::to_string);
+        Ok(Credentials {
+            registry: document.get("registry").and_then(token),
+            registries: document.get("registries").and_then(Value::as_object).unwrap_or_default().iter()
+                .filter_map(|(name, table)| Some((name.clone(), token(table)?)))
+                .collect(),
+        })
+    }
+
+    /// Reads the tokens saved by `cargo login`, the way Cargo does: from
+    /// `$CARGO_HOME/credentials.toml` (or the legacy `credentials`), with
+    /// `CARGO_HOME` defaulting to `~/.cargo`. The `CARGO_REGISTRY_TOKEN` and
+    /// `CARGO_REGISTRIES_<NAME>_TOKEN` environment variables take precedence.
+    ///
+    /// A missing file isn't an error: the credentials are then empty, or only hold
+    /// the environment's tokens.
+    ///
+    /// # Errors
+    /// Fails if no home directory can be found, or if the file exists but can't be
+    /// read or parsed.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, Config, Credentials, HttpClient};
+    ///
+    /// let credentials = Credentials::load().expect("Failed to read credentials");
+    /// let token = credentials.token().expect("Run `cargo login` first");
+    /// let client = HttpClient::with_config(Config::new().token(token));
+    /// let response = block_on(client.send(&client.get("/api/v1/me"))).expect("Request failed");
+    /// println!("HTTP {}", response.status);
+    /// ```
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let home = cargo_home().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no Cargo home directory (set CARGO_HOME or HOME)"))?;
+        let mut credentials = Credentials::default();
+        for file in ["credentials.toml", "credentials"] {
+            let path = home.join(file);
+            match fs::read_to_string(&path) {
+                Ok(text) => {
+                    credentials = Credentials::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+                    break;
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(io::Error::new(e.kind(), format!("{}: {}", path.display(), e)).into()),
+            }
+        }
+        for (key, value) in env::vars() {
+            if key == "CARGO_REGISTRY_TOKEN" {
+                credentials.registry = Some(value);
+            } else if let Some(name) = key.strip_prefix("CARGO_REGISTRIES_").and_then(|rest| rest.strip_suffix("_TOKEN")) {
+                credentials.registries.retain(|(registry, _)| env_name(registry) != name);
+                credentials.registries.push((name.to_string(), value));
+            }
+        }
+        Ok(credentials)
+    }
+
+    /// The crates.io token, if any.
+    pub fn token(&self) -> Option<&str> {
+        self.registry.as_deref()
+    }
+
+    /// The token of the registry named `name` in `.cargo/config.toml`, if any.
+    pub fn registry_token(&self, name: &str) -> Option<&str> {
+        let name = env_name(name);
+        self.registries.iter().find(|(registry, _)| env_name(registry) == name).map(|(_, token)| token.as_str())
+    }
+}
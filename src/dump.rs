@@ -0,0 +1,321 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A streaming reader of CSV records (RFC 4180) whose first line names the columns,
+/// as in the crates.io database dump.
+///
+/// Quoted fields may span lines and escape quotes as `""`. Records are read one at a
+/// time, so tables with millions of rows never sit in memory at once.
+///
+/// # Example
+/// ```rust
+/// use crator::CsvReader;
+///
+/// let csv = "id,name,description\n1,serde,\"A generic \"\"serialization\"\" framework\"\n2,rand,\"Random\nnumbers\"\n";
+/// let mut reader = CsvReader::new(csv.as_bytes()).expect("Missing header");
+/// assert_eq!(reader.headers(), ["id", "name", "description"]);
+///
+/// let first = reader.next().unwrap().expect("Invalid record");
+/// assert_eq!(first.get("description"), Some("A generic \"serialization\" framework"));
+/// let second = reader.next().unwrap().expect("Invalid record");
+/// assert_eq!(second.get("description"), Some("Random\nnumbers"));
+/// assert!(reader.next().is_none());
+/// ```
+pub struct CsvReader<R> {
+    reader: R,
+    headers: Arc<[String]>,
+    line: usize,
+}
+
+/// One record of a [`CsvReader`], with its fields addressable by column name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRecord {
+    headers: Arc<[String]>,
+    fields: Vec<String>,
+}
+
+impl CsvRecord {
+    /// The field in the column named `column`, if the table has one.
+    pub fn get(&self, column: &str) -> Option<&str> {
+        let index = self.headers.iter().position(|header| header == column)?;
+        self.fields.get(index).map(String::as_str)
+    }
+
+    /// The fields in column order.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    fn text(&self, column: &str) -> String {
+        self.get(column).unwrap_or_default().to_string()
+    }
+
+    /// The field, or `None` when empty (the dump's `NULL`).
+    fn optional(&self, column: &str) -> Option<String> {
+        self.get(column).filter(|value| !value.is_empty()).map(str::to_string)
+    }
+
+    fn number(&self, column: &str) -> io::Result<u64> {
+        let value = self.get(column).unwrap_or_default();
+        value.parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("column `{}`: `{}` is not a number", column, value)))
+    }
+}
+
+impl<R: BufRead> CsvReader<R> {
+    /// Reads the header line of `reader`.
+    ///
+    /// # Errors
+    /// Fails if the input is empty or can't be read.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut line = 0;
+        let headers = read_record(&mut reader, &mut line)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "missing CSV header"))?;
+        Ok(CsvReader { reader, headers: headers.into(), line })
+    }
+
+    /// The column names.
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+}
+
+impl<R: BufRead> Iterator for CsvReader<R> {
+    type Item = io::Result<CsvRecord>;
+
+    fn next(&mut self) -> Option<io::Result<CsvRecord>> {
+        read_record(&mut self.reader, &mut self.line)
+            .map(|fields| fields.map(|fields| CsvRecord { headers: Arc::clone(&self.headers), fields }))
+            .transpose()
+    }
+}
+
+/// Reads one record, or `None` at the end of the input; `line` counts the lines read.
+fn read_record<R: BufRead>(reader: &mut R, line: &mut usize) -> io::Result<Option<Vec<String>>> {
+    let mut text = String::new();
+    loop {
+        let start = text.len();
+        if reader.read_line(&mut text)? == 0 {
+            if text.is_empty() { return Ok(None); }
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("line {}: unterminated quoted field", line)));
+        }
+        *line += 1;
+        // A record ends at a line break outside quotes
+        if text.matches('"').count().is_multiple_of(2) {
+            if text.trim_end_matches(['\r', '\n']).is_empty() && start == 0 {
+                text.clear();
+                continue;
+            }
+            break;
+        }
+    }
+    let text = text.strip_suffix('\n').unwrap_or(&text);
+    let text = text.strip_suffix('\r').unwrap_or(text);
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    Ok(Some(fields))
+}
+
+/// A crate from the dump's `crates.csv`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DumpCrate {
+    /// The crate's database ID, referenced by the other tables.
+    pub id: u64,
+    /// The crate name.
+    pub name: String,
+    /// The one-line description, if any.
+    pub description: Option<String>,
+    /// The source repository URL, if any.
+    pub repository: Option<String>,
+    /// The project homepage URL, if any.
+    pub homepage: Option<String>,
+    /// The creation timestamp, as written in the dump (e.g. "2014-12-13 22:10:11.303311").
+    pub created_at: String,
+    /// The timestamp of the last update.
+    pub updated_at: String,
+}
+
+/// A published version from the dump's `versions.csv`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DumpVersion {
+    /// The version's database ID.
+    pub id: u64,
+    /// The ID of its [`DumpCrate`].
+    pub crate_id: u64,
+    /// The version number (e.g. "1.0.210").
+    pub num: String,
+    /// Whether the version was withdrawn with `cargo yank`.
+    pub yanked: bool,
+    /// The software license, if declared.
+    pub license: Option<String>,
+    /// The number of downloads of this version.
+    pub downloads: u64,
+    /// The minimum supported Rust version (`rust-version`), if declared.
+    pub rust_version: Option<String>,
+    /// The size of the `.crate` archive in bytes, if known.
+    pub crate_size: Option<u64>,
+    /// The publication timestamp.
+    pub created_at: String,
+}
+
+/// A category from the dump's `categories.csv`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DumpCategory {
+    /// The category's database ID.
+    pub id: u64,
+    /// The slug used in manifests (e.g. "encoding" or "web-programming::http-client").
+    pub slug: String,
+    /// The display name (e.g. "HTTP client").
+    pub name: String,
+    /// How many crates are in it, subcategories included.
+    pub crates_cnt: u64,
+}
+
+/// An extracted crates.io database dump, read table by table.
+///
+/// crates.io publishes the whole registry every day at
+/// <https://static.crates.io/db-dump.tar.gz>; extract it with `tar xzf` and point
+/// [`DbDump::open`] at the resulting directory. Ecosystem-wide questions (every
+/// crate in a category, download totals, license shares) then take one pass over a
+/// few files instead of millions of API calls. Each table is streamed record by
+/// record.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::DbDump;
+///
+/// let dump = DbDump::open("2026-10-15-020017").expect("Failed to open dump");
+/// let mut yanked = 0;
+/// for version in dump.versions().expect("Failed to read versions.csv") {
+///     yanked += version.expect("Invalid record").yanked as u64;
+/// }
+/// println!("{} yanked versions", yanked);
+/// println!("{:?}", dump.crates_in_category("cryptography").expect("Failed to read categories"));
+/// ```
+pub struct DbDump {
+    data: PathBuf,
+}
+
+impl DbDump {
+    /// Opens an extracted dump: the directory holding `data/` (and `metadata.json`),
+    /// or the `data/` directory itself.
+    ///
+    /// # Errors
+    /// Fails if the directory has no `crates.csv`.
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        let nested = dir.join("data");
+        let data = if nested.join("crates.csv").is_file() { nested } else { dir.to_path_buf() };
+        if !data.join("crates.csv").is_file() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("{}: no crates.csv in this directory or its data/", dir.display())));
+        }
+        Ok(DbDump { data })
+    }
+
+    /// Streams any table of the dump by name (e.g. `"dependencies"` for
+    /// `data/dependencies.csv`), for columns the typed readers leave out.
+    pub fn table(&self, name: &str) -> io::Result<CsvReader<BufReader<File>>> {
+        let path = self.data.join(format!("{}.csv", name));
+        let file = File::open(&path).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        CsvReader::new(BufReader::with_capacity(1 << 16, file))
+    }
+
+    /// Streams `crates.csv`.
+    pub fn crates(&self) -> io::Result<impl Iterator<Item = io::Result<DumpCrate>>> {
+        Ok(self.table("crates")?.map(|record| {
+            let record = record?;
+            Ok(DumpCrate {
+                id: record.number("id")?,
+                name: record.text("name"),
+                description: record.optional("description"),
+                repository: record.optional("repository"),
+                homepage: record.optional("homepage"),
+                created_at: record.text("created_at"),
+                updated_at: record.text("updated_at"),
+            })
+        }))
+    }
+
+    /// Streams `versions.csv`.
+    pub fn versions(&self) -> io::Result<impl Iterator<Item = io::Result<DumpVersion>>> {
+        Ok(self.table("versions")?.map(|record| {
+            let record = record?;
+            Ok(DumpVersion {
+                id: record.number("id")?,
+                crate_id: record.number("crate_id")?,
+                num: record.text("num"),
+                yanked: record.get("yanked") == Some("t"),
+                license: record.optional("license"),
+                downloads: record.number("downloads").unwrap_or(0),
+                rust_version: record.optional("rust_version"),
+                crate_size: record.number("crate_size").ok(),
+                created_at: record.text("created_at"),
+            })
+        }))
+    }
+
+    /// Streams `categories.csv`.
+    pub fn categories(&self) -> io::Result<impl Iterator<Item = io::Result<DumpCategory>>> {
+        Ok(self.table("categories")?.map(|record| {
+            let record = record?;
+            Ok(DumpCategory {
+                id: record.number("id")?,
+                slug: record.text("slug"),
+                name: record.text("category"),
+                crates_cnt: record.number("crates_cnt").unwrap_or(0),
+            })
+        }))
+    }
+
+    /// The all-time downloads of every crate, by crate ID, from `crate_downloads.csv`.
+    pub fn crate_downloads(&self) -> io::Result<HashMap<u64, u64>> {
+        self.table("crate_downloads")?
+            .map(|record| {
+                let record = record?;
+                Ok((record.number("crate_id")?, record.number("downloads")?))
+            })
+            .collect()
+    }
+
+    /// The names of the crates in a category (by slug, e.g. `"cryptography"`) or any
+    /// of its subcategories, sorted.
+    pub fn crates_in_category(&self, slug: &str) -> io::Result<Vec<String>> {
+        let subcategory = format!("{}::", slug);
+        let mut categories = HashSet::new();
+        for category in self.categories()? {
+            let category = category?;
+            if category.slug == slug || category.slug.starts_with(&subcategory) { categories.insert(category.id); }
+        }
+        let mut crate_ids = HashSet::new();
+        for record in self.table("crates_categories")? {
+            let record = record?;
+            if categories.contains(&record.number("category_id")?) { crate_ids.insert(record.number("crate_id")?); }
+        }
+        let mut names = Vec::new();
+        for krate in self.crates()? {
+            let krate = krate?;
+            if crate_ids.contains(&krate.id) { names.push(krate.name); }
+        }
+        names.sort();
+        Ok(names)
+    }
+}
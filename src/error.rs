@@ -0,0 +1,207 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// Errors raised by crator's network layer.
+///
+/// Functions such as [`crate_data`](crate::crate_data) return `Box<dyn Error>`; a
+/// `FetchError` can be recovered from it with [`downcast_ref`](https://doc.rust-lang.org/std/error/trait.Error.html#method.downcast_ref).
+///
+/// # Example
+/// ```rust
+/// use crator::FetchError;
+///
+/// let err: Box<dyn std::error::Error> = Box::new(FetchError::Status { code: 404, reason: "Not Found".into() });
+/// match err.downcast_ref::<FetchError>() {
+///     Some(FetchError::Status { code, .. }) => assert_eq!(*code, 404),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// The server answered with a non-2xx status code (e.g. `404` for an unknown crate).
+    Status {
+        /// The numeric HTTP status code.
+        code: u16,
+        /// The reason phrase sent by the server (e.g. `"Not Found"`).
+        reason: String,
+    },
+    /// crates.io rate-limited the client (`429 Too Many Requests`).
+    ///
+    /// Enable [`Config::retry_rate_limited`](crate::Config::retry_rate_limited) to
+    /// sleep and retry automatically instead.
+    RateLimited {
+        /// How long the server asked to wait, from the `Retry-After` header.
+        retry_after: Option<Duration>,
+    },
+    /// The fetch was cancelled through an [`AbortHandle`](crate::AbortHandle).
+    Aborted,
+    /// The response did not start with a valid HTTP status line.
+    MalformedResponse,
+    /// The response body exceeded [`Config::max_response_size`](crate::Config::max_response_size).
+    ResponseTooLarge {
+        /// The configured limit in bytes.
+        limit: usize,
+    },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Status { code, reason } => write!(f, "HTTP {} {}", code, reason),
+            FetchError::RateLimited { retry_after: Some(delay) } => write!(f, "rate limited, retry after {}s", delay.as_secs()),
+            FetchError::RateLimited { retry_after: None } => write!(f, "rate limited"),
+            FetchError::Aborted => write!(f, "request aborted"),
+            FetchError::MalformedResponse => write!(f, "malformed HTTP response"),
+            FetchError::ResponseTooLarge { limit } => write!(f, "response body exceeds the {} byte limit", limit),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+/// Errors returned by [`Json::try_extract`](crate::Json::try_extract), [`Json::parse`](crate::Json::parse),
+/// and the strict validation functions.
+///
+/// # Example
+/// ```rust
+/// use crator::{Json, JsonError};
+///
+/// let body = r#"{"crate": {"name": "crator", "keywords": ["json"]}}"#;
+/// assert_eq!(Json::try_extract(body, "crate.name"), Ok("crator".to_string()));
+/// assert_eq!(Json::try_extract(body, "crate.homepage"), Err(JsonError::PathNotFound { path: "crate.homepage".into() }));
+/// assert!(matches!(Json::try_extract(body, "crate.name.0"), Err(JsonError::TypeMismatch { .. })));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonError {
+    /// No value exists at the given path.
+    PathNotFound {
+        /// The path (up to the failing segment) that could not be resolved.
+        path: String,
+    },
+    /// A path segment addressed a value of the wrong type (e.g. an index into an object).
+    TypeMismatch {
+        /// The path (up to the failing segment).
+        path: String,
+        /// The JSON type the segment required (`"array"` or `"object"`).
+        expected: &'static str,
+    },
+    /// The document (or the addressed value) is not well-formed JSON.
+    Malformed {
+        /// The path (up to the failing segment), empty for the document itself.
+        path: String,
+    },
+    /// The value at `path` could not be parsed into the requested type
+    /// (see [`Json::extract_as`](crate::Json::extract_as)).
+    InvalidValue {
+        /// The path of the value.
+        path: String,
+        /// The extracted text that failed to parse.
+        value: String,
+        /// The name of the requested type.
+        target: &'static str,
+    },
+    /// Strict validation found a syntax error (see [`Json::validate`](crate::Json::validate)).
+    Syntax {
+        /// Byte offset of the error in the document.
+        offset: usize,
+        /// What was wrong (e.g. `"invalid escape sequence"`).
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::PathNotFound { path } => write!(f, "path `{}` not found", path),
+            JsonError::TypeMismatch { path, expected } => write!(f, "expected an {} at `{}`", expected, path),
+            JsonError::Malformed { path } if path.is_empty() => write!(f, "malformed JSON document"),
+            JsonError::Malformed { path } => write!(f, "malformed JSON value at `{}`", path),
+            JsonError::InvalidValue { path, value, target } => write!(f, "cannot parse `{}` at `{}` as {}", value, path, target),
+            JsonError::Syntax { offset, reason } => write!(f, "invalid JSON at byte {}: {}", offset, reason),
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+/// Returned by [`Toml::parse`](crate::Toml::parse) for a document it can't read.
+///
+/// # Example
+/// ```rust
+/// use crator::{Toml, TomlError};
+///
+/// let err = Toml::parse("[package]\nname = \"demo\nversion = \"0.1.0\"").unwrap_err();
+/// assert_eq!(err, TomlError { line: 2, reason: "unterminated string" });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TomlError {
+    /// The 1-based line of the error.
+    pub line: usize,
+    /// What was wrong (e.g. `"duplicate key"`).
+    pub reason: &'static str,
+}
+
+impl fmt::Display for TomlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid TOML at line {}: {}", self.line, self.reason)
+    }
+}
+
+impl Error for TomlError {}
+
+/// Returned when a future doesn't complete within its time limit
+/// (see [`timeout`](crate::timeout) and [`block_on_timeout`](crate::block_on_timeout)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "operation timed out")
+    }
+}
+
+impl Error for TimedOut {}
+
+/// Returned by [`Runtime::block_on`](crate::Runtime::block_on) when a future exhausts
+/// the limits of its [`ExecutorConfig`](crate::ExecutorConfig) without completing.
+///
+/// The future is dropped, so a long-running process can log the failure and move on
+/// instead of hanging on a future that never resolves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutorError {
+    /// The future was still pending after [`max_polls`](crate::ExecutorConfig::max_polls) polls.
+    Budget {
+        /// The configured poll budget.
+        polls: u64,
+    },
+    /// The future was still pending after [`max_duration`](crate::ExecutorConfig::max_duration).
+    Deadline {
+        /// The configured time limit.
+        limit: Duration,
+    },
+}
+
+impl fmt::Display for ExecutorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutorError::Budget { polls } => write!(f, "future still pending after {} polls", polls),
+            ExecutorError::Deadline { limit } => write!(f, "future still pending after {:?}", limit),
+        }
+    }
+}
+
+impl Error for ExecutorError {}
+
+/// Returned when a future is stopped through a [`CancelToken`](crate::CancelToken)
+/// (see [`cancellable`](crate::cancellable) and [`Config::cancel_token`](crate::Config::cancel_token)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "operation cancelled")
+    }
+}
+
+impl Error for Cancelled {}
@@ -0,0 +1,486 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::timer::{self, Sleep};
+use crate::ExecutorError;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{block_on, JoinHandle, TimedOut};
+use std::future::Future;
+use std::hint;
+#[cfg(not(target_arch = "wasm32"))]
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::{pin, Pin};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// What [`block_on`] does with its thread while the future is pending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleStrategy {
+    /// Sleep until the future's waker fires (the default): no CPU while waiting.
+    Park,
+    /// Re-poll after [`thread::yield_now`]: lowest latency, but keeps a core busy.
+    Yield,
+    /// Re-poll at a fixed interval, ignoring wakeups in between.
+    Sleep(Duration),
+}
+
+/// Tuning knobs for [`block_on`]: latency versus CPU usage, and limits on how long a future may run.
+///
+/// # Example
+/// ```rust
+/// use crator::{ExecutorConfig, ExecutorError, IdleStrategy};
+/// use std::time::Duration;
+///
+/// let executor = ExecutorConfig::new()
+///     .spin_threshold(1_000)
+///     .idle(IdleStrategy::Park)
+///     .max_duration(Duration::from_millis(50));
+///
+/// assert_eq!(executor.block_on(async { 42 }), Ok(42));
+/// assert_eq!(executor.block_on(std::future::pending::<()>()), Err(ExecutorError::Deadline { limit: Duration::from_millis(50) }));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutorConfig {
+    spin_threshold: u32,
+    idle: IdleStrategy,
+    max_duration: Option<Duration>,
+    max_polls: Option<u64>,
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        ExecutorConfig { spin_threshold: 0, idle: IdleStrategy::Park, max_duration: None, max_polls: None }
+    }
+}
+
+impl ExecutorConfig {
+    /// Creates the default configuration: no spinning, [`IdleStrategy::Park`], no limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spins up to `spins` times waiting for a wakeup before falling back to the idle strategy.
+    ///
+    /// Spinning trades CPU for latency on futures that complete within microseconds.
+    pub fn spin_threshold(mut self, spins: u32) -> Self {
+        self.spin_threshold = spins;
+        self
+    }
+
+    /// Sets what the thread does while the future is pending.
+    pub fn idle(mut self, strategy: IdleStrategy) -> Self {
+        self.idle = strategy;
+        self
+    }
+
+    /// Gives up once the future has run for `duration`.
+    pub fn max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// Gives up once the future has been polled `polls` times without completing.
+    ///
+    /// Each wakeup costs one poll, so the budget catches futures that keep waking
+    /// themselves without making progress, which a time limit alone would let spin
+    /// until the deadline.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{ExecutorConfig, ExecutorError};
+    /// use std::future;
+    /// use std::task::Poll;
+    ///
+    /// // Wakes itself on every poll but never completes
+    /// let busy = future::poll_fn(|cx| -> Poll<()> { cx.waker().wake_by_ref(); Poll::Pending });
+    /// let executor = ExecutorConfig::new().max_polls(1_000);
+    /// assert_eq!(executor.block_on(busy), Err(ExecutorError::Budget { polls: 1_000 }));
+    /// ```
+    pub fn max_polls(mut self, polls: u64) -> Self {
+        self.max_polls = Some(polls);
+        self
+    }
+
+    /// Drives a future to completion on the calling thread with these settings.
+    ///
+    /// Shorthand for [`Runtime::with_config`] followed by [`Runtime::block_on`].
+    ///
+    /// # Errors
+    /// Returns [`ExecutorError::Deadline`] if the future is still pending after the
+    /// configured [`max_duration`](ExecutorConfig::max_duration), and [`ExecutorError::Budget`]
+    /// once it has used up [`max_polls`](ExecutorConfig::max_polls); the future is dropped.
+    pub fn block_on<F: Future>(&self, future: F) -> Result<F::Output, ExecutorError> {
+        Runtime::with_config(*self).block_on(future)
+    }
+}
+
+/// A single-threaded runner: an [`ExecutorConfig`] plus a waker bound to the thread
+/// that created it, reused across every [`block_on`](Runtime::block_on) call.
+///
+/// The free [`block_on`] function is a thin wrapper around a default `Runtime`
+/// kept per thread.
+///
+/// # Example
+/// ```rust
+/// use crator::{ExecutorConfig, IdleStrategy, Runtime};
+///
+/// let runtime = Runtime::with_config(ExecutorConfig::new().idle(IdleStrategy::Park));
+/// assert_eq!(runtime.block_on(async { 1 + 1 }), Ok(2));
+/// assert_eq!(runtime.block_on(async { "again" }), Ok("again"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Runtime {
+    config: ExecutorConfig,
+    wake: Arc<ThreadWake>,
+    waker: Waker,
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Self::with_config(ExecutorConfig::default())
+    }
+}
+
+impl Runtime {
+    /// Creates a runtime with the default [`ExecutorConfig`] for the calling thread.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a runtime with custom settings for the calling thread.
+    pub fn with_config(config: ExecutorConfig) -> Self {
+        let wake = Arc::new(ThreadWake { thread: thread::current(), woken: AtomicBool::new(false) });
+        let waker = Waker::from(Arc::clone(&wake));
+        Runtime { config, wake, waker }
+    }
+
+    /// The settings this runtime drives futures with.
+    pub fn config(&self) -> &ExecutorConfig {
+        &self.config
+    }
+
+    /// The waker handed to futures polled on the runtime's thread.
+    ///
+    /// Waking it from any thread makes a pending [`block_on`](Runtime::block_on) poll its future again.
+    pub fn waker(&self) -> &Waker {
+        &self.waker
+    }
+
+    /// Drives a future to completion on the calling thread.
+    ///
+    /// Called from a thread other than the one that created the runtime, a
+    /// temporary waker for that thread is used instead of [`waker`](Runtime::waker).
+    ///
+    /// # Errors
+    /// Returns [`ExecutorError::Deadline`] if the future is still pending after the
+    /// configured [`max_duration`](ExecutorConfig::max_duration), and [`ExecutorError::Budget`]
+    /// once it has used up [`max_polls`](ExecutorConfig::max_polls); the future is dropped.
+    pub fn block_on<F: Future>(&self, future: F) -> Result<F::Output, ExecutorError> {
+        self.block_on_with_stats(future).0
+    }
+
+    /// Like [`block_on`](Runtime::block_on), also reporting what driving the future cost.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{sleep, ExecutorConfig, Runtime};
+    /// use std::time::Duration;
+    ///
+    /// let runtime = Runtime::with_config(ExecutorConfig::new().spin_threshold(100));
+    /// let (result, stats) = runtime.block_on_with_stats(sleep(Duration::from_millis(10)));
+    /// assert!(result.is_ok());
+    /// assert!(stats.polls >= 2 && stats.spins >= 100 && stats.parks >= 1);
+    /// assert!(stats.elapsed >= Duration::from_millis(10));
+    /// ```
+    pub fn block_on_with_stats<F: Future>(&self, future: F) -> (Result<F::Output, ExecutorError>, ExecutorStats) {
+        let mut stats = ExecutorStats::default();
+        let start = Instant::now();
+        let result = if self.wake.thread.id() == thread::current().id() {
+            self.run(future, &mut stats)
+        } else {
+            Runtime::with_config(self.config).run(future, &mut stats)
+        };
+        stats.elapsed = start.elapsed();
+        (result, stats)
+    }
+
+    fn run<F: Future>(&self, future: F, stats: &mut ExecutorStats) -> Result<F::Output, ExecutorError> {
+        let mut future = pin!(future);
+        let config = &self.config;
+        let deadline = config.max_duration.map(|limit| (Instant::now() + limit, limit));
+        let mut cx = Context::from_waker(&self.waker);
+
+        loop {
+            self.wake.woken.store(false, Ordering::SeqCst);
+            stats.polls += 1;
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) { return Ok(output); }
+            if let Some(polls) = config.max_polls && stats.polls >= polls { return Err(ExecutorError::Budget { polls }); }
+            let remaining = match deadline {
+                Some((deadline, limit)) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => Some(remaining),
+                    _ => return Err(ExecutorError::Deadline { limit }),
+                },
+                None => None,
+            };
+            // Efficiency: a wakeup arriving while spinning skips the trip through the OS
+            let woken = (0..config.spin_threshold).any(|_| {
+                hint::spin_loop();
+                stats.spins += 1;
+                self.wake.woken.load(Ordering::SeqCst)
+            });
+            if woken { continue; }
+            match (config.idle, remaining) {
+                // A wakeup that arrived before parking is not lost: the park returns immediately
+                (IdleStrategy::Park, Some(remaining)) => { stats.parks += 1; thread::park_timeout(remaining) }
+                (IdleStrategy::Park, None) => { stats.parks += 1; thread::park() }
+                (IdleStrategy::Yield, _) => { stats.yields += 1; thread::yield_now() }
+                (IdleStrategy::Sleep(interval), remaining) => { stats.sleeps += 1; thread::sleep(remaining.map_or(interval, |r| r.min(interval))) }
+            }
+        }
+    }
+}
+
+/// What driving one future cost, as reported by [`Runtime::block_on_with_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutorStats {
+    /// How many times the future was polled.
+    pub polls: u64,
+    /// Spin-loop iterations spent waiting for a wakeup before idling.
+    pub spins: u64,
+    /// Times the thread yielded to the OS ([`IdleStrategy::Yield`]).
+    pub yields: u64,
+    /// Times the thread parked until woken ([`IdleStrategy::Park`]).
+    pub parks: u64,
+    /// Times the thread slept for the polling interval ([`IdleStrategy::Sleep`]).
+    pub sleeps: u64,
+    /// Wall-clock time from the first poll to completion (or until a limit was hit).
+    pub elapsed: Duration,
+}
+
+/// A thread-safe Waker that unparks the thread running `block_on`.
+///
+/// Futures hand it to whatever they are waiting on (the reactor, the timer, a
+/// helper thread), so the runner can sleep until one of them signals progress.
+#[derive(Debug)]
+struct ThreadWake {
+    thread: thread::Thread,
+    woken: AtomicBool,
+}
+
+impl Wake for ThreadWake {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::SeqCst);
+        self.thread.unpark();
+    }
+}
+
+/// Future returned by [`timeout`].
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use = "futures do nothing unless polled"]
+pub struct Timeout<F: Future> {
+    future: Pin<Box<F>>,
+    deadline: Sleep,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, TimedOut>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(output) = self.future.as_mut().poll(cx) { return Poll::Ready(Ok(output)); }
+        // The timer wakes the task at the deadline even if the inner future never does
+        if Pin::new(&mut self.deadline).poll(cx).is_ready() { return Poll::Ready(Err(TimedOut)); }
+        Poll::Pending
+    }
+}
+
+/// Limits how long a future may run, resolving to `Err(TimedOut)` once `duration` has elapsed.
+///
+/// A background timer wakes the task at the deadline. crator's fetches wait for
+/// the network without blocking `poll` on Unix, so they are cut off on time there; on
+/// other platforms a stuck read is only interrupted by an [`AbortHandle`](crate::AbortHandle).
+///
+/// # Example
+/// ```rust
+/// use crator::{block_on, timeout, TimedOut};
+/// use std::time::Duration;
+///
+/// let fast = block_on(timeout(async { 42 }, Duration::from_secs(1)));
+/// assert_eq!(fast, Ok(42));
+///
+/// let never = block_on(timeout(std::future::pending::<()>(), Duration::from_millis(20)));
+/// assert_eq!(never, Err(TimedOut));
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn timeout<F: Future>(future: F, duration: Duration) -> Timeout<F> {
+    Timeout { future: Box::pin(future), deadline: timer::sleep(duration) }
+}
+
+/// Drives a future to completion like [`block_on`], giving up after `duration`.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on_timeout, crate_data};
+/// use std::time::Duration;
+///
+/// match block_on_timeout(crate_data("serde"), Duration::from_secs(10)) {
+///     Ok(Ok(info)) => println!("serde v{}", info.latest),
+///     Ok(Err(e)) => eprintln!("fetch failed: {}", e),
+///     Err(_) => eprintln!("crates.io did not answer in time"),
+/// }
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn block_on_timeout<F: Future>(future: F, duration: Duration) -> Result<F::Output, TimedOut> {
+    block_on(timeout(future, duration))
+}
+
+/// Future returned by [`join_all`].
+#[must_use = "futures do nothing unless polled"]
+pub struct JoinAll<F: Future> {
+    futures: Vec<Option<Pin<Box<F>>>>,
+    outputs: Vec<Option<F::Output>>,
+}
+
+impl<F: Future> Unpin for JoinAll<F> {}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut pending = false;
+        for (slot, output) in this.futures.iter_mut().zip(this.outputs.iter_mut()) {
+            let Some(future) = slot else { continue };
+            match future.as_mut().poll(cx) {
+                Poll::Ready(value) => { *output = Some(value); *slot = None; }
+                Poll::Pending => pending = true,
+            }
+        }
+        if pending { return Poll::Pending; }
+        Poll::Ready(this.outputs.iter_mut().map(|output| output.take().expect("output polled after completion")).collect())
+    }
+}
+
+/// Drives many futures concurrently, resolving to their outputs in input order.
+///
+/// All futures are polled within the same task, so they make progress whenever
+/// any of them is woken, e.g. several [`crate_data`](crate::crate_data) calls inside
+/// a single [`block_on`]. On Unix their network I/O is non-blocking, so the requests
+/// genuinely overlap.
+///
+/// # Example
+/// ```rust
+/// use crator::{block_on, join_all};
+///
+/// let squares = block_on(join_all((1..=3).map(|n| async move { n * n })));
+/// assert_eq!(squares, vec![1, 4, 9]);
+/// ```
+///
+/// ```rust,no_run
+/// use crator::{block_on, crate_data, join_all};
+///
+/// let names = ["serde", "rand", "mathlab"];
+/// for (name, info) in names.iter().zip(block_on(join_all(names.iter().map(|n| crate_data(n))))) {
+///     match info {
+///         Ok(info) => println!("{}: v{}", name, info.latest),
+///         Err(e) => eprintln!("{}: {}", name, e),
+///     }
+/// }
+/// ```
+pub fn join_all<I>(futures: I) -> JoinAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    let futures: Vec<_> = futures.into_iter().map(|future| Some(Box::pin(future))).collect();
+    let outputs = futures.iter().map(|_| None).collect();
+    JoinAll { futures, outputs }
+}
+
+/// Runs `task` on every item with at most `limit` tasks in flight, resolving to the
+/// outputs in input order.
+pub(crate) async fn map_bounded<'a, T, F, Fut>(items: &'a [T], limit: usize, task: F) -> Vec<Fut::Output>
+where
+    F: Fn(&'a T) -> Fut,
+    Fut: Future,
+{
+    let next = AtomicUsize::new(0);
+    // Each worker takes the next item until none are left
+    let workers = (0..limit.max(1).min(items.len())).map(|_| async {
+        let mut outputs = Vec::new();
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(item) = items.get(index) else { break };
+            outputs.push((index, task(item).await));
+        }
+        outputs
+    });
+    let mut outputs: Vec<_> = join_all(workers).await.into_iter().flatten().collect();
+    outputs.sort_by_key(|(index, _)| *index);
+    outputs.into_iter().map(|(_, output)| output).collect()
+}
+
+/// Future returned by [`join`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Join<A: Future, B: Future> {
+    a: Pin<Box<A>>,
+    b: Pin<Box<B>>,
+    a_output: Option<A::Output>,
+    b_output: Option<B::Output>,
+}
+
+impl<A: Future, B: Future> Unpin for Join<A, B> {}
+
+impl<A: Future, B: Future> Future for Join<A, B> {
+    type Output = (A::Output, B::Output);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if this.a_output.is_none() && let Poll::Ready(value) = this.a.as_mut().poll(cx) { this.a_output = Some(value); }
+        if this.b_output.is_none() && let Poll::Ready(value) = this.b.as_mut().poll(cx) { this.b_output = Some(value); }
+        if this.a_output.is_none() || this.b_output.is_none() { return Poll::Pending; }
+        Poll::Ready((this.a_output.take().expect("checked above"), this.b_output.take().expect("checked above")))
+    }
+}
+
+/// Drives two futures of different types concurrently, resolving to both outputs.
+///
+/// # Example
+/// ```rust
+/// use crator::{block_on, join};
+///
+/// let (name, count) = block_on(join(async { "crator" }, async { 42 }));
+/// assert_eq!((name, count), ("crator", 42));
+/// ```
+pub fn join<A: Future, B: Future>(a: A, b: B) -> Join<A, B> {
+    Join { a: Box::pin(a), b: Box::pin(b), a_output: None, b_output: None }
+}
+
+/// Runs the blocking `job` on its own thread, returning a handle that resolves to its result.
+///
+/// Awaiting the handle doesn't hold up the other tasks of the executor, whichever
+/// executor that is, so blocking work (file I/O, heavy parsing, third-party blocking
+/// clients) can be mixed into async code. A panic in `job` resumes where the handle is awaited.
+///
+/// # Example
+/// ```rust
+/// use crator::{block_on, spawn_blocking};
+///
+/// let handle = spawn_blocking(|| {
+///     // Stands in for blocking work, e.g. reading a cache file
+///     std::thread::sleep(std::time::Duration::from_millis(10));
+///     "cached"
+/// });
+/// assert_eq!(block_on(handle), "cached");
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn_blocking<T: Send + 'static>(job: impl FnOnce() -> T + Send + 'static) -> JoinHandle<T> {
+    let (handle, completion) = JoinHandle::new();
+    thread::spawn(move || completion.complete(panic::catch_unwind(AssertUnwindSafe(job))));
+    handle
+}
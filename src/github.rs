@@ -0,0 +1,121 @@
+use crate::{http, Config, CrateInfo, FetchError, Json, Request};
+use std::error::Error;
+
+/// Serves the GitHub REST API.
+const GITHUB_API: &str = "api.github.com";
+
+/// Activity and popularity figures of a crate's GitHub repository, fetched with
+/// [`github_stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RepoStats {
+    /// The repository as `owner/name` (e.g. "serde-rs/serde"), following renames.
+    pub full_name: String,
+    /// The number of stargazers.
+    pub stars: u64,
+    /// The number of forks.
+    pub forks: u64,
+    /// Open issues and pull requests, which GitHub counts together.
+    pub open_issues: u64,
+    /// Whether the repository is archived (read-only).
+    pub archived: bool,
+    /// The default branch (e.g. "main").
+    pub default_branch: String,
+    /// ISO 8601 timestamp of the last commit on the default branch, if any.
+    pub last_commit: Option<String>,
+}
+
+impl RepoStats {
+    /// Whole days since the last commit on the default branch, or `None` if there is
+    /// none or its date can't be parsed.
+    pub fn days_since_commit(&self) -> Option<u64> {
+        CrateInfo::days_since(self.last_commit.as_deref()?)
+    }
+}
+
+/// Extracts `(owner, name)` from a GitHub repository URL, ignoring a `.git` suffix
+/// and any path below the repository (e.g. `/tree/master/serde`).
+///
+/// # Example
+/// ```rust
+/// use crator::github_repo;
+///
+/// assert_eq!(github_repo("https://github.com/serde-rs/serde"), Some(("serde-rs".into(), "serde".into())));
+/// assert_eq!(github_repo("https://github.com/rust-lang/cargo.git/"), Some(("rust-lang".into(), "cargo".into())));
+/// assert_eq!(github_repo("https://github.com/tokio-rs/tokio/tree/master/tokio"), Some(("tokio-rs".into(), "tokio".into())));
+/// assert_eq!(github_repo("https://gitlab.com/a/b"), None);
+/// ```
+pub fn github_repo(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")).unwrap_or(url);
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    let (host, path) = rest.split_once('/')?;
+    if !host.eq_ignore_ascii_case("github.com") { return None; }
+    let mut segments = path.split(['/', '?', '#']);
+    let owner = segments.next().filter(|owner| !owner.is_empty())?;
+    let name = segments.next().map(|name| name.strip_suffix(".git").unwrap_or(name)).filter(|name| !name.is_empty())?;
+    Some((owner.to_string(), name.to_string()))
+}
+
+/// Sends a GitHub API request, failing on non-2xx responses.
+async fn get(request: Request, token: Option<&str>, config: &Config) -> Result<String, Box<dyn Error>> {
+    let mut request = request.header("Accept", "application/vnd.github+json");
+    if let Some(token) = token { request = request.header("Authorization", &format!("Bearer {}", token)); }
+    let (head, body) = http::fetch_async(GITHUB_API, &request, config).await?;
+    http::ensure_success(&head)?;
+    Ok(body)
+}
+
+/// Fetches stars, forks, open issues and the last commit date of the GitHub
+/// repository in [`CrateInfo::repository`], for a fuller picture of a crate's health
+/// than its download counts.
+///
+/// Returns `Ok(None)` when the crate has no repository or it isn't on GitHub. Costs
+/// two GitHub API requests; unauthenticated clients get 60 per hour, so pass a
+/// `token` (a personal access token, no scopes needed) for batches. See
+/// [`github_stats_with`] for a custom [`Config`].
+///
+/// # Errors
+/// A non-2xx response (e.g. a deleted repository, or `403` once rate-limited) yields
+/// a [`FetchError::Status`](crate::FetchError::Status).
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, crate_data, github_stats};
+///
+/// let info = block_on(crate_data("serde")).expect("Failed to fetch crate data");
+/// let token = std::env::var("GITHUB_TOKEN").ok();
+/// if let Some(stats) = block_on(github_stats(&info, token.as_deref())).expect("Failed to fetch repository") {
+///     println!("{}: {} stars, {} open issues", stats.full_name, stats.stars, stats.open_issues);
+///     if let Some(days) = stats.days_since_commit() {
+///         println!("last commit {} days ago", days);
+///     }
+/// }
+/// ```
+pub async fn github_stats(info: &CrateInfo, token: Option<&str>) -> Result<Option<RepoStats>, Box<dyn Error>> {
+    github_stats_with(info, token, crate::default_config()).await
+}
+
+/// Same as [`github_stats`], but driven by a custom [`Config`].
+pub async fn github_stats_with(info: &CrateInfo, token: Option<&str>, config: &Config) -> Result<Option<RepoStats>, Box<dyn Error>> {
+    let Some((owner, name)) = info.repository.as_deref().and_then(github_repo) else { return Ok(None) };
+    let body = get(Request::new("GET", &format!("/repos/{}/{}", owner, name)), token, config).await?;
+    let full_name = Json::extract_opt(&body, "full_name").unwrap_or_else(|| format!("{}/{}", owner, name));
+    let default_branch = Json::extract_opt(&body, "default_branch").unwrap_or_else(|| "main".to_string());
+    let commits = Request::new("GET", &format!("/repos/{}/commits", full_name)).query("sha", &default_branch).query("per_page", "1");
+    let last_commit = match get(commits, token, config).await {
+        Ok(commits) => Json::extract_opt(&commits, "0.commit.committer.date"),
+        // An empty repository answers 409 Conflict
+        Err(e) if matches!(e.downcast_ref(), Some(FetchError::Status { code: 409, .. })) => None,
+        Err(e) => return Err(e),
+    };
+    Ok(Some(RepoStats {
+        stars: Json::extract_u64(&body, "stargazers_count"),
+        forks: Json::extract_u64(&body, "forks_count"),
+        open_issues: Json::extract_u64(&body, "open_issues_count"),
+        archived: Json::extract_bool(&body, "archived"),
+        last_commit,
+        full_name,
+        default_branch,
+    }))
+}
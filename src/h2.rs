@@ -0,0 +1,435 @@
+use crate::{http, net, Config, FetchError, Request};
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::io::{self, Read, Write};
+use std::sync::OnceLock;
+
+const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+// Frame types (RFC 9113, section 6)
+const DATA: u8 = 0x0;
+const HEADERS: u8 = 0x1;
+const RST_STREAM: u8 = 0x3;
+const SETTINGS: u8 = 0x4;
+const PING: u8 = 0x6;
+const GOAWAY: u8 = 0x7;
+const WINDOW_UPDATE: u8 = 0x8;
+const CONTINUATION: u8 = 0x9;
+
+// Frame flags
+const END_STREAM: u8 = 0x1;
+const ACK: u8 = 0x1;
+const END_HEADERS: u8 = 0x4;
+const PADDED: u8 = 0x8;
+const PRIORITY: u8 = 0x20;
+
+// Settings identifiers
+const SETTINGS_ENABLE_PUSH: u16 = 0x2;
+const SETTINGS_MAX_CONCURRENT_STREAMS: u16 = 0x3;
+const SETTINGS_INITIAL_WINDOW_SIZE: u16 = 0x4;
+
+/// Largest frame payload a peer may send before `SETTINGS_MAX_FRAME_SIZE` is raised.
+const MAX_FRAME_SIZE: usize = 16_384;
+/// Flow-control window advertised for the connection and every stream.
+const WINDOW: u32 = 16 * 1024 * 1024;
+/// Concurrency assumed until the server announces `SETTINGS_MAX_CONCURRENT_STREAMS`.
+const DEFAULT_CONCURRENCY: usize = 100;
+
+/// A response head and its buffered body.
+type RawResponse = (String, String);
+
+/// Sends every request over one multiplexed HTTP/2 connection to `host`.
+///
+/// Each response is returned as an HTTP/1-style head (`HTTP/2 200` followed by the
+/// headers) plus the buffered body, so the usual `http` helpers apply. Returns
+/// `Ok(None)` when the server doesn't negotiate `h2` via ALPN; the caller then falls
+/// back to HTTP/1.1.
+pub(crate) fn send_many(host: &str, requests: &[Request], config: &Config) -> Result<Option<Vec<RawResponse>>, Box<dyn Error>> {
+    http::abortable(config, send_multiplexed(host, requests, config))
+}
+
+fn send_multiplexed(host: &str, requests: &[Request], config: &Config) -> Result<Option<Vec<RawResponse>>, Box<dyn Error>> {
+    let connector = config.tls_connector_alpn(&["h2", "http/1.1"])?;
+    let (name, port) = net::split_port(host, 443);
+    let stream = net::connect(name, port, config)?;
+    let _registration = config.abort.as_ref().map(|handle| handle.register(&stream)).transpose()?;
+    let mut tls_stream = connector.connect(name, stream)?;
+    if tls_stream.negotiated_alpn()?.as_deref() != Some(b"h2") { return Ok(None); }
+    exchange(&mut tls_stream, host, requests, config).map(Some)
+}
+
+/// A request in flight on the connection.
+#[derive(Default)]
+struct Stream {
+    head: Option<String>,
+    body: Vec<u8>,
+    done: bool,
+}
+
+/// Drives the HTTP/2 conversation over an established stream until every request completed.
+fn exchange<S: Read + Write>(io: &mut S, host: &str, requests: &[Request], config: &Config) -> Result<Vec<RawResponse>, Box<dyn Error>> {
+    let limit = config.max_response_size;
+    io.write_all(PREFACE)?;
+    let mut settings = Vec::new();
+    for (id, value) in [(SETTINGS_ENABLE_PUSH, 0), (SETTINGS_INITIAL_WINDOW_SIZE, WINDOW)] {
+        settings.extend_from_slice(&id.to_be_bytes());
+        settings.extend_from_slice(&value.to_be_bytes());
+    }
+    write_frame(io, SETTINGS, 0, 0, &settings)?;
+    // The connection-level window starts at 65,535 regardless of SETTINGS
+    write_frame(io, WINDOW_UPDATE, 0, 0, &(WINDOW - 65_535).to_be_bytes())?;
+
+    let mut streams: Vec<Stream> = Vec::with_capacity(requests.len());
+    let mut decoder = Decoder::new();
+    let mut max_concurrent = DEFAULT_CONCURRENCY;
+    let (mut active, mut completed) = (0, 0);
+
+    while completed < requests.len() {
+        while active < max_concurrent && streams.len() < requests.len() {
+            let stream_id = 1 + 2 * streams.len() as u32;
+            if let Some(limiter) = &config.rate_limiter { limiter.acquire_blocking(); }
+            write_headers(io, stream_id, &encode_request(host, &requests[streams.len()], config))?;
+            streams.push(Stream::default());
+            active += 1;
+        }
+
+        let (kind, mut flags, stream_id, mut payload) = read_frame(io)?;
+        let index = (stream_id as usize).wrapping_sub(1) / 2;
+        match kind {
+            DATA => {
+                if !payload.is_empty() {
+                    // Hand the consumed bytes back to the server's flow-control windows
+                    let increment = (payload.len() as u32).to_be_bytes();
+                    write_frame(io, WINDOW_UPDATE, 0, 0, &increment)?;
+                    if flags & END_STREAM == 0 { write_frame(io, WINDOW_UPDATE, 0, stream_id, &increment)?; }
+                }
+                let data = strip_padding(&payload, flags)?;
+                let stream = streams.get_mut(index).ok_or(FetchError::MalformedResponse)?;
+                if let Some(limit) = limit && stream.body.len() + data.len() > limit {
+                    return Err(Box::new(FetchError::ResponseTooLarge { limit }));
+                }
+                stream.body.extend_from_slice(data);
+            }
+            HEADERS => {
+                let mut block = strip_padding(&payload, flags)?.to_vec();
+                if flags & PRIORITY != 0 { block.drain(..5.min(block.len())); }
+                while flags & END_HEADERS == 0 {
+                    let (next_kind, next_flags, next_id, fragment) = read_frame(io)?;
+                    if next_kind != CONTINUATION || next_id != stream_id { return Err(Box::new(FetchError::MalformedResponse)); }
+                    block.extend_from_slice(&fragment);
+                    flags = (flags & END_STREAM) | next_flags;
+                }
+                // Trailers must still be decoded to keep the HPACK table in sync
+                let fields = decoder.decode(&block)?;
+                let stream = streams.get_mut(index).ok_or(FetchError::MalformedResponse)?;
+                let status = fields.iter().find(|(name, _)| name == ":status").map(|(_, v)| v.as_str());
+                if stream.head.is_none() && status.is_some_and(|s| !s.starts_with('1')) {
+                    let mut head = format!("HTTP/2 {}", status.unwrap_or_default());
+                    for (name, value) in fields.iter().filter(|(name, _)| !name.starts_with(':')) {
+                        head.push_str(&format!("\r\n{}: {}", name, value));
+                    }
+                    stream.head = Some(head);
+                }
+            }
+            RST_STREAM => {
+                let code = u32::from_be_bytes(payload.get(..4).ok_or(FetchError::MalformedResponse)?.try_into()?);
+                return Err(Box::new(io::Error::new(io::ErrorKind::ConnectionReset, format!("HTTP/2 stream {} reset by server (error code {})", stream_id, code))));
+            }
+            SETTINGS if flags & ACK == 0 => {
+                for entry in payload.chunks_exact(6) {
+                    let id = u16::from_be_bytes([entry[0], entry[1]]);
+                    let value = u32::from_be_bytes([entry[2], entry[3], entry[4], entry[5]]);
+                    if id == SETTINGS_MAX_CONCURRENT_STREAMS { max_concurrent = (value as usize).max(1); }
+                }
+                write_frame(io, SETTINGS, ACK, 0, &[])?;
+            }
+            PING if flags & ACK == 0 => write_frame(io, PING, ACK, 0, &payload)?,
+            GOAWAY => {
+                let last_id = u32::from_be_bytes(payload.get(..4).ok_or(FetchError::MalformedResponse)?.try_into()?) & 0x7fff_ffff;
+                // Streams above `last_id` will never be answered; everything else may still finish
+                if streams.len() > (last_id as usize).div_ceil(2) || streams.len() < requests.len() {
+                    payload.drain(..8.min(payload.len()));
+                    return Err(Box::new(io::Error::new(io::ErrorKind::ConnectionAborted, format!("HTTP/2 connection closed by server: {}", String::from_utf8_lossy(&payload)))));
+                }
+                flags = 0;
+            }
+            _ => {} // PRIORITY, WINDOW_UPDATE, acknowledgements and unknown frames
+        }
+
+        if (kind == DATA || kind == HEADERS) && flags & END_STREAM != 0
+            && let Some(stream) = streams.get_mut(index) && !stream.done
+        {
+            stream.done = true;
+            active -= 1;
+            completed += 1;
+        }
+    }
+
+    streams.into_iter()
+        .map(|s| Ok((s.head.ok_or(FetchError::MalformedResponse)?, String::from_utf8_lossy(&s.body).into_owned())))
+        .collect()
+}
+
+/// Lists the header fields of `request` in HTTP/2 form: pseudo-headers first, names lowercased.
+fn encode_request(host: &str, request: &Request, config: &Config) -> Vec<u8> {
+    let target = request.target();
+    let mut fields = vec![(":method", request.method()), (":scheme", "https"), (":authority", host), (":path", target.as_str())];
+    // Connection-specific headers are forbidden in HTTP/2
+    const HOP_BY_HOP: [&str; 6] = ["connection", "host", "keep-alive", "proxy-connection", "transfer-encoding", "upgrade"];
+    for (name, value) in http::outgoing_headers(request, config) {
+        if !HOP_BY_HOP.iter().any(|h| name.eq_ignore_ascii_case(h)) { fields.push((name, value)); }
+    }
+    let mut block = Vec::new();
+    for (name, value) in fields {
+        // Literal header field without indexing, new name (RFC 7541, section 6.2.2)
+        block.push(0x00);
+        encode_string(&mut block, &name.to_ascii_lowercase());
+        encode_string(&mut block, value);
+    }
+    block
+}
+
+/// Writes a header block as a HEADERS frame, followed by CONTINUATION frames if it is too large.
+fn write_headers<W: Write>(io: &mut W, stream_id: u32, block: &[u8]) -> io::Result<()> {
+    let mut fragments = block.chunks(MAX_FRAME_SIZE).peekable();
+    let mut kind = HEADERS;
+    let mut flags = END_STREAM;
+    loop {
+        let fragment = fragments.next().unwrap_or(&[]);
+        if fragments.peek().is_none() { flags |= END_HEADERS; }
+        write_frame(io, kind, flags, stream_id, fragment)?;
+        if flags & END_HEADERS != 0 { return Ok(()); }
+        (kind, flags) = (CONTINUATION, 0);
+    }
+}
+
+fn write_frame<W: Write>(io: &mut W, kind: u8, flags: u8, stream_id: u32, payload: &[u8]) -> io::Result<()> {
+    let len = (payload.len() as u32).to_be_bytes();
+    let mut frame = Vec::with_capacity(9 + payload.len());
+    frame.extend_from_slice(&len[1..]);
+    frame.extend_from_slice(&[kind, flags]);
+    frame.extend_from_slice(&(stream_id & 0x7fff_ffff).to_be_bytes());
+    frame.extend_from_slice(payload);
+    io.write_all(&frame)
+}
+
+fn read_frame<R: Read>(io: &mut R) -> io::Result<(u8, u8, u32, Vec<u8>)> {
+    let mut header = [0u8; 9];
+    io.read_exact(&mut header)?;
+    let len = u32::from_be_bytes([0, header[0], header[1], header[2]]) as usize;
+    let stream_id = u32::from_be_bytes([header[5], header[6], header[7], header[8]]) & 0x7fff_ffff;
+    let mut payload = vec![0u8; len];
+    io.read_exact(&mut payload)?;
+    Ok((header[3], header[4], stream_id, payload))
+}
+
+/// Removes the pad-length byte and trailing padding from a PADDED frame payload.
+fn strip_padding(payload: &[u8], flags: u8) -> Result<&[u8], FetchError> {
+    if flags & PADDED == 0 { return Ok(payload); }
+    let pad = *payload.first().ok_or(FetchError::MalformedResponse)? as usize;
+    payload.get(1..payload.len().saturating_sub(pad)).filter(|_| pad < payload.len()).ok_or(FetchError::MalformedResponse)
+}
+
+/// An HPACK (RFC 7541) header block decoder with its dynamic table.
+struct Decoder {
+    table: VecDeque<(String, String)>,
+    size: usize,
+    max_size: usize,
+}
+
+impl Decoder {
+    fn new() -> Self {
+        Self { table: VecDeque::new(), size: 0, max_size: 4096 }
+    }
+
+    fn decode(&mut self, block: &[u8]) -> Result<Vec<(String, String)>, FetchError> {
+        self.decode_fields(block).ok_or(FetchError::MalformedResponse)
+    }
+
+    fn decode_fields(&mut self, block: &[u8]) -> Option<Vec<(String, String)>> {
+        let mut fields = Vec::new();
+        let mut pos = 0;
+        while pos < block.len() {
+            let first = block[pos];
+            if first & 0x80 != 0 {
+                // Indexed header field
+                let index = decode_integer(block, &mut pos, 7)?;
+                fields.push(self.entry(index)?);
+            } else if first & 0x40 != 0 {
+                // Literal with incremental indexing
+                let index = decode_integer(block, &mut pos, 6)?;
+                let name = if index == 0 { decode_string(block, &mut pos)? } else { self.entry(index)?.0 };
+                let value = decode_string(block, &mut pos)?;
+                self.insert(name.clone(), value.clone());
+                fields.push((name, value));
+            } else if first & 0x20 != 0 {
+                // Dynamic table size update
+                self.max_size = decode_integer(block, &mut pos, 5)?;
+                self.evict(0);
+            } else {
+                // Literal without indexing / never indexed
+                let index = decode_integer(block, &mut pos, 4)?;
+                let name = if index == 0 { decode_string(block, &mut pos)? } else { self.entry(index)?.0 };
+                fields.push((name, decode_string(block, &mut pos)?));
+            }
+        }
+        Some(fields)
+    }
+
+    fn entry(&self, index: usize) -> Option<(String, String)> {
+        match index {
+            0 => None,
+            1..=61 => STATIC_TABLE.get(index - 1).map(|(n, v)| (n.to_string(), v.to_string())),
+            _ => self.table.get(index - 62).cloned(),
+        }
+    }
+
+    fn insert(&mut self, name: String, value: String) {
+        let size = name.len() + value.len() + 32;
+        self.evict(size);
+        if size <= self.max_size {
+            self.size += size;
+            self.table.push_front((name, value));
+        }
+    }
+
+    /// Evicts the oldest entries until `incoming` more bytes fit in the table.
+    fn evict(&mut self, incoming: usize) {
+        while self.size + incoming > self.max_size {
+            match self.table.pop_back() {
+                Some((n, v)) => self.size -= n.len() + v.len() + 32,
+                None => { self.size = 0; return; }
+            }
+        }
+    }
+}
+
+fn decode_integer(buf: &[u8], pos: &mut usize, prefix: u32) -> Option<usize> {
+    let mask = (1u8 << prefix) - 1;
+    let mut value = (buf.get(*pos)? & mask) as usize;
+    *pos += 1;
+    if value < mask as usize { return Some(value); }
+    let mut shift = 0;
+    loop {
+        let byte = *buf.get(*pos)?;
+        *pos += 1;
+        value += ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 { return Some(value); }
+        shift += 7;
+        if shift > 28 { return None; }
+    }
+}
+
+fn decode_string(buf: &[u8], pos: &mut usize) -> Option<String> {
+    let huffman = buf.get(*pos)? & 0x80 != 0;
+    let len = decode_integer(buf, pos, 7)?;
+    let raw = buf.get(*pos..*pos + len)?;
+    *pos += len;
+    let bytes = if huffman { huffman_decode(raw)? } else { raw.to_vec() };
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn encode_integer(out: &mut Vec<u8>, value: usize, prefix: u32, flags: u8) {
+    let mask = (1usize << prefix) - 1;
+    if value < mask { out.push(flags | value as u8); return; }
+    out.push(flags | mask as u8);
+    let mut rest = value - mask;
+    while rest >= 128 {
+        out.push((rest % 128) as u8 | 0x80);
+        rest /= 128;
+    }
+    out.push(rest as u8);
+}
+
+/// Encodes a raw (non-Huffman) string literal.
+fn encode_string(out: &mut Vec<u8>, value: &str) {
+    encode_integer(out, value.len(), 7, 0);
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn huffman_decode(data: &[u8]) -> Option<Vec<u8>> {
+    static CODES: OnceLock<HashMap<(u8, u32), u16>> = OnceLock::new();
+    let codes = CODES.get_or_init(|| HUFFMAN_CODES.iter().enumerate().map(|(sym, &(code, len))| ((len, code), sym as u16)).collect());
+    let (mut code, mut len) = (0u32, 0u8);
+    let mut out = Vec::with_capacity(data.len() * 8 / 5);
+    for &byte in data {
+        for shift in (0..8).rev() {
+            code = (code << 1) | ((byte >> shift) & 1) as u32;
+            len += 1;
+            if let Some(&sym) = codes.get(&(len, code)) {
+                if sym == 256 { return None; } // EOS must not appear in the string
+                out.push(sym as u8);
+                (code, len) = (0, 0);
+            } else if len >= 30 {
+                return None;
+            }
+        }
+    }
+    // Leftover bits must be a (short) prefix of EOS, i.e. all ones
+    if len > 7 || code != (1u32 << len) - 1 { return None; }
+    Some(out)
+}
+
+/// The HPACK static table (RFC 7541, appendix A).
+const STATIC_TABLE: [(&str, &str); 61] = [
+    (":authority", ""), (":method", "GET"), (":method", "POST"), (":path", "/"), (":path", "/index.html"),
+    (":scheme", "http"), (":scheme", "https"), (":status", "200"), (":status", "204"), (":status", "206"),
+    (":status", "304"), (":status", "400"), (":status", "404"), (":status", "500"), ("accept-charset", ""),
+    ("accept-encoding", "gzip, deflate"), ("accept-language", ""), ("accept-ranges", ""), ("accept", ""),
+    ("access-control-allow-origin", ""), ("age", ""), ("allow", ""), ("authorization", ""), ("cache-control", ""),
+    ("content-disposition", ""), ("content-encoding", ""), ("content-language", ""), ("content-length", ""),
+    ("content-location", ""), ("content-range", ""), ("content-type", ""), ("cookie", ""), ("date", ""),
+    ("etag", ""), ("expect", ""), ("expires", ""), ("from", ""), ("host", ""), ("if-match", ""),
+    ("if-modified-since", ""), ("if-none-match", ""), ("if-range", ""), ("if-unmodified-since", ""),
+    ("last-modified", ""), ("link", ""), ("location", ""), ("max-forwards", ""), ("proxy-authenticate", ""),
+    ("proxy-authorization", ""), ("range", ""), ("referer", ""), ("refresh", ""), ("retry-after", ""),
+    ("server", ""), ("set-cookie", ""), ("strict-transport-security", ""), ("transfer-encoding", ""),
+    ("user-agent", ""), ("vary", ""), ("via", ""), ("www-authenticate", ""),
+];
+
+/// The HPACK Huffman code `(code, bit length)` for each symbol 0..=256 (RFC 7541, appendix B).
+const HUFFMAN_CODES: [(u32, u8); 257] = [
+    (0x1ff8, 13), (0x7fffd8, 23), (0xfffffe2, 28), (0xfffffe3, 28), (0xfffffe4, 28), (0xfffffe5, 28),
+    (0xfffffe6, 28), (0xfffffe7, 28), (0xfffffe8, 28), (0xffffea, 24), (0x3ffffffc, 30), (0xfffffe9, 28),
+    (0xfffffea, 28), (0x3ffffffd, 30), (0xfffffeb, 28), (0xfffffec, 28), (0xfffffed, 28), (0xfffffee, 28),
+    (0xfffffef, 28), (0xffffff0, 28), (0xffffff1, 28), (0xffffff2, 28), (0x3ffffffe, 30), (0xffffff3, 28),
+    (0xffffff4, 28), (0xffffff5, 28), (0xffffff6, 28), (0xffffff7, 28), (0xffffff8, 28), (0xffffff9, 28),
+    (0xffffffa, 28), (0xffffffb, 28), (0x14, 6), (0x3f8, 10), (0x3f9, 10), (0xffa, 12),
+    (0x1ff9, 13), (0x15, 6), (0xf8, 8), (0x7fa, 11), (0x3fa, 10), (0x3fb, 10),
+    (0xf9, 8), (0x7fb, 11), (0xfa, 8), (0x16, 6), (0x17, 6), (0x18, 6),
+    (0x0, 5), (0x1, 5), (0x2, 5), (0x19, 6), (0x1a, 6), (0x1b, 6),
+    (0x1c, 6), (0x1d, 6), (0x1e, 6), (0x1f, 6), (0x5c, 7), (0xfb, 8),
+    (0x7ffc, 15), (0x20, 6), (0xffb, 12), (0x3fc, 10), (0x1ffa, 13), (0x21, 6),
+    (0x5d, 7), (0x5e, 7), (0x5f, 7), (0x60, 7), (0x61, 7), (0x62, 7),
+    (0x63, 7), (0x64, 7), (0x65, 7), (0x66, 7), (0x67, 7), (0x68, 7),
+    (0x69, 7), (0x6a, 7), (0x6b, 7), (0x6c, 7), (0x6d, 7), (0x6e, 7),
+    (0x6f, 7), (0x70, 7), (0x71, 7), (0x72, 7), (0xfc, 8), (0x73, 7),
+    (0xfd, 8), (0x1ffb, 13), (0x7fff0, 19), (0x1ffc, 13), (0x3ffc, 14), (0x22, 6),
+    (0x7ffd, 15), (0x3, 5), (0x23, 6), (0x4, 5), (0x24, 6), (0x5, 5),
+    (0x25, 6), (0x26, 6), (0x27, 6), (0x6, 5), (0x74, 7), (0x75, 7),
+    (0x28, 6), (0x29, 6), (0x2a, 6), (0x7, 5), (0x2b, 6), (0x76, 7),
+    (0x2c, 6), (0x8, 5), (0x9, 5), (0x2d, 6), (0x77, 7), (0x78, 7),
+    (0x79, 7), (0x7a, 7), (0x7b, 7), (0x7ffe, 15), (0x7fc, 11), (0x3ffd, 14),
+    (0x1ffd, 13), (0xffffffc, 28), (0xfffe6, 20), (0x3fffd2, 22), (0xfffe7, 20), (0xfffe8, 20),
+    (0x3fffd3, 22), (0x3fffd4, 22), (0x3fffd5, 22), (0x7fffd9, 23), (0x3fffd6, 22), (0x7fffda, 23),
+    (0x7fffdb, 23), (0x7fffdc, 23), (0x7fffdd, 23), (0x7fffde, 23), (0xffffeb, 24), (0x7fffdf, 23),
+    (0xffffec, 24), (0xffffed, 24), (0x3fffd7, 22), (0x7fffe0, 23), (0xffffee, 24), (0x7fffe1, 23),
+    (0x7fffe2, 23), (0x7fffe3, 23), (0x7fffe4, 23), (0x1fffdc, 21), (0x3fffd8, 22), (0x7fffe5, 23),
+    (0x3fffd9, 22), (0x7fffe6, 23), (0x7fffe7, 23), (0xffffef, 24), (0x3fffda, 22), (0x1fffdd, 21),
+    (0xfffe9, 20), (0x3fffdb, 22), (0x3fffdc, 22), (0x7fffe8, 23), (0x7fffe9, 23), (0x1fffde, 21),
+    (0x7fffea, 23), (0x3fffdd, 22), (0x3fffde, 22), (0xfffff0, 24), (0x1fffdf, 21), (0x3fffdf, 22),
+    (0x7fffeb, 23), (0x7fffec, 23), (0x1fffe0, 21), (0x1fffe1, 21), (0x3fffe0, 22), (0x1fffe2, 21),
+    (0x7fffed, 23), (0x3fffe1, 22), (0x7fffee, 23), (0x7fffef, 23), (0xfffea, 20), (0x3fffe2, 22),
+    (0x3fffe3, 22), (0x3fffe4, 22), (0x7ffff0, 23), (0x3fffe5, 22), (0x3fffe6, 22), (0x7ffff1, 23),
+    (0x3ffffe0, 26), (0x3ffffe1, 26), (0xfffeb, 20), (0x7fff1, 19), (0x3fffe7, 22), (0x7ffff2, 23),
+    (0x3fffe8, 22), (0x1ffffec, 25), (0x3ffffe2, 26), (0x3ffffe3, 26), (0x3ffffe4, 26), (0x7ffffde, 27),
+    (0x7ffffdf, 27), (0x3ffffe5, 26), (0xfffff1, 24), (0x1ffffed, 25), (0x7fff2, 19), (0x1fffe3, 21),
+    (0x3ffffe6, 26), (0x7ffffe0, 27), (0x7ffffe1, 27), (0x3ffffe7, 26), (0x7ffffe2, 27), (0xfffff2, 24),
+    (0x1fffe4, 21), (0x1fffe5, 21), (0x3ffffe8, 26), (0x3ffffe9, 26), (0xffffffd, 28), (0x7ffffe3, 27),
+    (0x7ffffe4, 27), (0x7ffffe5, 27), (0xfffec, 20), (0xfffff3, 24), (0xfffed, 20), (0x1fffe6, 21),
+    (0x3fffe9, 22), (0x1fffe7, 21), (0x1fffe8, 21), (0x7ffff3, 23), (0x3fffea, 22), (0x3fffeb, 22),
+    (0x1ffffee, 25), (0x1ffffef, 25), (0xfffff4, 24), (0xfffff5, 24), (0x3ffffea, 26), (0x7ffff4, 23),
+    (0x3ffffeb, 26), (0x7ffffe6, 27), (0x3ffffec, 26), (0x3ffffed, 26), (0x7ffffe7, 27), (0x7ffffe8, 27),
+    (0x7ffffe9, 27), (0x7ffffea, 27), (0x7ffffeb, 27), (0xffffffe, 28), (0x7ffffec, 27), (0x7ffffed, 27),
+    (0x7ffffee, 27), (0x7ffffef, 27), (0x7fffff0, 27), (0x3ffffee, 26), (0x3fffffff, 30)
+];
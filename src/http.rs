@@ -0,0 +1,716 @@
+use crate::cache::Lookup;
+use crate::{cancellable, Config, FetchError, Request};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{abort::Registration, net, spawn_blocking, timer};
+#[cfg(all(unix, not(feature = "async-io")))]
+use crate::reactor::Source;
+#[cfg(any(unix, feature = "async-io"))]
+use native_tls::HandshakeError;
+#[cfg(not(target_arch = "wasm32"))]
+use native_tls::TlsStream;
+use std::error::Error;
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+#[cfg(not(target_arch = "wasm32"))]
+use {
+    std::collections::VecDeque,
+    std::future,
+    std::io::{BufRead, BufReader, Read, Write},
+    std::net::TcpStream,
+    std::sync::{Arc, Condvar, Mutex, MutexGuard},
+    std::task::{Poll, Waker},
+    std::thread,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Receives decoded body chunks; returning an error aborts the transfer.
+pub(crate) type ChunkSink<'a> = dyn FnMut(&[u8]) -> Result<(), Box<dyn Error>> + 'a;
+
+#[cfg(not(target_arch = "wasm32"))]
+/// The buffered reader over an established TLS connection.
+pub(crate) struct Connection {
+    reader: BufReader<TlsStream<TcpStream>>,
+    _registration: Option<Registration>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Connection {
+    /// Turns the EOF or error caused by an abort-triggered shutdown into an error,
+    /// so a cancelled transfer never passes for a complete (truncated) body.
+    fn check_aborted(&self) -> io::Result<()> {
+        match &self._registration {
+            Some(registration) if registration.is_aborted() => Err(io::Error::new(io::ErrorKind::Interrupted, "request aborted")),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let result = self.reader.read(buf);
+        if matches!(result, Ok(0) | Err(_)) { self.check_aborted()?; }
+        result
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BufRead for Connection {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.check_aborted()?;
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.reader.consume(amount)
+    }
+}
+
+/// Replaces the error of a fetch cancelled through the configured [`AbortHandle`](crate::AbortHandle)
+/// with [`FetchError::Aborted`], whatever socket error the shutdown caused.
+pub(crate) fn abortable<T>(config: &Config, result: Result<T, Box<dyn Error>>) -> Result<T, Box<dyn Error>> {
+    match result {
+        Err(_) if config.abort.as_ref().is_some_and(|handle| handle.is_aborted()) => Err(Box::new(FetchError::Aborted)),
+        result => result,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Sends `request` and returns the response head and the fully buffered body.
+fn fetch_body(host: &str, request: &Request, config: &Config) -> Result<(String, String), Box<dyn Error>> {
+    let (head, mut reader) = open(host, request, config)?;
+    buffer_body(head, &mut reader, config)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Reads the whole body following `head`, enforcing [`Config::max_response_size`].
+fn buffer_body<R: BufRead>(head: String, reader: &mut R, config: &Config) -> Result<(String, String), Box<dyn Error>> {
+    let mut body = Vec::new();
+    let limit = config.max_response_size;
+    if let (Some(limit), Some(len)) = (limit, header(&head, "content-length")) {
+        // Reject oversized payloads before reading a single body byte
+        if len.parse::<usize>().is_ok_and(|len| len > limit) { return Err(Box::new(FetchError::ResponseTooLarge { limit })); }
+    }
+    read_body(reader, &head, &mut |chunk| {
+        if let Some(limit) = limit && body.len() + chunk.len() > limit {
+            return Err(Box::new(FetchError::ResponseTooLarge { limit }));
+        }
+        body.extend_from_slice(chunk);
+        Ok(())
+    })?;
+    Ok((head, String::from_utf8_lossy(&body).into_owned()))
+}
+
+/// The host and full request for `request` to the crates.io API, or to the registry
+/// set with [`Config::registry`]: the path gets the registry's prefix, and the
+/// [`Config::token`], if any, is sent unless the request has its own `Authorization`.
+pub(crate) fn api_request<'a>(request: &Request, config: &'a Config) -> (&'a str, Request) {
+    let (host, prefix) = config.api_base();
+    let mut request = request.prefixed(prefix);
+    if let Some(token) = &config.token && request.header_value("authorization").is_none() {
+        request = request.header("Authorization", token);
+    }
+    (host, request)
+}
+
+/// Sends `request` to the registry API (see [`api_request`]) through [`fetch_async`].
+pub(crate) async fn fetch_api(request: &Request, config: &Config) -> Result<(String, String), Box<dyn Error>> {
+    let (host, request) = api_request(request, config);
+    fetch_async(host, &request, config).await
+}
+
+/// Sends `request` and returns the response head and the fully buffered body,
+/// waiting for the network without blocking the executor thread. Unauthenticated
+/// `GET` requests go through the configured [`ResponseCache`](crate::ResponseCache), if any, and
+/// expired entries with an `ETag` are revalidated with `If-None-Match` (in the
+/// background, while serving the stale entry, within its stale-while-revalidate window).
+/// With [`Config::coalesce_requests`], identical `GET`s in flight share one request.
+///
+/// See [`transport`] for how the socket is driven; without a non-blocking
+/// transport, the blocking request runs on a helper thread. On wasm32, the
+/// request goes through the JavaScript `fetch` API instead.
+pub(crate) async fn fetch_async(host: &str, request: &Request, config: &Config) -> Result<(String, String), Box<dyn Error>> {
+    let cache = config.cache.as_ref().filter(|_| request.method() == "GET" && request.header_value("authorization").is_none());
+    let mut revalidating = None;
+    let conditional;
+    let request = match cache.map(|cache| cache.get(host, &request.target())) {
+        Some(Lookup::Fresh(head, body)) => return Ok((head, body)),
+        Some(Lookup::Stale { etag, head, body, serve: true }) => {
+            refresh_in_background(host, request, config, etag, (head.clone(), body.clone()));
+            return Ok((head, body));
+        }
+        // Requests carrying their own validator get the server's 304 as is
+        Some(Lookup::Stale { etag: Some(etag), head, body, .. }) if request.header_value("if-none-match").is_none() => {
+            conditional = request.clone().header("If-None-Match", &etag);
+            revalidating = Some((head, body));
+            &conditional
+        }
+        _ => request,
+    };
+    let fetch = async {
+        #[cfg(target_arch = "wasm32")]
+        let result = crate::wasm::fetch(host, request, config).await;
+        #[cfg(not(target_arch = "wasm32"))]
+        let result = match transport() {
+            Some(transport) => fetch_nonblocking(host, request, config, transport).await,
+            None => {
+                let (host, request, config) = (host.to_string(), request.clone(), config.clone());
+                spawn_blocking(move || fetch_body(&host, &request, &config).map_err(sendable)).await.map_err(|e| e as Box<dyn Error>)
+            }
+        };
+        abortable(config, result)
+    };
+    let fetch = async {
+        match &config.cancel {
+            Some(token) => cancellable(fetch, token).await.unwrap_or_else(|cancelled| Err(Box::new(cancelled))),
+            None => fetch.await,
+        }
+    };
+    let (head, body) = match &config.in_flight {
+        Some(in_flight) if request.method() == "GET" => in_flight.run(coalesce_key(host, request), fetch).await,
+        _ => fetch.await,
+    }?;
+    if let Some(cache) = cache {
+        if let Some((cached_head, cached_body)) = revalidating && parse_status_line(&head)?.0 == 304 {
+            cache.insert(host, &request.target(), &cached_head, &cached_body);
+            return Ok((cached_head, cached_body));
+        }
+        if ensure_success(&head).is_ok() { cache.insert(host, &request.target(), &head, &body); }
+    }
+    Ok((head, body))
+}
+
+/// Identifies requests that can share a response: same host, target and headers.
+fn coalesce_key(host: &str, request: &Request) -> String {
+    let headers: String = request.headers().iter().map(|(name, value)| format!("\n{}: {}", name.to_ascii_lowercase(), value)).collect();
+    format!("{} {}{}", host, request.target(), headers)
+}
+
+/// Refetches a stale cache entry without making the caller wait, revalidating it
+/// with its `ETag` when it has one. At most one refresh per entry runs at a time,
+/// and a failed refresh leaves the stale entry in place.
+fn refresh_in_background(host: &str, request: &Request, config: &Config, etag: Option<String>, cached: (String, String)) {
+    let Some(cache) = config.cache.clone() else { return };
+    let target = request.target();
+    if !cache.begin_refresh(host, &target) { return; }
+    let request = match etag {
+        Some(etag) if request.header_value("if-none-match").is_none() => request.clone().header("If-None-Match", &etag),
+        _ => request.clone(),
+    };
+    let (host, config) = (host.to_string(), config.clone());
+    let store = {
+        let host = host.clone();
+        move |response: Result<(String, String), Box<dyn Error>>| {
+            if let Ok((head, body)) = response {
+                match parse_status_line(&head) {
+                    Ok((304, _)) => cache.insert(&host, &target, &cached.0, &cached.1),
+                    Ok((200..=299, _)) => cache.insert(&host, &target, &head, &body),
+                    _ => {}
+                }
+            }
+            cache.end_refresh(&host, &target);
+        }
+    };
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(async move {
+        let response = crate::wasm::fetch(&host, &request, &config).await;
+        store(response);
+    });
+    #[cfg(not(target_arch = "wasm32"))]
+    drop(spawn_blocking(move || store(fetch_body(&host, &request, &config))));
+}
+
+/// Makes an error transferable across threads, keeping [`FetchError`]s and I/O errors intact.
+pub(crate) fn sendable(error: Box<dyn Error>) -> Box<dyn Error + Send + Sync> {
+    let error = match error.downcast::<FetchError>() {
+        Ok(error) => return error,
+        Err(error) => error,
+    };
+    match error.downcast::<io::Error>() {
+        Ok(error) => error,
+        Err(error) => error.to_string().into(),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// The socket layer driving a non-blocking fetch.
+#[derive(Clone, Copy)]
+enum Transport {
+    /// crator's own `poll(2)` reactor.
+    #[cfg(all(unix, not(feature = "async-io")))]
+    Reactor,
+    /// `tokio::net`, when awaited inside a tokio runtime.
+    #[cfg(feature = "tokio")]
+    Tokio,
+    /// The `async-io` reactor shared with smol-based stacks.
+    #[cfg(feature = "async-io")]
+    AsyncIo,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Picks the transport for the current context: tokio inside a tokio runtime, then
+/// `async-io`, then crator's reactor. Returns `None` when none is available.
+fn transport() -> Option<Transport> {
+    #[cfg(feature = "tokio")]
+    if tokio::runtime::Handle::try_current().is_ok() { return Some(Transport::Tokio); }
+    #[cfg(feature = "async-io")]
+    let fallback = Some(Transport::AsyncIo);
+    #[cfg(all(unix, not(feature = "async-io")))]
+    let fallback = Some(Transport::Reactor);
+    #[cfg(not(any(unix, feature = "async-io")))]
+    let fallback = None;
+    fallback
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn fetch_nonblocking(host: &str, request: &Request, config: &Config, transport: Transport) -> Result<(String, String), Box<dyn Error>> {
+    let mut attempt = 0;
+    loop {
+        if let Some(limiter) = &config.rate_limiter { limiter.acquire().await; }
+        let response = match transport {
+            #[cfg(all(unix, not(feature = "async-io")))]
+            Transport::Reactor => exchange(host, request, config, Poller::reactor).await?,
+            #[cfg(feature = "tokio")]
+            Transport::Tokio => crate::tokio_net::exchange(host, request, config).await?,
+            #[cfg(feature = "async-io")]
+            Transport::AsyncIo => exchange(host, request, config, Poller::async_io).await?,
+        };
+        let mut reader = io::Cursor::new(response);
+        let head = read_head(&mut reader)?;
+        if parse_status_line(&head)?.0 == 429 && attempt < config.rate_limit_retries {
+            // Same backoff as the blocking path, without holding up the executor thread
+            timer::sleep(retry_after(&head).unwrap_or(Duration::from_secs(1 << attempt.min(6)))).await;
+            attempt += 1;
+            continue;
+        }
+        return buffer_body(head, &mut reader, config);
+    }
+}
+
+/// Bounds the raw response buffered by a non-blocking exchange.
+///
+/// The body limit is enforced once the response is parsed; until then, the buffer
+/// may hold twice the limit (room for chunk framing) plus the head.
+#[cfg(any(unix, feature = "tokio", feature = "async-io"))]
+pub(crate) fn check_buffered(len: usize, config: &Config) -> Result<(), FetchError> {
+    match config.max_response_size {
+        Some(limit) if len > limit.saturating_mul(2).saturating_add(64 * 1024) => Err(FetchError::ResponseTooLarge { limit }),
+        _ => Ok(()),
+    }
+}
+
+/// Waits for a non-blocking socket to become ready on behalf of [`exchange`].
+#[cfg(any(unix, feature = "async-io"))]
+enum Poller {
+    #[cfg(all(unix, not(feature = "async-io")))]
+    Reactor(Source),
+    #[cfg(feature = "async-io")]
+    AsyncIo(async_io::Async<TcpStream>),
+}
+
+#[cfg(any(unix, feature = "async-io"))]
+impl Poller {
+    #[cfg(all(unix, not(feature = "async-io")))]
+    fn reactor(stream: &TcpStream) -> io::Result<Self> {
+        Source::new(stream).map(Poller::Reactor)
+    }
+
+    /// Registers a duplicate of the socket, so the TLS layer can keep the original.
+    #[cfg(feature = "async-io")]
+    fn async_io(stream: &TcpStream) -> io::Result<Self> {
+        async_io::Async::new(stream.try_clone()?).map(Poller::AsyncIo)
+    }
+
+    async fn readable(&self) -> io::Result<()> {
+        match self {
+            #[cfg(all(unix, not(feature = "async-io")))]
+            Poller::Reactor(source) => { source.readable().await; Ok(()) }
+            #[cfg(feature = "async-io")]
+            Poller::AsyncIo(socket) => socket.readable().await,
+        }
+    }
+
+    async fn writable(&self) -> io::Result<()> {
+        match self {
+            #[cfg(all(unix, not(feature = "async-io")))]
+            Poller::Reactor(source) => { source.writable().await; Ok(()) }
+            #[cfg(feature = "async-io")]
+            Poller::AsyncIo(socket) => socket.writable().await,
+        }
+    }
+}
+
+/// Sends `request` over a fresh non-blocking connection and returns the raw response.
+///
+/// `poller` wraps the connected socket for readiness notifications. The response is
+/// read until the server closes the connection (`Connection: close`).
+#[cfg(any(unix, feature = "async-io"))]
+async fn exchange(host: &str, request: &Request, config: &Config, poller: fn(&TcpStream) -> io::Result<Poller>) -> Result<Vec<u8>, Box<dyn Error>> {
+    let connector = config.tls_connector()?;
+    // DNS resolution and Happy Eyeballs block, so they run on a helper thread
+    let (name, port) = net::split_port(host, 443);
+    let stream = {
+        let (name, config) = (name.to_string(), config.clone());
+        spawn_blocking(move || net::connect(&name, port, &config)).await?
+    };
+    let registration = config.abort.as_ref().map(|handle| handle.register(&stream)).transpose()?;
+    stream.set_nonblocking(true)?;
+    let source = poller(&stream)?;
+
+    let mut tls_stream = match connector.connect(name, stream) {
+        Ok(tls_stream) => tls_stream,
+        Err(HandshakeError::WouldBlock(mut handshake)) => loop {
+            source.readable().await?;
+            match handshake.handshake() {
+                Ok(tls_stream) => break tls_stream,
+                Err(HandshakeError::WouldBlock(pending)) => handshake = pending,
+                Err(HandshakeError::Failure(e)) => return Err(e.into()),
+            }
+        },
+        Err(HandshakeError::Failure(e)) => return Err(e.into()),
+    };
+
+    let raw = request_head(host, request, config);
+    let mut written = 0;
+    while written < raw.len() {
+        match tls_stream.write(&raw.as_bytes()[written..]) {
+            Ok(0) => return Err(io::Error::from(io::ErrorKind::WriteZero).into()),
+            Ok(n) => written += n,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => source.writable().await?,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let (mut response, mut buf) = (Vec::new(), [0u8; 8192]);
+    loop {
+        match tls_stream.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => response.extend_from_slice(&buf[..n]),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => source.readable().await?,
+            Err(e) => return Err(e.into()),
+        }
+        check_buffered(response.len(), config)?;
+    }
+    // An abort shuts the socket down, which reads like a regular (truncated) end of the body
+    if registration.is_some_and(|registration| registration.is_aborted()) { return Err(Box::new(FetchError::Aborted)); }
+    Ok(response)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Connects to `host`, sends `request`, and reads the response head (status line + headers).
+///
+/// The returned reader is positioned at the start of the body, which the caller
+/// consumes with [`read_body`] (or drops, e.g. for non-2xx responses). `429 Too Many
+/// Requests` responses are retried after their `Retry-After` delay as many times as
+/// [`Config::retry_rate_limited`] allows.
+pub(crate) fn open(host: &str, request: &Request, config: &Config) -> Result<(String, Connection), Box<dyn Error>> {
+    abortable(config, open_with_retries(host, request, config))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn open_with_retries(host: &str, request: &Request, config: &Config) -> Result<(String, Connection), Box<dyn Error>> {
+    let mut attempt = 0;
+    loop {
+        if let Some(limiter) = &config.rate_limiter { limiter.acquire_blocking(); }
+        let (head, reader) = open_once(host, request, config)?;
+        if parse_status_line(&head)?.0 != 429 || attempt >= config.rate_limit_retries { return Ok((head, reader)); }
+        // Without a hint, back off exponentially: 1s, 2s, 4s, ...
+        let delay = retry_after(&head).unwrap_or(Duration::from_secs(1 << attempt.min(6)));
+        thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn open_once(host: &str, request: &Request, config: &Config) -> Result<(String, Connection), Box<dyn Error>> {
+    let connector = config.tls_connector()?;
+    let (name, port) = net::split_port(host, 443);
+    let stream = net::connect(name, port, config)?;
+    let registration = config.abort.as_ref().map(|handle| handle.register(&stream)).transpose()?;
+    let mut tls_stream = connector.connect(name, stream)?;
+    tls_stream.write_all(request_head(host, request, config).as_bytes())?;
+
+    let mut reader = Connection { reader: BufReader::new(tls_stream), _registration: registration };
+    let head = read_head(&mut reader)?;
+    Ok((head, reader))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Serializes the request line and headers of `request`, asking the server to close the connection afterwards.
+pub(crate) fn request_head(host: &str, request: &Request, config: &Config) -> String {
+    let mut raw = format!("{} {} HTTP/1.1\r\nHost: {}\r\n", request.method(), request.target(), host);
+    raw.push_str("Connection: close\r\n");
+    for (name, value) in outgoing_headers(request, config) {
+        raw.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    raw.push_str("\r\n");
+    raw
+}
+
+/// Lists the headers to send with `request`: the configured defaults (unless the
+/// request overrides them), the request's own headers, and crator's `User-Agent`
+/// if none was given.
+pub(crate) fn outgoing_headers<'a>(request: &'a Request, config: &'a Config) -> Vec<(&'a str, &'a str)> {
+    let mut headers: Vec<(&str, &str)> = config.headers.iter()
+        .filter(|(name, _)| request.header_value(name).is_none())
+        .chain(request.headers())
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("user-agent")) {
+        headers.insert(0, ("User-Agent", "crator_safe/1.0"));
+    }
+    headers
+}
+
+/// Reads the status line and headers, up to (and excluding) the blank separator line.
+#[cfg(not(target_arch = "wasm32"))]
+fn read_head<R: BufRead>(reader: &mut R) -> Result<String, Box<dyn Error>> {
+    let mut head = String::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line == "\r\n" || line == "\n" { break; }
+        head.push_str(&line);
+    }
+    if head.is_empty() { return Err(Box::new(FetchError::MalformedResponse)); }
+    Ok(head.trim_end().to_string())
+}
+
+/// Streams the body to `on_chunk`, decoding `Transfer-Encoding: chunked` when present.
+///
+/// An error returned by `on_chunk` aborts the transfer.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn read_body<R: BufRead>(reader: &mut R, head: &str, on_chunk: &mut ChunkSink) -> Result<(), Box<dyn Error>> {
+    let mut buf = [0u8; 8192];
+    // Informational, 204 and 304 responses never carry a body
+    let status = parse_status_line(head)?.0;
+    if status < 200 || status == 204 || status == 304 { return Ok(()); }
+    let chunked = header(head, "transfer-encoding").is_some_and(|v| v.to_ascii_lowercase().contains("chunked"));
+    if !chunked {
+        loop {
+            let n = reader.read(&mut buf)?;
+            if n == 0 { return Ok(()); }
+            on_chunk(&buf[..n])?;
+        }
+    }
+    loop {
+        let mut size_line = String::new();
+        if reader.read_line(&mut size_line)? == 0 { return Ok(()); }
+        // Chunk extensions (";name=value") are permitted after the size
+        let size_hex = size_line.split(';').next().unwrap_or("").trim();
+        let mut remaining = usize::from_str_radix(size_hex, 16).map_err(|_| FetchError::MalformedResponse)?;
+        if remaining == 0 { return Ok(()); }
+        while remaining > 0 {
+            let n = reader.read(&mut buf[..remaining.min(8192)])?;
+            if n == 0 { return Err(Box::new(FetchError::MalformedResponse)); }
+            on_chunk(&buf[..n])?;
+            remaining -= n;
+        }
+        // Consume the CRLF terminating the chunk data
+        reader.read_line(&mut String::new())?;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// How many body chunks a streaming transfer may queue before waiting for the consumer.
+const MAX_QUEUED_CHUNKS: usize = 16;
+
+#[cfg(not(target_arch = "wasm32"))]
+/// State shared between a streaming transfer's helper thread and its [`BodyStream`].
+#[derive(Default)]
+struct Transfer {
+    chunks: VecDeque<Vec<u8>>,
+    /// The outcome of the transfer, set once it has ended.
+    done: Option<Result<(), Box<dyn Error + Send + Sync>>>,
+    /// Set when the [`BodyStream`] is dropped, to stop the transfer.
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// The body of a successful response, streamed from a helper thread.
+pub(crate) struct BodyStream {
+    shared: Arc<(Mutex<Transfer>, Condvar)>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Sends `request` on a helper thread and streams the body of its 2xx response back,
+/// so the awaiting task never blocks on the socket.
+///
+/// A non-2xx response ends the stream with a [`FetchError::Status`] before any chunk.
+pub(crate) fn stream(host: String, request: Request, config: Config) -> BodyStream {
+    let shared = Arc::new((Mutex::new(Transfer::default()), Condvar::new()));
+    let producer = Arc::clone(&shared);
+    thread::spawn(move || {
+        let (transfer, space) = &*producer;
+        let result = open(&host, &request, &config).and_then(|(head, mut reader)| {
+            ensure_success(&head)?;
+            abortable(&config, read_body(&mut reader, &head, &mut |chunk| {
+                let mut state = lock(transfer);
+                // Backpressure: wait for the consumer instead of buffering the whole body
+                while state.chunks.len() >= MAX_QUEUED_CHUNKS && !state.closed {
+                    state = space.wait(state).unwrap_or_else(|e| e.into_inner());
+                }
+                if state.closed { return Err("body stream dropped".into()); }
+                state.chunks.push_back(chunk.to_vec());
+                let waker = state.waker.take();
+                drop(state);
+                waker.into_iter().for_each(Waker::wake);
+                Ok(())
+            }))
+        });
+        let mut state = lock(transfer);
+        state.done = Some(result.map_err(sendable));
+        let waker = state.waker.take();
+        drop(state);
+        waker.into_iter().for_each(Waker::wake);
+    });
+    BodyStream { shared }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BodyStream {
+    /// Resolves to the next body chunk, or `None` once the body is complete.
+    pub(crate) async fn next(&mut self) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+        future::poll_fn(|cx| {
+            let (transfer, space) = &*self.shared;
+            let mut state = lock(transfer);
+            if let Some(chunk) = state.chunks.pop_front() {
+                space.notify_one();
+                return Poll::Ready(Ok(Some(chunk)));
+            }
+            match state.done.take() {
+                Some(Ok(())) => { state.done = Some(Ok(())); Poll::Ready(Ok(None)) }
+                Some(Err(e)) => Poll::Ready(Err(e as Box<dyn Error>)),
+                None => { state.waker = Some(cx.waker().clone()); Poll::Pending }
+            }
+        }).await
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for BodyStream {
+    fn drop(&mut self) {
+        let (transfer, space) = &*self.shared;
+        lock(transfer).closed = true;
+        space.notify_one();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn lock(transfer: &Mutex<Transfer>) -> MutexGuard<'_, Transfer> {
+    transfer.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Parses the status line (e.g. `HTTP/1.1 404 Not Found`) at the start of a response head.
+pub(crate) fn parse_status_line(head: &str) -> Result<(u16, String), FetchError> {
+    let line = head.lines().next().unwrap_or("");
+    let mut parts = line.splitn(3, ' ');
+    if !parts.next().unwrap_or("").starts_with("HTTP/") { return Err(FetchError::MalformedResponse); }
+    let code = parts.next().and_then(|c| c.parse::<u16>().ok()).ok_or(FetchError::MalformedResponse)?;
+    let reason = parts.next().unwrap_or("").trim().to_string();
+    Ok((code, reason))
+}
+
+/// Returns an error unless the response head carries a 2xx status code.
+///
+/// `429 Too Many Requests` maps to [`FetchError::RateLimited`].
+pub(crate) fn ensure_success(head: &str) -> Result<u16, FetchError> {
+    let (code, reason) = parse_status_line(head)?;
+    match code {
+        200..=299 => Ok(code),
+        429 => Err(FetchError::RateLimited { retry_after: retry_after(head) }),
+        _ => Err(FetchError::Status { code, reason }),
+    }
+}
+
+/// Parses the `Retry-After` header, given either as delay seconds or as an HTTP date.
+pub(crate) fn retry_after(head: &str) -> Option<Duration> {
+    let value = header(head, "retry-after")?;
+    if let Ok(secs) = value.parse::<u64>() { return Some(Duration::from_secs(secs)); }
+    let at = parse_http_date(value)?;
+    Some(at.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+}
+
+/// Parses every header line of a response head into `(name, value)` pairs, in order.
+pub(crate) fn parse_headers(head: &str) -> Vec<(String, String)> {
+    head.lines().skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Returns the value of the first header named `name` (case-insensitive) in a response head.
+pub(crate) fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if key.trim().eq_ignore_ascii_case(name) { Some(value.trim()) } else { None }
+    })
+}
+
+/// Formats a point in time as an IMF-fixdate (e.g. `Sun, 06 Nov 1994 08:49:37 GMT`).
+pub(crate) fn http_date(time: SystemTime) -> String {
+    const DAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        DAYS[(days % 7) as usize], day, MONTHS[(month - 1) as usize], year,
+        rem / 3600, (rem % 3600) / 60, rem % 60
+    )
+}
+
+/// Parses an IMF-fixdate (e.g. `Sun, 06 Nov 1994 08:49:37 GMT`) into a point in time,
+/// rejecting out-of-range fields and years outside 1970..=9999.
+pub(crate) fn parse_http_date(value: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let mut parts = value.split_whitespace().skip(1);
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month_name = parts.next()?;
+    let month = MONTHS.iter().position(|m| *m == month_name)? as u32 + 1;
+    let year: i64 = parts.next()?.parse().ok()?;
+    let mut clock = parts.next()?.split(':').map(|p| p.parse::<u64>().ok());
+    let (h, m, s) = (clock.next()??, clock.next()??, clock.next()??);
+    if !(1..=31).contains(&day) || !(1970..=9999).contains(&year) || h >= 24 || m >= 60 || s > 60 { return None; }
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    let secs = days.checked_mul(86_400)?.checked_add(h * 3600 + m * 60 + s)?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs))
+}
+
+/// Converts a `(year, month, day)` civil date into days since the Unix epoch.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Converts days since the Unix epoch into a `(year, month, day)` civil date.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Percent-encodes a query component, leaving RFC 3986 unreserved characters intact.
+pub(crate) fn percent_encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for &b in input.as_bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => out.push(b as char),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
@@ -0,0 +1,376 @@
+use crate::config::IndexSource;
+use crate::{http, Config, DependencyInfo, DependencyKind, Json, JsonError, Request, Value, Version, VersionInfo};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{Manifest, Toml};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+
+/// Serves the crates.io index over Cargo's sparse protocol.
+const SPARSE_INDEX: &str = "index.crates.io";
+
+/// The `config.json` at the root of a registry index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexConfig {
+    /// The download URL template for `.crate` files (e.g. `"https://static.crates.io/crates"`);
+    /// may contain markers like `{crate}` and `{version}`.
+    pub dl: String,
+    /// The base URL of the registry's web API, if it has one.
+    pub api: Option<String>,
+    /// Whether every request to the registry needs a token.
+    pub auth_required: bool,
+}
+
+/// A dependency of one [`IndexEntry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexDependency {
+    /// The name of the depended-on crate in the registry (e.g. "serde_derive").
+    pub name: String,
+    /// The name the dependency is imported under when renamed with `package = ...`.
+    pub rename: Option<String>,
+    /// The version requirement (e.g. "^1.0").
+    pub req: String,
+    /// Whether it is a regular, dev-, or build-dependency.
+    pub kind: DependencyKind,
+    /// Whether the dependency is only enabled through a feature.
+    pub optional: bool,
+    /// Whether the dependency's default features are enabled.
+    pub default_features: bool,
+    /// The features explicitly enabled on the dependency.
+    pub features: Vec<String>,
+    /// The platform restriction (e.g. `cfg(windows)`), if any.
+    pub target: Option<String>,
+}
+
+/// One published version of a crate, as recorded in the registry index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexEntry {
+    /// The crate name.
+    pub name: String,
+    /// The version number (e.g. "1.0.210").
+    pub vers: String,
+    /// The dependencies of this version.
+    pub deps: Vec<IndexDependency>,
+    /// The SHA-256 checksum of the `.crate` file, hex-encoded.
+    pub cksum: String,
+    /// The features and what each enables, merging `features` and `features2`.
+    pub features: BTreeMap<String, Vec<String>>,
+    /// Whether the version was withdrawn with `cargo yank`.
+    pub yanked: bool,
+    /// The native library linked through `links`, if any.
+    pub links: Option<String>,
+    /// The minimum supported Rust version (`rust-version`), if declared.
+    pub rust_version: Option<String>,
+}
+
+fn strings(value: Option<&Value>) -> Vec<String> {
+    value.and_then(Value::as_array).unwrap_or_default().iter().filter_map(|item| item.as_str().map(str::to_string)).collect()
+}
+
+fn string(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+impl IndexDependency {
+    fn parse(value: &Value) -> Self {
+        let name = string(value, "name").unwrap_or_default();
+        let (name, rename) = match string(value, "package") {
+            Some(package) => (package, Some(name)),
+            None => (name, None),
+        };
+        IndexDependency {
+            name,
+            rename,
+            req: string(value, "req").unwrap_or_else(|| "*".to_string()),
+            kind: match value.get("kind").and_then(Value::as_str) {
+                Some("dev") => DependencyKind::Dev,
+                Some("build") => DependencyKind::Build,
+                _ => DependencyKind::Normal,
+            },
+            optional: value.get("optional").and_then(Value::as_bool).unwrap_or(false),
+            default_features: value.get("default_features").and_then(Value::as_bool).unwrap_or(true),
+            features: strings(value.get("features")),
+            target: string(value, "target"),
+        }
+    }
+
+    /// The same dependency in the web API's shape.
+    pub(crate) fn to_dependency_info(&self) -> DependencyInfo {
+        DependencyInfo {
+            name: self.name.clone(),
+            req: self.req.clone(),
+            kind: self.kind,
+            optional: self.optional,
+            default_features: self.default_features,
+            features: self.features.clone(),
+            target: self.target.clone(),
+        }
+    }
+}
+
+impl IndexEntry {
+    /// Parses one line of an index file.
+    ///
+    /// # Errors
+    /// Fails if the line isn't a JSON object with a `name` and a `vers`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{DependencyKind, IndexEntry};
+    ///
+    /// let entry = IndexEntry::parse(r#"{"name":"demo","vers":"0.2.0","deps":[{"name":"json","package":"serde_json","req":"^1","features":[],"optional":true,"default_features":true,"target":null,"kind":"normal"}],"cksum":"ab12","features":{"default":["std"],"std":[]},"features2":{"json":["dep:json"]},"yanked":false,"rust_version":"1.70"}"#)
+    ///     .expect("Invalid index entry");
+    ///
+    /// assert_eq!(entry.vers, "0.2.0");
+    /// assert_eq!(entry.deps[0].name, "serde_json");
+    /// assert_eq!(entry.deps[0].rename.as_deref(), Some("json"));
+    /// assert_eq!(entry.deps[0].kind, DependencyKind::Normal);
+    /// assert_eq!(entry.features["json"], ["dep:json"]);
+    /// assert_eq!(entry.rust_version.as_deref(), Some("1.70"));
+    /// ```
+    pub fn parse(line: &str) -> Result<Self, JsonError> {
+        let value = Json::parse(line)?;
+        let required = |key: &str| string(&value, key).ok_or_else(|| JsonError::PathNotFound { path: key.to_string() });
+        let mut features = BTreeMap::new();
+        for table in ["features", "features2"] {
+            for (name, enables) in value.get(table).and_then(Value::as_object).unwrap_or_default() {
+                features.insert(name.clone(), strings(Some(enables)));
+            }
+        }
+        Ok(IndexEntry {
+            name: required("name")?,
+            vers: required("vers")?,
+            deps: value.get("deps").and_then(Value::as_array).unwrap_or_default().iter().map(IndexDependency::parse).collect(),
+            cksum: string(&value, "cksum").unwrap_or_default(),
+            features,
+            yanked: value.get("yanked").and_then(Value::as_bool).unwrap_or(false),
+            links: string(&value, "links"),
+            rust_version: string(&value, "rust_version"),
+        })
+    }
+
+    /// The same version in the web API's shape, minus what the index doesn't record.
+    pub(crate) fn to_version_info(&self, crate_size: Option<u64>) -> VersionInfo {
+        VersionInfo {
+            num: self.vers.clone(),
+            yanked: self.yanked,
+            license: None,
+            created_at: "N/A".to_string(),
+            downloads: 0,
+            rust_version: self.rust_version.clone(),
+            crate_size,
+        }
+    }
+}
+
+/// The path of a crate's file in the index, relative to its root: `1/a`, `2/ab`,
+/// `3/a/abc`, or the first two pairs of letters for longer names (`se/rd/serde`).
+///
+/// # Example
+/// ```rust
+/// use crator::index_path;
+///
+/// assert_eq!(index_path("serde"), "se/rd/serde");
+/// assert_eq!(index_path("syn"), "3/s/syn");
+/// assert_eq!(index_path("Inflector"), "in/fl/inflector");
+/// ```
+pub fn index_path(crate_name: &str) -> String {
+    let name = crate_name.to_ascii_lowercase();
+    let prefix = |range: std::ops::Range<usize>| name.chars().skip(range.start).take(range.len()).collect::<String>();
+    match name.chars().count() {
+        count @ (1 | 2) => format!("{}/{}", count, name),
+        3 => format!("3/{}/{}", prefix(0..1), name),
+        _ => format!("{}/{}/{}", prefix(0..2), prefix(2..4), name),
+    }
+}
+
+/// The index directory of a [`Config::local_registry`]: `index/` for a `cargo
+/// local-registry` layout, else the directory itself.
+#[cfg(not(target_arch = "wasm32"))]
+fn index_dir(root: &Path) -> PathBuf {
+    let nested = root.join("index");
+    if nested.is_dir() { nested } else { root.to_path_buf() }
+}
+
+/// Names a file in an I/O error.
+#[cfg(not(target_arch = "wasm32"))]
+fn with_path(path: &Path) -> impl FnOnce(io::Error) -> io::Error + '_ {
+    move |e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e))
+}
+
+/// A crate version vendored by `cargo vendor`, with the license the index lacks.
+#[cfg(not(target_arch = "wasm32"))]
+struct Vendored {
+    entry: IndexEntry,
+    license: Option<String>,
+}
+
+/// Reads the versions of a crate in a `cargo vendor` directory, oldest first, from
+/// their manifests and `.cargo-checksum.json` files.
+#[cfg(not(target_arch = "wasm32"))]
+fn vendored(dir: &Path, crate_name: &str) -> Result<Vec<Vendored>, Box<dyn Error>> {
+    let mut found = Vec::new();
+    for item in fs::read_dir(dir).map_err(with_path(dir))? {
+        let path = item?.path();
+        // Directories are named `name`, or `name-version` when several versions are vendored
+        let Some(rest) = path.file_name().and_then(|name| name.to_str()).and_then(|name| name.strip_prefix(crate_name)) else { continue };
+        if !rest.is_empty() && !rest.strip_prefix('-').is_some_and(|version| version.starts_with(|c: char| c.is_ascii_digit())) { continue; }
+        let manifest_path = path.join("Cargo.toml");
+        let Ok(text) = fs::read_to_string(&manifest_path) else { continue };
+        let error = |e: crate::TomlError| format!("{}: {}", manifest_path.display(), e);
+        let manifest = Manifest::parse(&text).map_err(error)?;
+        if manifest.name.as_deref() != Some(crate_name) { continue; }
+        let document = Toml::parse(&text).map_err(error)?;
+        let package = |key: &str| document.get("package").and_then(|package| package.get(key)).and_then(Value::as_str).map(str::to_string);
+        let entry = IndexEntry {
+            name: crate_name.to_string(),
+            vers: manifest.version.clone().unwrap_or_default(),
+            deps: manifest.dependencies.into_iter().map(|dependency| IndexDependency {
+                name: dependency.name,
+                rename: dependency.rename,
+                req: dependency.req.unwrap_or_else(|| "*".to_string()),
+                kind: dependency.kind,
+                optional: dependency.optional,
+                default_features: dependency.default_features,
+                features: dependency.features,
+                target: dependency.target,
+            }).collect(),
+            cksum: fs::read_to_string(path.join(".cargo-checksum.json")).ok().and_then(|text| Json::extract_opt(&text, "package")).unwrap_or_default(),
+            features: document.get("features").and_then(Value::as_object).unwrap_or_default().iter()
+                .map(|(name, enables)| (name.clone(), strings(Some(enables))))
+                .collect(),
+            yanked: false,
+            links: package("links"),
+            rust_version: package("rust-version"),
+        };
+        found.push(Vendored { entry, license: package("license") });
+    }
+    if found.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("{} is not vendored in {}", crate_name, dir.display())).into());
+    }
+    found.sort_by_cached_key(|vendored| Version::parse(&vendored.entry.vers));
+    Ok(found)
+}
+
+/// Parses an index file, skipping lines that can't be parsed.
+fn parse_lines(body: &str) -> Vec<IndexEntry> {
+    body.lines().filter(|line| !line.trim().is_empty()).filter_map(|line| IndexEntry::parse(line).ok()).collect()
+}
+
+/// Fetches a file of the sparse index, failing on non-2xx responses, or reads it
+/// from the configured [`Config::sparse_index`] or [`Config::local_registry`].
+async fn get(path: &str, config: &Config) -> Result<String, Box<dyn Error>> {
+    let (host, prefix) = match &config.index_source {
+        None => (SPARSE_INDEX, ""),
+        Some(IndexSource::Sparse(index)) => (index.host.as_str(), index.prefix.as_str()),
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(IndexSource::Local(root)) => {
+            let file = index_dir(root).join(path.trim_start_matches('/'));
+            return Ok(fs::read_to_string(&file).map_err(with_path(&file))?);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(IndexSource::Directory(dir)) => {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, format!("{} is a vendor directory, not an index", dir.display())).into());
+        }
+    };
+    let (head, body) = http::fetch_async(host, &Request::new("GET", &format!("{}{}", prefix, path)), config).await?;
+    http::ensure_success(&head)?;
+    Ok(body)
+}
+
+/// The index entries of a crate from the configured source, oldest first.
+async fn entries(crate_name: &str, config: &Config) -> Result<Vec<IndexEntry>, Box<dyn Error>> {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(IndexSource::Directory(dir)) = &config.index_source {
+        return Ok(vendored(dir, crate_name)?.into_iter().map(|vendored| vendored.entry).collect());
+    }
+    Ok(parse_lines(&get(&format!("/{}", index_path(crate_name)), config).await?))
+}
+
+/// The published versions of a crate in the [`Config`]'s index source, newest first
+/// like the web API lists them. Local registries contribute the archive sizes of
+/// their `.crate` files and vendor directories the licenses of their manifests.
+pub(crate) async fn source_versions(crate_name: &str, config: &Config) -> Result<Vec<VersionInfo>, Box<dyn Error>> {
+    let mut versions: Vec<VersionInfo> = match &config.index_source {
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(IndexSource::Local(root)) => entries(crate_name, config).await?.iter().map(|entry| {
+            let size = fs::metadata(root.join(format!("{}-{}.crate", entry.name, entry.vers))).ok().map(|metadata| metadata.len());
+            entry.to_version_info(size)
+        }).collect(),
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(IndexSource::Directory(dir)) => vendored(dir, crate_name)?.into_iter()
+            .map(|vendored| VersionInfo { license: vendored.license, ..vendored.entry.to_version_info(None) })
+            .collect(),
+        _ => entries(crate_name, config).await?.iter().map(|entry| entry.to_version_info(None)).collect(),
+    };
+    versions.sort_by_cached_key(|version| Reverse(Version::parse(&version.num)));
+    Ok(versions)
+}
+
+/// The dependencies of one version of a crate in the [`Config`]'s index source.
+pub(crate) async fn source_dependencies(crate_name: &str, version: &str, config: &Config) -> Result<Vec<DependencyInfo>, Box<dyn Error>> {
+    let entries = entries(crate_name, config).await?;
+    let entry = entries.iter().find(|entry| entry.vers == version)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{} {} is not in the index", crate_name, version)))?;
+    Ok(entry.deps.iter().map(IndexDependency::to_dependency_info).collect())
+}
+
+/// Fetches the `config.json` of the crates.io sparse index.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, index_config};
+///
+/// let config = block_on(index_config()).expect("Failed to fetch index config");
+/// println!("Downloads from {}", config.dl);
+/// ```
+pub async fn index_config() -> Result<IndexConfig, Box<dyn Error>> {
+    index_config_with(crate::default_config()).await
+}
+
+/// Same as [`index_config`], but driven by a custom [`Config`].
+pub async fn index_config_with(config: &Config) -> Result<IndexConfig, Box<dyn Error>> {
+    let value = Json::parse(&get("/config.json", config).await?)?;
+    Ok(IndexConfig {
+        dl: string(&value, "dl").ok_or_else(|| JsonError::PathNotFound { path: "dl".to_string() })?,
+        api: string(&value, "api"),
+        auth_required: value.get("auth-required").and_then(Value::as_bool).unwrap_or(false),
+    })
+}
+
+/// Fetches every published version of a crate from the crates.io sparse index
+/// (`index.crates.io`), oldest first.
+///
+/// The index is what Cargo itself resolves against: one small, CDN-cached file per
+/// crate with the dependencies, features, checksum and yank status of each version,
+/// much faster than the web API. Lines that can't be parsed (e.g. from a newer
+/// schema) are skipped, like Cargo does.
+///
+/// # Errors
+/// A non-2xx response (e.g. an unknown crate) yields a [`FetchError::Status`](crate::FetchError::Status).
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, index_entries};
+///
+/// let entries = block_on(index_entries("serde")).expect("Failed to fetch index");
+/// let latest = entries.iter().rev().find(|entry| !entry.yanked).expect("No release");
+/// println!("serde {} has {} features", latest.vers, latest.features.len());
+/// ```
+pub async fn index_entries(crate_name: &str) -> Result<Vec<IndexEntry>, Box<dyn Error>> {
+    index_entries_with(crate_name, crate::default_config()).await
+}
+
+/// Same as [`index_entries`], but driven by a custom [`Config`], e.g. one reading a
+/// [`Config::sparse_index`], [`Config::local_registry`] or [`Config::directory_source`].
+pub async fn index_entries_with(crate_name: &str, config: &Config) -> Result<Vec<IndexEntry>, Box<dyn Error>> {
+    entries(crate_name, config).await
+}
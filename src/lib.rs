@@ -10,23 +10,105 @@
 //!
 //! ### Key Features
 //! - **Zero-Dependency JSON Extraction**: Custom parsing logic without `serde_json`.
-//! - **Custom block_on Implementation**: Built-in "Spin-then-Yield" strategy for running futures to completion—no `tokio` or `async-std` required.
+//! - **Custom block_on Implementation**: Parks the thread while futures wait and wakes it on progress—no `tokio` or `async-std` required.
 //! - **Minimal Footprint**: Only one external dependency ([native-tls](https://docs.rs)) for secure HTTPS.
 //! - **Deep Path Support**: Robust dot-notation extraction (e.g., `metadata.stats.0.count`).
 //! - **Human-Readable Formatting**: Compacts large numbers (e.g., `56000` -> `56k`).
 
 #![doc = include_str!("../README.md")]
 
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("crator needs the `wasm` feature on wasm32 targets, which have no sockets to connect with");
+
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
 use std::future::Future;
-use std::hint;
-use std::io::{Read, Write};
-use std::net::TcpStream;
-use std::pin::pin;
-use std::task::{Context, Poll, Waker};
 pub use std::time::Instant;
-use std::{thread, str, sync::Arc};
-pub use native_tls::TlsConnector;
+use std::time::SystemTime;
+use std::{str, sync::OnceLock};
+#[cfg(not(target_arch = "wasm32"))]
+pub use native_tls::{Certificate, TlsConnector};
+
+mod abort;
+mod advisory;
+mod cache;
+mod cancel;
+mod cargo_config;
+mod client;
+mod coalesce;
+mod config;
+mod credentials;
+mod dump;
+mod error;
+mod executor;
+mod github;
+#[cfg(all(feature = "http2", not(target_arch = "wasm32")))]
+mod h2;
+mod http;
+mod index;
+mod license;
+mod lockfile;
+mod manifest;
+mod msrv;
+#[cfg(not(target_arch = "wasm32"))]
+mod net;
+#[cfg(not(target_arch = "wasm32"))]
+mod pool;
+mod query;
+#[cfg(not(target_arch = "wasm32"))]
+mod ratelimit;
+#[cfg(all(unix, not(feature = "async-io")))]
+mod reactor;
+mod scanner;
+#[cfg(not(target_arch = "wasm32"))]
+mod timer;
+mod timestamp;
+mod toml;
+mod tree;
+#[cfg(feature = "tokio")]
+mod tokio_net;
+mod value;
+mod version;
+#[cfg(target_arch = "wasm32")]
+mod wasm;
+pub use abort::AbortHandle;
+pub use advisory::{audit_report, audit_report_with, Advisory, AuditFinding, AuditReport};
+pub use cache::{CacheStore, CachedResponse, DiskStore, MemoryStore, ResponseCache};
+pub use cancel::{cancellable, CancelToken, Cancellable};
+pub use cargo_config::{CargoConfig, SourceReplacement};
+pub use client::{send_request, HttpClient, Request, Response};
+pub use config::{Config, Resolver};
+pub use credentials::Credentials;
+pub use dump::{CsvReader, CsvRecord, DbDump, DumpCategory, DumpCrate, DumpVersion};
+pub use error::{Cancelled, ExecutorError, FetchError, JsonError, TimedOut, TomlError};
+pub use executor::{join, join_all, ExecutorConfig, ExecutorStats, IdleStrategy, Join, JoinAll, Runtime};
+#[cfg(not(target_arch = "wasm32"))]
+pub use executor::{block_on_timeout, spawn_blocking, timeout, Timeout};
+pub use github::{github_repo, github_stats, github_stats_with, RepoStats};
+pub use index::{index_config, index_config_with, index_entries, index_entries_with, index_path, IndexConfig, IndexDependency, IndexEntry};
+pub use license::{license_report, license_report_with, LicenseGroup, LicenseReport};
+pub use lockfile::{outdated_report, outdated_report_with, LockedPackage, Lockfile, OutdatedCrate, OutdatedReport, YankedCrate};
+pub use manifest::{
+    dependency_report, dependency_report_with, workspace_report, workspace_report_with, DependencyReport, DependencyStatus, Manifest,
+    ManifestDependency, Workspace, WorkspaceMember,
+};
+pub use msrv::{msrv_report, msrv_report_with, MsrvConflict, MsrvReport};
+#[cfg(not(target_arch = "wasm32"))]
+pub use pool::{spawn, JoinHandle, ThreadPool};
+pub use query::{
+    crate_dependencies, crate_dependencies_with, crate_owners, crate_owners_with, CrateDetails, CrateQuery, DependencyInfo, DependencyKind,
+    OwnerInfo, OwnerKind, VersionInfo,
+};
+pub use scanner::{JsonEvent, JsonScanner};
+pub use tree::{dependency_tree, dependency_tree_with, DepNode, DepTree, DownloadSize, Duplicate, DuplicateVersion};
+#[cfg(not(target_arch = "wasm32"))]
+pub use timer::{sleep, Sleep};
+pub use timestamp::Timestamp;
+pub use toml::Toml;
+pub use value::Value;
+pub use version::{UpdateKind, Version, VersionReq};
 
 /// A lightweight, zero-dependency JSON extractor designed for maximum performance.
 /// 
@@ -48,99 +130,809 @@ impl Json {
     /// # Path Syntax
     /// - **Keys**: `metadata.version`
     /// - **Arrays**: `releases.0.v`
+    /// - **Wildcards**: `releases.*.v` (first match; see [`Json::extract_all`] for every match)
+    /// - **Dotted keys**: `badges.appveyor\\.yml` (escape dots that belong to the key)
+    /// - **Filters**: `releases[?yanked=false].0.v` (array elements whose field equals, or with `!=` differs from, a value)
     ///
     /// # Performance
     /// Operates in O(N) time with minimal heap allocations. 
     ///
     /// # Returns
     /// Returns the value as an owned `String`, or `"N/A"` if the key is not found.
+    /// String values are unquoted and their escape sequences (`\n`, `\"`, `\u00e9`, ...)
+    /// are decoded.
     /// 
     /// # Example
     /// ```rust
     /// use crator::Json;
     /// 
-    /// let body = r#"{"stats": {"downloads": 56000}}"#;
+    /// let body = r#"{"stats": {"downloads": 56000}, "description": "A \"fast\" caf\u00e9 \ud83e\udd80"}"#;
     /// let val = Json::extract(body, "stats.downloads");
     /// assert_eq!(val, "56000");
+    /// assert_eq!(Json::extract(body, "description"), "A \"fast\" café 🦀");
+    ///
+    /// let badges = r#"{"badges": {"appveyor.yml": {"status": "passing"}}}"#;
+    /// assert_eq!(Json::extract(badges, r"badges.appveyor\.yml.status"), "passing");
     /// ```
     pub fn extract(body: &str, path: &str) -> String {
-        let mut current_body = body.to_string();
-        for part in path.split('.') {
+        Self::extract_opt(body, path).unwrap_or_else(|| "N/A".to_string())
+    }
+
+    /// Extracts a value like [`Json::extract`], returning `None` if the path is not found.
+    ///
+    /// Unlike `extract`, this distinguishes a missing key from a literal `"N/A"` string value.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"license": "N/A", "stats": {"downloads": 56000}}"#;
+    /// assert_eq!(Json::extract_opt(body, "license"), Some("N/A".to_string()));
+    /// assert_eq!(Json::extract_opt(body, "homepage"), None);
+    /// assert_eq!(Json::extract_u64_opt(body, "stats.downloads"), Some(56000));
+    /// ```
+    pub fn extract_opt(body: &str, path: &str) -> Option<String> {
+        Self::extract_ref(body, path).map(Cow::into_owned)
+    }
+
+    /// Extracts a value like [`Json::extract_opt`] without allocating.
+    ///
+    /// The value is borrowed straight from `body`; only strings containing escape
+    /// sequences have to be decoded into an owned copy. Use it in hot loops over
+    /// large payloads.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    /// use std::borrow::Cow;
+    ///
+    /// let body = r#"{"crate": {"name": "crator", "description": "A \"fast\" fetcher"}}"#;
+    /// assert!(matches!(Json::extract_ref(body, "crate.name"), Some(Cow::Borrowed("crator"))));
+    /// assert_eq!(Json::extract_ref(body, "crate.description").as_deref(), Some("A \"fast\" fetcher"));
+    /// assert_eq!(Json::extract_ref(body, "crate.homepage"), None);
+    /// ```
+    pub fn extract_ref<'a>(body: &'a str, path: &str) -> Option<Cow<'a, str>> {
+        // Auto-unquote (and unescape) if the final result is a string
+        Self::raw_value(body, path).map(Self::unquote)
+    }
+
+    /// Returns `true` if `path` resolves to a value, without copying it.
+    ///
+    /// A key explicitly set to `null` counts as present.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"crate": {"homepage": null, "repository": "https://github.com/dr-montasir/crator"}}"#;
+    /// assert!(Json::exists(body, "crate.repository"));
+    /// assert!(Json::exists(body, "crate.homepage"));
+    /// assert!(!Json::exists(body, "crate.documentation"));
+    /// ```
+    pub fn exists(body: &str, path: &str) -> bool {
+        Self::raw_value(body, path).is_some()
+    }
+
+    /// Extracts every element of the JSON array at `path`.
+    ///
+    /// String elements are unquoted and unescaped; objects and nested arrays are
+    /// returned as raw JSON text, ready for further extraction. Returns an empty `Vec`
+    /// if the path is missing or doesn't point to an array.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"crate": {"keywords": ["json", "parser", "fast"]}}"#;
+    /// assert_eq!(Json::extract_array(body, "crate.keywords"), vec!["json", "parser", "fast"]);
+    /// assert!(Json::extract_array(body, "crate.categories").is_empty());
+    /// ```
+    pub fn extract_array(body: &str, path: &str) -> Vec<String> {
+        Self::iter_array(body, path).map(Cow::into_owned).collect()
+    }
+
+    /// Lazily iterates over the elements of the JSON array at `path`.
+    ///
+    /// Elements are parsed on demand and borrowed from `body` (strings unquoted,
+    /// objects and arrays as raw JSON text; only strings containing escapes are
+    /// copied), so even huge arrays such as reverse dependencies are never
+    /// materialized. Yields nothing if the path is missing or doesn't point to an array.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"versions": [{"num": "1.1.0", "yanked": true}, {"num": "1.0.0", "yanked": false}]}"#;
+    /// let first_ok = Json::iter_array(body, "versions")
+    ///     .find(|v| !Json::extract_bool(v, "yanked"))
+    ///     .map(|v| Json::extract(&v, "num"));
+    /// assert_eq!(first_ok.as_deref(), Some("1.0.0"));
+    /// ```
+    pub fn iter_array<'a>(body: &'a str, path: &str) -> ArrayIter<'a> {
+        Self::raw_value(body, path).and_then(ArrayIter::new).unwrap_or_default()
+    }
+
+    /// Returns the number of elements in the JSON array at `path`.
+    ///
+    /// Returns `0` if the path is missing or doesn't point to an array.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"versions": [{"num": "1.1.0"}, {"num": "1.0.0"}], "keywords": []}"#;
+    /// assert_eq!(Json::array_len(body, "versions"), 2);
+    /// assert_eq!(Json::array_len(body, "keywords"), 0);
+    /// ```
+    pub fn array_len(body: &str, path: &str) -> usize {
+        Self::iter_array(body, path).count()
+    }
+
+    /// Returns the index of the first element of the array at `path` whose `key`
+    /// equals `value`, e.g. to look up a version without a separate endpoint.
+    ///
+    /// The index plugs straight into a path such as `versions.3.yanked`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"versions": [{"num": "1.2.4", "yanked": false}, {"num": "1.2.3", "yanked": true}]}"#;
+    /// let idx = Json::find_index(body, "versions", "num", "1.2.3").unwrap();
+    /// assert_eq!(idx, 1);
+    /// assert!(Json::extract_bool(body, &format!("versions.{}.yanked", idx)));
+    /// assert_eq!(Json::find_index(body, "versions", "num", "9.9.9"), None);
+    /// ```
+    pub fn find_index(body: &str, path: &str, key: &str, value: &str) -> Option<usize> {
+        let mut elements = ArrayIter::new(Self::raw_value(body, path)?)?;
+        std::iter::from_fn(|| elements.next_raw())
+            .position(|element| Self::extract_ref(element, key).as_deref() == Some(value))
+    }
+
+    /// Returns the top-level keys of the JSON object at `path`, in document order.
+    ///
+    /// Useful for maps whose keys aren't known in advance, such as a version's
+    /// `features`. An empty `path` addresses the whole document. Returns an empty
+    /// `Vec` if the path is missing or doesn't point to an object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"version": {"features": {"default": ["std"], "std": [], "serde": ["dep:serde"]}}}"#;
+    /// assert_eq!(Json::keys(body, "version.features"), vec!["default", "std", "serde"]);
+    /// assert_eq!(Json::keys(body, ""), vec!["version"]);
+    /// assert!(Json::keys(body, "version.yanked").is_empty());
+    /// ```
+    pub fn keys(body: &str, path: &str) -> Vec<String> {
+        let raw = if path.is_empty() { Some(body) } else { Self::raw_value(body, path) };
+        raw.and_then(Self::object_entries)
+            .map(|entries| entries.into_iter().map(|(key, _)| Self::unescape(key).into_owned()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Extracts every key/value pair of the flat JSON object at `path` (such as `links`).
+    ///
+    /// Values are unquoted like [`Json::extract`]; nested objects and arrays are kept
+    /// as raw JSON text. An empty `path` addresses the whole document. Returns an empty
+    /// map if the path is missing or doesn't point to an object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"crate": {"links": {"owners": "/api/v1/crates/crator/owners", "reverse_dependencies": "/api/v1/crates/crator/reverse_dependencies"}}}"#;
+    /// let links = Json::extract_map(body, "crate.links");
+    /// assert_eq!(links.len(), 2);
+    /// assert_eq!(links["owners"], "/api/v1/crates/crator/owners");
+    /// ```
+    pub fn extract_map(body: &str, path: &str) -> HashMap<String, String> {
+        let raw = if path.is_empty() { Some(body) } else { Self::raw_value(body, path) };
+        raw.and_then(Self::object_entries)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, value)| (Self::unescape(key).into_owned(), Self::unquote(value).into_owned()))
+            .collect()
+    }
+
+    /// Extracts the value at every location matched by a path containing `*` wildcards
+    /// or `[?field=value]` filters.
+    ///
+    /// A `*` segment matches every element of an array (or every value of an object),
+    /// so one expression can pull a field out of a whole collection. A filter such as
+    /// `versions[?yanked=false]` keeps only the array elements whose `yanked` field
+    /// equals `false` (`!=` inverts the test); an index right after a filter picks
+    /// among the kept elements. Matches are returned in document order, unquoted like
+    /// [`Json::extract`]; elements lacking the rest of the path are skipped. Plain
+    /// paths yield at most one value.
+    ///
+    /// The other `extract` functions accept these paths too and return the first match.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"versions": [
+    ///     {"num": "1.2.0", "yanked": true, "license": "MIT"},
+    ///     {"num": "1.1.0", "yanked": false},
+    ///     {"num": "1.0.0", "yanked": false}
+    /// ]}"#;
+    /// assert_eq!(Json::extract_all(body, "versions.*.num"), vec!["1.2.0", "1.1.0", "1.0.0"]);
+    /// assert_eq!(Json::extract_all(body, "versions.*.license"), vec!["MIT"]);
+    /// assert_eq!(Json::extract_all(body, "versions[?yanked=false].num"), vec!["1.1.0", "1.0.0"]);
+    /// assert_eq!(Json::extract(body, "versions[?yanked=false].0.num"), "1.1.0");
+    /// assert_eq!(Json::extract(body, "versions.*.num"), "1.2.0");
+    /// ```
+    pub fn extract_all(body: &str, path: &str) -> Vec<String> {
+        let mut matches = Vec::new();
+        Self::collect_matches(body, &Self::segments(path), &mut matches);
+        matches.into_iter().map(|raw| Self::unquote(raw).into_owned()).collect()
+    }
+
+    /// Splits a path on the dots that are neither escaped (`\.`) nor inside a `[...]` filter.
+    fn segments(path: &str) -> Vec<&str> {
+        let mut segments = Vec::new();
+        let bytes = path.as_bytes();
+        let (mut depth, mut start) = (0usize, 0);
+        for (i, &b) in bytes.iter().enumerate() {
+            match b {
+                b'[' => depth += 1,
+                b']' => depth = depth.saturating_sub(1),
+                b'.' if depth == 0 && (i == 0 || bytes[i - 1] != b'\\') => { segments.push(&path[start..i]); start = i + 1; }
+                _ => {}
+            }
+        }
+        segments.push(&path[start..]);
+        segments
+    }
+
+    /// Turns a path segment into the key it names, unescaping `\.` into `.`.
+    fn segment_key(segment: &str) -> Cow<'_, str> {
+        if segment.contains("\\.") { Cow::Owned(segment.replace("\\.", ".")) } else { Cow::Borrowed(segment) }
+    }
+
+    /// Resolves path segments to raw values, expanding each `*` over every child value
+    /// and each filter over the matching array elements.
+    fn collect_matches<'a>(body: &'a str, segments: &[&str], out: &mut Vec<&'a str>) {
+        let Some(pos) = segments.iter().position(|part| *part == "*" || part.contains("[?")) else {
+            out.extend(Self::resolve(body, segments.iter().copied()));
+            return;
+        };
+        let (segment, mut rest) = (segments[pos], &segments[pos + 1..]);
+        let children: Vec<&str> = if segment == "*" {
+            let Some(container) = Self::resolve(body, segments[..pos].iter().copied()) else { return };
+            match ArrayIter::new(container) {
+                Some(mut elements) => std::iter::from_fn(|| elements.next_raw()).collect(),
+                None => Self::object_entries(container).unwrap_or_default().into_iter().map(|(_, value)| value).collect(),
+            }
+        } else {
+            let (name, filter) = segment.split_once("[?").unwrap_or_default();
+            let Some(filter) = filter.strip_suffix(']') else { return };
+            let parents = segments[..pos].iter().copied().chain(Some(name).filter(|name| !name.is_empty()));
+            let Some(mut elements) = Self::resolve(body, parents).and_then(ArrayIter::new) else { return };
+            let mut kept: Vec<&str> = std::iter::from_fn(|| elements.next_raw())
+                .filter(|element| Self::filter_matches(element, filter))
+                .collect();
+            // An index right after a filter selects among the kept elements
+            if let Some(idx) = rest.first().and_then(|part| part.parse::<usize>().ok()) {
+                kept = kept.into_iter().nth(idx).into_iter().collect();
+                rest = &rest[1..];
+            }
+            kept
+        };
+        for child in children {
+            Self::collect_matches(child, rest, out);
+        }
+    }
+
+    /// Evaluates a `field=value` / `field!=value` filter against an array element.
+    fn filter_matches(element: &str, filter: &str) -> bool {
+        if let Some((field, value)) = filter.split_once("!=") {
+            return Self::extract_ref(element, field).as_deref() != Some(value);
+        }
+        match filter.split_once('=') {
+            Some((field, value)) => Self::extract_ref(element, field).as_deref() == Some(value),
+            None => false,
+        }
+    }
+
+    /// Scans a raw JSON object and collects its entries as raw `(key, value)` slices.
+    fn object_entries(raw: &str) -> Option<Vec<(&str, &str)>> {
+        let mut rest = raw.trim_start().strip_prefix('{')?;
+        let mut entries = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            let Some(after_quote) = rest.strip_prefix('"') else { break };
+            let end = Self::string_end(after_quote)?;
+            let after_key = &after_quote[end + 1..];
+            let colon_idx = after_key.find(':')?;
+            let value_src = after_key[colon_idx + 1..].trim_start();
+            let value = Self::slice_until_boundary(value_src);
+            if value.is_empty() { break; }
+            entries.push((&after_quote[..end], value));
+            // Stop after this entry unless a comma announces another one
+            match value_src[value.len()..].trim_start().strip_prefix(',') {
+                Some(next) => rest = next,
+                None => break,
+            }
+        }
+        Some(entries)
+    }
+
+    /// Extracts several paths in a single pass over `body`.
+    ///
+    /// Each path resolves exactly like [`Json::extract`], but the document is scanned
+    /// once for all first segments instead of once per path, which matters for large
+    /// responses. The map is keyed by path; paths that can't be resolved are absent.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"crate": {"max_version": "1.2.0", "downloads": 56000}, "versions": [{"license": "MIT"}]}"#;
+    /// let values = Json::multi_extract(body, &["max_version", "downloads", "versions.0.license", "homepage"]);
+    /// assert_eq!(values["max_version"], "1.2.0");
+    /// assert_eq!(values["downloads"], "56000");
+    /// assert_eq!(values["versions.0.license"], "MIT");
+    /// assert!(!values.contains_key("homepage"));
+    /// ```
+    pub fn multi_extract(body: &str, paths: &[&str]) -> HashMap<String, String> {
+        let mut found = HashMap::new();
+        let mut pending: Vec<(&str, Cow<str>, Option<&str>)> = Vec::new();
+        for &path in paths {
+            let first = Self::segments(path)[0];
+            let rest = path.get(first.len() + 1..);
+            // Index, wildcard and filter segments can't be matched by the key scan below
+            if first.parse::<usize>().is_ok() || first == "*" || path.contains("[?") {
+                found.extend(Self::raw_value(body, path).map(|raw| (path.to_string(), Self::unquote(raw).into_owned())));
+            } else {
+                pending.push((path, Self::segment_key(first), rest));
+            }
+        }
+
+        let mut rest = body;
+        while !pending.is_empty() {
+            let Some(quote) = rest.find('"') else { break };
+            let string = &rest[quote + 1..];
+            let Some(end) = Self::string_end(string) else { break };
+            let (key, after_key) = (&string[..end], &string[end + 1..]);
+            rest = after_key;
+            let Some(after_colon) = after_key.trim_start().strip_prefix(':') else { continue };
+            let value = Self::slice_until_boundary(after_colon);
+            pending.retain(|(path, first, rest)| {
+                if first != key { return true; }
+                let raw = match rest {
+                    Some(rest) => Self::raw_value(value, rest),
+                    None => Some(value),
+                };
+                found.extend(raw.map(|raw| (path.to_string(), Self::unquote(raw).into_owned())));
+                false
+            });
+        }
+        found
+    }
+
+    /// Parses the whole document into a [`Value`] tree.
+    ///
+    /// An opt-in alternative to path extraction for consumers who need to walk
+    /// arbitrary structures. It validates and allocates the entire document, so
+    /// prefer the `extract` functions for simple lookups.
+    ///
+    /// # Errors
+    /// Returns [`JsonError::Syntax`] with the byte offset of the first error if `body`
+    /// is not valid JSON.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{Json, Value};
+    ///
+    /// let value = Json::parse(r#"{"features": {"default": ["std"], "std": []}}"#).unwrap();
+    /// for (name, deps) in value.get("features").and_then(Value::as_object).unwrap_or_default() {
+    ///     println!("{} enables {} feature(s)", name, deps.as_array().map_or(0, |d| d.len()));
+    /// }
+    /// assert!(Json::parse(r#"{"unclosed": [1, 2"#).is_err());
+    /// ```
+    pub fn parse(body: &str) -> Result<Value, JsonError> {
+        value::Parser::new(body).parse_document()
+    }
+
+    /// Serializes a [`Value`] as compact JSON, e.g. a request body for an authenticated
+    /// `PUT` endpoint; build the value with [`Value::object`] and [`Value::array`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{Json, Value};
+    ///
+    /// let body = Value::object().field("users", Value::array().item("alice").item("bob"));
+    /// assert_eq!(Json::write(&body), r#"{"users":["alice","bob"]}"#);
+    /// assert_eq!(Json::parse(&Json::write(&body)).unwrap(), body);
+    /// ```
+    pub fn write(value: &Value) -> String {
+        value.to_string()
+    }
+
+    /// Quotes and escapes `s` as a JSON string literal.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// assert_eq!(Json::quote("tab\there \"quoted\""), r#""tab\there \"quoted\"""#);
+    /// ```
+    pub fn quote(s: &str) -> String {
+        let mut out = String::with_capacity(s.len() + 2);
+        let _ = value::write_string(&mut out, s);
+        out
+    }
+
+    /// Deserializes the value at `path` (or the whole body for an empty path) into a
+    /// user-defined type, for typed models on top of crator's networking.
+    ///
+    /// Requires the `serde` feature.
+    ///
+    /// # Errors
+    /// Returns [`JsonError::PathNotFound`] if the path doesn't resolve, or the
+    /// `serde_json` error if the value doesn't match `T`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Version {
+    ///     num: String,
+    ///     yanked: bool,
+    /// }
+    ///
+    /// let body = r#"{"versions": [{"num": "1.2.0", "yanked": false, "id": 7}]}"#;
+    /// let versions: Vec<Version> = Json::deserialize(body, "versions").unwrap();
+    /// assert_eq!(versions[0].num, "1.2.0");
+    /// assert!(!versions[0].yanked);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn deserialize<T: serde::de::DeserializeOwned>(body: &str, path: &str) -> Result<T, Box<dyn Error>> {
+        let raw = if path.is_empty() { Some(body) } else { Self::raw_value(body, path) };
+        let raw = raw.ok_or_else(|| JsonError::PathNotFound { path: path.to_string() })?;
+        Ok(serde_json::from_str(raw)?)
+    }
+
+    /// Re-indents the JSON at `path` (or the whole body for an empty path) for human display.
+    ///
+    /// Uses two-space indentation; strings and numbers are kept exactly as written.
+    /// Returns `None` if the path is not found.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"crate": {"name": "crator", "keywords": ["json", "tls"], "links": {}}}"#;
+    /// assert_eq!(Json::pretty(body, "crate").unwrap(), "{
+    ///   \"name\": \"crator\",
+    ///   \"keywords\": [
+    ///     \"json\",
+    ///     \"tls\"
+    ///   ],
+    ///   \"links\": {}
+    /// }");
+    /// ```
+    pub fn pretty(body: &str, path: &str) -> Option<String> {
+        let raw = if path.is_empty() { body } else { Self::raw_value(body, path)? };
+        Some(Self::reformat(raw, Some("  ")))
+    }
+
+    /// Strips insignificant whitespace from `body`, leaving quoted strings untouched.
+    ///
+    /// Minified payloads take less space in caches and compare equal regardless of
+    /// how the server formatted them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = "{\n  \"name\": \"my crate\",\n  \"keywords\": [ \"json\", \"tls\" ]\n}";
+    /// assert_eq!(Json::minify(body), r#"{"name":"my crate","keywords":["json","tls"]}"#);
+    /// ```
+    pub fn minify(body: &str) -> String {
+        Self::reformat(body, None)
+    }
+
+    /// Rewrites JSON text token by token: compact without `indent`, one entry per line with it.
+    fn reformat(raw: &str, indent: Option<&str>) -> String {
+        let mut out = String::with_capacity(raw.len());
+        let mut depth = 0usize;
+        let newline = |out: &mut String, depth: usize| {
+            if let Some(indent) = indent {
+                out.push('\n');
+                for _ in 0..depth { out.push_str(indent); }
+            }
+        };
+        let mut rest = raw.trim();
+        while let Some(c) = rest.chars().next() {
+            match c {
+                '"' => {
+                    let end = Self::string_end(&rest[1..]).map_or(rest.len(), |end| end + 2);
+                    out.push_str(&rest[..end]);
+                    rest = &rest[end..];
+                    continue;
+                }
+                '{' | '[' => {
+                    out.push(c);
+                    let close = if c == '{' { '}' } else { ']' };
+                    // Keep empty containers on one line
+                    if let Some(after) = rest[1..].trim_start().strip_prefix(close) {
+                        out.push(close);
+                        rest = after;
+                        continue;
+                    }
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+                '}' | ']' => {
+                    depth = depth.saturating_sub(1);
+                    newline(&mut out, depth);
+                    out.push(c);
+                }
+                ',' => {
+                    out.push(',');
+                    newline(&mut out, depth);
+                }
+                ':' => out.push_str(if indent.is_some() { ": " } else { ":" }),
+                c if c.is_whitespace() => {}
+                c => out.push(c),
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        out
+    }
+
+    /// Strictly validates `body`, reporting the first syntax error with its byte offset.
+    ///
+    /// The `extract` functions are lenient and may return a truncated value from a
+    /// broken document; validating first turns weird proxy responses (truncated
+    /// bodies, HTML error pages, bad escapes) into a descriptive error instead.
+    ///
+    /// # Errors
+    /// Returns [`JsonError::Syntax`] for unbalanced brackets, invalid escapes or
+    /// numbers, raw control characters in strings, and trailing garbage.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{Json, JsonError};
+    ///
+    /// assert!(Json::validate(r#"{"crate": {"name": "crator"}}"#).is_ok());
+    /// assert_eq!(
+    ///     Json::validate(r#"{"name": "bad \q escape"}"#),
+    ///     Err(JsonError::Syntax { offset: 14, reason: "invalid escape sequence" })
+    /// );
+    /// let err = Json::validate(r#"{"versions": [1, 2"#).unwrap_err();
+    /// assert_eq!(err.to_string(), "invalid JSON at byte 18: unexpected end of input");
+    /// ```
+    pub fn validate(body: &str) -> Result<(), JsonError> {
+        value::Parser::new(body).parse_document().map(drop)
+    }
+
+    /// Extracts a value like [`Json::try_extract`] after validating the whole document
+    /// with [`Json::validate`].
+    ///
+    /// # Errors
+    /// Returns [`JsonError::Syntax`] if the document is malformed, otherwise the
+    /// errors of [`Json::try_extract`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{Json, JsonError};
+    ///
+    /// let body = r#"{"crate": {"max_version": "1.2.0"}}"#;
+    /// assert_eq!(Json::extract_strict(body, "crate.max_version"), Ok("1.2.0".to_string()));
+    /// assert!(matches!(Json::extract_strict(r#"{"crate": {"max_version": "1.2.0"}"#, "crate.max_version"), Err(JsonError::Syntax { .. })));
+    /// ```
+    pub fn extract_strict(body: &str, path: &str) -> Result<String, JsonError> {
+        Self::validate(body)?;
+        Self::try_extract(body, path)
+    }
+
+    /// Extracts a value like [`Json::extract`], reporting *why* it failed as a [`JsonError`].
+    ///
+    /// Each path segment is checked against the type of the value it addresses:
+    /// an index requires an array, a key requires an object (or an array to search).
+    /// Enables proper error propagation with `?` in library consumers.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{Json, JsonError};
+    ///
+    /// fn latest(body: &str) -> Result<String, JsonError> {
+    ///     Json::try_extract(body, "crate.max_version")
+    /// }
+    ///
+    /// assert_eq!(latest(r#"{"crate": {"max_version": "1.2.0"}}"#).unwrap(), "1.2.0");
+    /// assert!(latest(r#"{"crate": {"max_version": [1, 2"#).is_err());
+    /// ```
+    pub fn try_extract(body: &str, path: &str) -> Result<String, JsonError> {
+        let mut current_body = body.trim();
+        if !Self::is_complete(current_body) { return Err(JsonError::Malformed { path: String::new() }); }
+        let mut walked = String::new();
+        for part in Self::segments(path) {
+            if !walked.is_empty() { walked.push('.'); }
+            walked.push_str(part);
+            let first = current_body.as_bytes().first().copied();
             let next = if let Ok(idx) = part.parse::<usize>() {
-                Self::get_array_index(&current_body, idx)
+                if first != Some(b'[') { return Err(JsonError::TypeMismatch { path: walked, expected: "array" }); }
+                Self::get_array_index(current_body, idx)
             } else {
-                Self::get_key_value(&current_body, part)
+                if first != Some(b'{') && first != Some(b'[') { return Err(JsonError::TypeMismatch { path: walked, expected: "object" }); }
+                Self::get_key_value(current_body, part)
             };
-            if next == "N/A" { return "N/A".to_string(); }
+            let Some(next) = next else { return Err(JsonError::PathNotFound { path: walked }); };
+            if !Self::is_complete(next) { return Err(JsonError::Malformed { path: walked }); }
             current_body = next;
         }
-        // Auto-unquote if the final result is a string
-        if current_body.starts_with('"') && current_body.ends_with('"') {
-            return current_body[1..current_body.len() - 1].to_string();
+        Ok(Self::unquote(current_body).into_owned())
+    }
+
+    /// Cheap well-formedness check: non-empty, with containers and strings closed.
+    fn is_complete(raw: &str) -> bool {
+        match raw.as_bytes().first() {
+            None => false,
+            Some(b'{') => raw.ends_with('}'),
+            Some(b'[') => raw.ends_with(']'),
+            Some(b'"') => raw.len() >= 2 && raw.ends_with('"'),
+            Some(_) => true,
         }
-        current_body
     }
 
-    fn get_key_value(body: &str, key: &str) -> String {
-        let pattern = format!("\"{}\"", key);
-        if let Some(key_idx) = body.find(&pattern) {
-            let after_key = &body[key_idx + pattern.len()..];
-            // Skip the colon and find the value
-            if let Some(colon_idx) = after_key.find(':') {
-                return Self::slice_until_boundary(&after_key[colon_idx + 1..]);
-            }
+    /// Resolves `path` to the raw JSON text of the value (strings keep their quotes).
+    ///
+    /// A path with `*` wildcards or filters resolves to its first match.
+    fn raw_value<'a>(body: &'a str, path: &str) -> Option<&'a str> {
+        if path.contains("[?") || path.split('.').any(|part| part == "*") {
+            let mut matches = Vec::new();
+            Self::collect_matches(body, &Self::segments(path), &mut matches);
+            return matches.into_iter().next();
+        }
+        Self::resolve(body, Self::segments(path))
+    }
+
+    /// Walks plain (wildcard-free) path segments down to the raw JSON text they address.
+    fn resolve<'a, 'p>(body: &'a str, parts: impl IntoIterator<Item = &'p str>) -> Option<&'a str> {
+        let mut current_body = body;
+        for part in parts {
+            current_body = if let Ok(idx) = part.parse::<usize>() {
+                Self::get_array_index(current_body, idx)?
+            } else {
+                Self::get_key_value(current_body, part)?
+            };
+        }
+        Some(current_body)
+    }
+
+    fn get_key_value<'a>(body: &'a str, key: &str) -> Option<&'a str> {
+        let pattern = format!("\"{}\"", Self::segment_key(key));
+        let key_idx = body.find(&pattern)?;
+        let after_key = &body[key_idx + pattern.len()..];
+        // Skip the colon and find the value
+        let colon_idx = after_key.find(':')?;
+        Some(Self::slice_until_boundary(&after_key[colon_idx + 1..]))
+    }
+
+    fn get_array_index(body: &str, target: usize) -> Option<&str> {
+        let mut elements = ArrayIter::new(body)?;
+        for _ in 0..target {
+            elements.next_raw()?;
         }
-        "N/A".to_string()
-    }
-
-    fn get_array_index(body: &str, target: usize) -> String {
-        let trimmed = body.trim_start();
-        if !trimmed.starts_with('[') { return "N/A".to_string(); }
-        let mut content = &trimmed[1..];
-        for i in 0..=target {
-            content = content.trim_start();
-            let val = Self::slice_until_boundary(content);
-            if i == target { return val; }
-            let val_len = val.len();
-            if val_len == 0 { break; }
-            content = &content[val_len..].trim_start();
-            if content.starts_with(',') { content = &content[1..]; } 
-            else { break; }
+        elements.next_raw()
+    }
+
+    /// Strips the quotes of a raw JSON string value and decodes its escapes;
+    /// other values are returned as-is.
+    fn unquote(raw: &str) -> Cow<'_, str> {
+        if raw.len() >= 2 && raw.starts_with('"') && raw.ends_with('"') {
+            return Self::unescape(&raw[1..raw.len() - 1]);
+        }
+        Cow::Borrowed(raw)
+    }
+
+    /// Decodes the escape sequences of a JSON string's content, including `\uXXXX`
+    /// escapes and UTF-16 surrogate pairs. Strings without escapes are borrowed;
+    /// invalid sequences are kept verbatim.
+    fn unescape(content: &str) -> Cow<'_, str> {
+        if !content.contains('\\') { return Cow::Borrowed(content); }
+        let mut out = String::with_capacity(content.len());
+        let mut rest = content;
+        while let Some(idx) = rest.find('\\') {
+            out.push_str(&rest[..idx]);
+            rest = &rest[idx..];
+            let (decoded, len) = match rest.as_bytes().get(1) {
+                Some(b'"') => ('"', 2),
+                Some(b'\\') => ('\\', 2),
+                Some(b'/') => ('/', 2),
+                Some(b'b') => ('\u{8}', 2),
+                Some(b'f') => ('\u{c}', 2),
+                Some(b'n') => ('\n', 2),
+                Some(b'r') => ('\r', 2),
+                Some(b't') => ('\t', 2),
+                Some(b'u') => Self::unicode_escape(rest).unwrap_or(('\\', 1)),
+                _ => ('\\', 1),
+            };
+            out.push(decoded);
+            rest = &rest[len..];
+        }
+        out.push_str(rest);
+        Cow::Owned(out)
+    }
+
+    /// Decodes a `\uXXXX` escape (or a `\uD83D\uDE00` surrogate pair) at the start of `s`,
+    /// returning the character and the number of bytes consumed.
+    fn unicode_escape(s: &str) -> Option<(char, usize)> {
+        let hex = |at: usize| s.get(at..at + 4).and_then(|h| u32::from_str_radix(h, 16).ok());
+        let high = hex(2)?;
+        if (0xD800..0xDC00).contains(&high) {
+            let low = if s.get(6..8) == Some("\\u") { hex(8)? } else { return None };
+            if !(0xDC00..0xE000).contains(&low) { return None; }
+            let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+            return char::from_u32(code).map(|c| (c, 12));
         }
-        "N/A".to_string()
+        char::from_u32(high).map(|c| (c, 6))
+    }
+
+    /// Finds the closing quote of a string whose content starts at `s`, skipping escaped quotes.
+    fn string_end(s: &str) -> Option<usize> {
+        let mut escaped = false;
+        s.bytes().position(|b| match b {
+            _ if escaped => { escaped = false; false }
+            b'\\' => { escaped = true; false }
+            b => b == b'"',
+        })
     }
 
-    fn slice_until_boundary(data: &str) -> String {
+    fn slice_until_boundary(data: &str) -> &str {
         let s = data.trim_start();
-        if s.is_empty() { return "".to_string(); }
+        if s.is_empty() { return ""; }
         let bytes = s.as_bytes();
-        let (mut d_obj, mut d_arr, mut q) = (0, 0, false);
+        let (mut d_obj, mut d_arr, mut q, mut esc) = (0, 0, false, false);
         for (i, &b) in bytes.iter().enumerate() {
             match b {
-                b'"' if i == 0 || bytes[i-1] != b'\\' => q = !q,
+                _ if esc => esc = false,
+                b'\\' if q => esc = true,
+                b'"' => q = !q,
                 _ if q => continue, // Ignore everything inside quotes
                 b'{' => d_obj += 1,
-                b'}' => { if d_obj == 0 { return s[..i].trim().to_string(); } d_obj -= 1; }
+                b'}' => { if d_obj == 0 { return s[..i].trim(); } d_obj -= 1; }
                 b'[' => d_arr += 1,
-                b']' => { if d_arr == 0 { return s[..i].trim().to_string(); } d_arr -= 1; }
-                b',' if d_obj == 0 && d_arr == 0 => return s[..i].trim().to_string(),
-                _ if d_obj == 0 && d_arr == 0 && b.is_ascii_whitespace() && i > 0 => return s[..i].trim().to_string(),
+                b']' => { if d_arr == 0 { return s[..i].trim(); } d_arr -= 1; }
+                b',' if d_obj == 0 && d_arr == 0 => return s[..i].trim(),
+                _ if d_obj == 0 && d_arr == 0 && b.is_ascii_whitespace() && i > 0 => return s[..i].trim(),
                 _ => {}
             }
         }
-        s.trim_matches(|c| c == ',' || c == '}' || c == ']').trim().to_string()
+        s.trim_matches(|c| c == ',' || c == '}' || c == ']').trim()
     }
 
     /// Attempts to parse the extracted value as an `i64`. 
     /// Returns `0` if extraction or parsing fails.
+    ///
+    /// Like all integer extractors, it accepts numbers written in scientific notation
+    /// or with a fraction (`1.23e6`, `56000.0`), truncating toward zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"downloads": 1.23e6, "recent": 56000.0, "delta": -12.7, "name": "crator"}"#;
+    /// assert_eq!(Json::extract_u64(body, "downloads"), 1_230_000);
+    /// assert_eq!(Json::extract_u64(body, "recent"), 56_000);
+    /// assert_eq!(Json::extract_int(body, "delta"), -12);
+    /// assert_eq!(Json::extract_int(body, "name"), 0);
+    /// ```
     pub fn extract_int(body: &str, path: &str) -> i64 {
-        Self::extract(body, path).parse::<i64>().unwrap_or(0)
+        Self::extract_int_opt(body, path).unwrap_or(0)
     }
 
     /// Attempts to parse the extracted value as a `u64`. 
     /// Returns `0` if extraction or parsing fails.
     pub fn extract_u64(body: &str, path: &str) -> u64 {
-        Self::extract(body, path).parse::<u64>().unwrap_or(0)
+        Self::extract_u64_opt(body, path).unwrap_or(0)
     }
 
     /// Attempts to parse the extracted value as an `f64`. 
@@ -154,77 +946,544 @@ impl Json {
     pub fn extract_bool(body: &str, path: &str) -> bool {
         Self::extract(body, path).to_lowercase() == "true"
     }
+
+    /// Parses the extracted value as an `i64`.
+    /// Returns `None` if the path is missing or the value isn't an integer.
+    pub fn extract_int_opt(body: &str, path: &str) -> Option<i64> {
+        Self::parse_i64(&Self::extract_ref(body, path)?)
+    }
+
+    /// Parses the extracted value as a `u64`.
+    /// Returns `None` if the path is missing or the value isn't an unsigned integer.
+    pub fn extract_u64_opt(body: &str, path: &str) -> Option<u64> {
+        Self::parse_u64(&Self::extract_ref(body, path)?)
+    }
+
+    /// Parses an integer, tolerating scientific notation and fractions (truncated).
+    fn parse_i64(raw: &str) -> Option<i64> {
+        raw.parse::<i64>().ok().or_else(|| {
+            let n = Self::parse_finite(raw)?.trunc();
+            (n >= i64::MIN as f64 && n < i64::MAX as f64).then_some(n as i64)
+        })
+    }
+
+    /// Parses an unsigned integer, tolerating scientific notation and fractions (truncated).
+    fn parse_u64(raw: &str) -> Option<u64> {
+        raw.parse::<u64>().ok().or_else(|| {
+            let n = Self::parse_finite(raw)?.trunc();
+            (n >= 0.0 && n < u64::MAX as f64).then_some(n as u64)
+        })
+    }
+
+    /// Parses a finite float, the fallback for integers written as `1.23e6` or `56000.0`.
+    fn parse_finite(raw: &str) -> Option<f64> {
+        raw.parse::<f64>().ok().filter(|n| n.is_finite())
+    }
+
+    /// Parses the extracted value as an `f64`.
+    /// Returns `None` if the path is missing or the value isn't a number.
+    pub fn extract_float_opt(body: &str, path: &str) -> Option<f64> {
+        Self::extract_ref(body, path)?.parse::<f64>().ok()
+    }
+
+    /// Parses the extracted value as a `bool`.
+    /// Returns `None` if the path is missing or the value is neither `true` nor `false`.
+    pub fn extract_bool_opt(body: &str, path: &str) -> Option<bool> {
+        match Self::extract_opt(body, path)?.to_lowercase().as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Parses the ISO 8601 timestamp at `path` (e.g. `created_at`) into a [`Timestamp`].
+    /// Returns `None` if the path is missing or the value isn't a timestamp.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"crate": {"updated_at": "2024-03-09T17:45:02.123456+00:00"}}"#;
+    /// let updated = Json::extract_datetime(body, "crate.updated_at").unwrap();
+    /// assert_eq!((updated.year, updated.month, updated.day), (2024, 3, 9));
+    /// assert_eq!(updated.unix, 1_710_006_302);
+    /// ```
+    pub fn extract_datetime(body: &str, path: &str) -> Option<Timestamp> {
+        Timestamp::parse(&Self::extract_ref(body, path)?)
+    }
+
+    /// Extracts the value at `path` and parses it into any [`FromStr`](std::str::FromStr) type,
+    /// such as your own numeric or enum types.
+    ///
+    /// # Errors
+    /// Returns [`JsonError::PathNotFound`] if the path is missing, or
+    /// [`JsonError::InvalidValue`] if the value doesn't parse as `T`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{Json, JsonError};
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let body = r#"{"mirror": {"ip": "10.0.0.7", "port": "eighty"}}"#;
+    /// assert_eq!(Json::extract_as::<Ipv4Addr>(body, "mirror.ip"), Ok(Ipv4Addr::new(10, 0, 0, 7)));
+    /// assert!(matches!(Json::extract_as::<u16>(body, "mirror.port"), Err(JsonError::InvalidValue { .. })));
+    /// assert!(matches!(Json::extract_as::<u16>(body, "mirror.tls"), Err(JsonError::PathNotFound { .. })));
+    /// ```
+    pub fn extract_as<T: str::FromStr>(body: &str, path: &str) -> Result<T, JsonError> {
+        let value = Self::extract_ref(body, path).ok_or_else(|| JsonError::PathNotFound { path: path.to_string() })?;
+        value.parse::<T>().map_err(|_| JsonError::InvalidValue {
+            path: path.to_string(),
+            value: value.into_owned(),
+            target: std::any::type_name::<T>(),
+        })
+    }
+
+    /// Extracts a value, falling back to `default` if the path is not found.
+    ///
+    /// Saves call sites from comparing against the `"N/A"` sentinel of [`Json::extract`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Json;
+    ///
+    /// let body = r#"{"crate": {"license": "MIT", "downloads": "n/a"}}"#;
+    /// assert_eq!(Json::extract_or(body, "crate.license", "unknown"), "MIT");
+    /// assert_eq!(Json::extract_or(body, "crate.homepage", "none"), "none");
+    /// assert_eq!(Json::extract_u64_or(body, "crate.downloads", 0), 0);
+    /// assert!(Json::extract_bool_or(body, "crate.yanked", true));
+    /// ```
+    pub fn extract_or(body: &str, path: &str, default: &str) -> String {
+        Self::extract_opt(body, path).unwrap_or_else(|| default.to_string())
+    }
+
+    /// Parses the extracted value as an `i64`, falling back to `default` if the path
+    /// is missing or the value isn't an integer.
+    pub fn extract_int_or(body: &str, path: &str, default: i64) -> i64 {
+        Self::extract_int_opt(body, path).unwrap_or(default)
+    }
+
+    /// Parses the extracted value as a `u64`, falling back to `default` if the path
+    /// is missing or the value isn't an unsigned integer.
+    pub fn extract_u64_or(body: &str, path: &str, default: u64) -> u64 {
+        Self::extract_u64_opt(body, path).unwrap_or(default)
+    }
+
+    /// Parses the extracted value as an `f64`, falling back to `default` if the path
+    /// is missing or the value isn't a number.
+    pub fn extract_float_or(body: &str, path: &str, default: f64) -> f64 {
+        Self::extract_float_opt(body, path).unwrap_or(default)
+    }
+
+    /// Parses the extracted value as a `bool`, falling back to `default` if the path
+    /// is missing or the value is neither `true` nor `false`.
+    pub fn extract_bool_or(body: &str, path: &str, default: bool) -> bool {
+        Self::extract_bool_opt(body, path).unwrap_or(default)
+    }
 }
 
-/// A minimal, thread-safe Waker implementation that performs no action.
-/// 
-/// This is used by the internal `block_on` runner to satisfy the `Context` requirements 
-/// of the [Future::poll](https://doc.rust-lang.org) method. 
-/// Since this library uses a high-performance polling strategy, a functional 
-/// wake-up notification is not required.
-struct NoopWake;
-
-impl std::task::Wake for NoopWake {
-    /// No-op: The runner polls continuously using a spin-then-yield strategy until completion.
-    fn wake(self: Arc<Self>) {}
+/// A lazy iterator over the elements of a JSON array, created by [`Json::iter_array`].
+///
+/// Each element is located on demand by scanning forward from the previous one.
+#[derive(Debug, Clone, Default)]
+pub struct ArrayIter<'a> {
+    rest: &'a str,
 }
 
-/// A high-performance, single-threaded runner for driving Futures to completion.
+impl<'a> ArrayIter<'a> {
+    /// Starts iterating over a raw JSON array; `None` if `raw` is not an array.
+    fn new(raw: &'a str) -> Option<Self> {
+        let rest = raw.trim_start().strip_prefix('[')?;
+        Some(Self { rest })
+    }
+
+    /// Returns the next element as raw JSON text, keeping the quotes of strings.
+    fn next_raw(&mut self) -> Option<&'a str> {
+        let content = self.rest.trim_start();
+        if content.is_empty() || content.starts_with(']') { self.rest = ""; return None; }
+        let val = Json::slice_until_boundary(content);
+        if val.is_empty() { self.rest = ""; return None; }
+        let after = content[val.len()..].trim_start();
+        // Stop after this element unless a comma announces another one
+        self.rest = after.strip_prefix(',').unwrap_or("");
+        Some(val)
+    }
+}
+
+impl<'a> Iterator for ArrayIter<'a> {
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Cow<'a, str>> {
+        self.next_raw().map(Json::unquote)
+    }
+}
+
+/// A lightweight, single-threaded runner for driving Futures to completion.
 ///
-/// This function uses a hybrid "Spin-then-Yield" strategy:
-/// 1. **Spinning**: For the first 150,000 iterations, it uses [`hint::spin_loop`](https://doc.rust-lang.org) 
-///    to minimize latency for near-instant tasks.
-/// 2. **Yielding**: If the task remains pending, it calls [`thread::yield_now`](https://doc.rust-lang.org) 
-///    to allow the OS to schedule other threads, preventing 100% CPU starvation.
+/// The future is polled on the calling thread. Whenever it is pending, the thread
+/// [parks](std::thread::park) until the future's [`Waker`](std::task::Waker) is woken, so waiting on the
+/// network costs no CPU. A wake that arrives before the thread parks is not lost:
+/// the park returns immediately and the future is polled again.
+///
+/// This is a thin wrapper around a default [`Runtime`] kept per thread. Use a
+/// [`Runtime`] with an [`ExecutorConfig`] to spin before parking, poll on an
+/// interval instead, or bound the total running time.
 ///
 /// # Safety
-/// Uses a thread-safe [`Waker`](https://doc.rust-lang.org) backed by an `Arc<NoopWake>`, 
-/// ensuring full compliance with the [Rust Future Trait](https://doc.rust-lang.org) 
+/// Uses a thread-safe [`Waker`](std::task::Waker) backed by an `Arc`,
+/// ensuring full compliance with the [Rust Future Trait](https://doc.rust-lang.org)
 /// without the overhead of a complex event loop.
 pub fn block_on<F: Future>(future: F) -> F::Output {
-    let mut future = pin!(future);
-    
-    // Completely safe Waker using Arc
-    let waker = Waker::from(Arc::new(NoopWake));
-    let mut cx = Context::from_waker(&waker);
-    
-    let mut spins = 0u64;
-    loop {
-        match future.as_mut().poll(&mut cx) {
-            Poll::Ready(v) => return v,
-            Poll::Pending => {
-                // Efficiency: Spin briefly before yielding to the OS
-                if spins < 150_000 {
-                    hint::spin_loop();
-                    spins += 1;
-                } else {
-                    thread::yield_now();
-                    spins = 0;
-                }
-            }
-        }
+    thread_local! {
+        static RUNTIME: Runtime = Runtime::new();
+    }
+    match RUNTIME.with(|runtime| runtime.block_on(future)) {
+        Ok(output) => output,
+        Err(_) => unreachable!("the default runtime has no limits"),
     }
 }
 
 /// Represents the essential metadata of a crate retrieved from crates.io.
 /// 
 /// This structure holds both human-readable strings for display and 
-/// raw numeric values for programmatic use.
+/// raw numeric values for programmatic use. Its [`Display`](fmt::Display) output is
+/// an aligned, multi-line summary ready for a terminal.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{crate_data, block_on};
+///
+/// match block_on(crate_data("mathlab")) {
+///     Ok(info) => println!("{}", info),
+///     Err(e) => eprintln!("❌ Error: {}", e),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CrateInfo {
-    /// The latest version of the crate (e.g., "1.5.0").
+    /// The highest version of the crate (e.g., "1.5.0"), crates.io's `max_version`.
+    ///
+    /// This may be a pre-release (e.g., "2.0.0-rc.1"); see [`max_stable_version`](CrateInfo::max_stable_version).
     pub latest: String,
+    /// The highest version that is not a pre-release, if any was published.
+    pub max_stable_version: Option<String>,
+    /// The most recently published version, which may be lower than [`latest`](CrateInfo::latest) for backports.
+    pub newest_version: String,
+    /// Whether [`latest`](CrateInfo::latest) has been yanked, so it shouldn't be recommended.
+    pub latest_yanked: bool,
     /// Human-readable download count (e.g., "56k").
     pub downloads: String,
     /// The exact total number of downloads.
     pub total_downloads: u64,
+    /// Human-readable download count over the last 90 days (e.g., "12k").
+    pub recent_downloads: String,
+    /// The exact number of downloads over the last 90 days.
+    pub recent_downloads_count: u64,
     /// The total number of versions ever published.
     pub versions: u64,
     /// The software license (e.g., "MIT OR Apache-2.0").
     pub license: String,
+    /// The one-line description from the crate's manifest, if any.
+    pub description: Option<String>,
+    /// The source repository URL (e.g., "https://github.com/serde-rs/serde"), if any.
+    pub repository: Option<String>,
+    /// The project homepage URL, if any.
+    pub homepage: Option<String>,
+    /// The documentation URL from the manifest (often docs.rs), if any.
+    pub documentation: Option<String>,
+    /// The crate's keywords (e.g., `["serde", "serialization", "no_std"]`).
+    pub keywords: Vec<String>,
+    /// The slugs of the crate's categories (e.g., `["encoding", "no-std"]`).
+    pub categories: Vec<String>,
     /// ISO 8601 formatted creation timestamp.
     pub created_at: String,
     /// ISO 8601 formatted timestamp of the last update.
     pub updated_at: String,
+    /// The `ETag` validator sent by the server, if any.
+    ///
+    /// Pass it back through [`Conditions::if_none_match`] to avoid re-downloading
+    /// an unchanged payload.
+    pub etag: Option<String>,
+    /// The `Last-Modified` date sent by the server, if any (an HTTP date string).
+    pub last_modified: Option<String>,
+    /// The raw JSON body of the response, kept only when [`Config::retain_body`] is enabled.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{crate_data_with, block_on, Config, Json};
+    ///
+    /// let info = block_on(crate_data_with("serde", &Config::new().retain_body(true))).expect("Failed to fetch crate data");
+    /// let body = info.raw_body.as_deref().unwrap_or_default();
+    /// println!("Reverse dependencies: {}", Json::extract(body, "crate.links.reverse_dependencies"));
+    /// ```
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw_body: Option<String>,
+}
+
+impl CrateInfo {
+    /// Builds a `CrateInfo` from a successful API response, keeping the body if `config` asks for it.
+    fn from_response(head: &str, body: String, config: &Config) -> Self {
+        let mut info = Self::parse(head, &body);
+        if config.retain_body { info.raw_body = Some(body); }
+        info
+    }
+
+    fn parse(head: &str, body: &str) -> Self {
+        let mut fields = Json::multi_extract(body, &[
+            "max_version", "downloads", "recent_downloads", "num_versions", "license", "created_at", "updated_at",
+            "crate.description", "crate.repository", "crate.homepage", "crate.documentation",
+            "max_stable_version", "newest_version",
+        ]);
+        // Optional manifest fields are `null` when unset
+        let mut optional = |path: &str| fields.remove(path).filter(|value| value != "null" && !value.is_empty());
+        let description = optional("crate.description");
+        let repository = optional("crate.repository");
+        let homepage = optional("crate.homepage");
+        let documentation = optional("crate.documentation");
+        let max_stable_version = optional("max_stable_version");
+        let keywords = Json::extract_array(body, "crate.keywords");
+        let categories = Json::extract_array(body, "crate.categories");
+        let mut take = |path: &str| fields.remove(path).unwrap_or_else(|| "N/A".to_string());
+        let latest = take("max_version");
+        let newest_version = take("newest_version");
+        let latest_yanked = query::versions_array(body).and_then(ArrayIter::new).into_iter().flatten()
+            .find(|version| Json::extract_ref(version, "num").as_deref() == Some(latest.as_str()))
+            .is_some_and(|version| Json::extract_bool(&version, "yanked"));
+        let total_downloads = Json::parse_u64(&take("downloads")).unwrap_or(0);
+        let recent_downloads_count = Json::parse_u64(&take("recent_downloads")).unwrap_or(0);
+        // Get total number of versions
+        let versions = Json::parse_u64(&take("num_versions")).unwrap_or(0);
+        let license = take("license");
+        let created_at = take("created_at");
+        let updated_at = take("updated_at");
+        let etag = http::header(head, "etag").map(str::to_string);
+        let last_modified = http::header(head, "last-modified").map(str::to_string);
+
+        CrateInfo {
+            latest, max_stable_version, newest_version, latest_yanked, downloads: format_number(total_downloads), total_downloads,
+            recent_downloads: format_number(recent_downloads_count), recent_downloads_count, versions, license,
+            description, repository, homepage, documentation, keywords, categories, created_at, updated_at, etag, last_modified,
+            raw_body: None,
+        }
+    }
+
+    /// Converts the metadata into a JSON object; unset optional fields become `null`.
+    ///
+    /// [`raw_body`](CrateInfo::raw_body) is left out.
+    pub fn to_value(&self) -> Value {
+        Value::object()
+            .field("latest", self.latest.as_str())
+            .field("max_stable_version", self.max_stable_version.clone())
+            .field("newest_version", self.newest_version.as_str())
+            .field("latest_yanked", self.latest_yanked)
+            .field("downloads", self.downloads.as_str())
+            .field("total_downloads", self.total_downloads)
+            .field("recent_downloads", self.recent_downloads.as_str())
+            .field("recent_downloads_count", self.recent_downloads_count)
+            .field("versions", self.versions)
+            .field("license", self.license.as_str())
+            .field("description", self.description.clone())
+            .field("repository", self.repository.clone())
+            .field("homepage", self.homepage.clone())
+            .field("documentation", self.documentation.clone())
+            .field("keywords", self.keywords.clone())
+            .field("categories", self.categories.clone())
+            .field("created_at", self.created_at.as_str())
+            .field("updated_at", self.updated_at.as_str())
+            .field("etag", self.etag.clone())
+            .field("last_modified", self.last_modified.clone())
+    }
+
+    /// Serializes the metadata as a compact JSON object, using crator's own writer (no `serde` needed).
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, crate_data, Json};
+    ///
+    /// let json = block_on(crate_data("serde")).expect("Failed to fetch crate data").to_json();
+    /// assert_eq!(Json::extract(&json, "latest_yanked"), "false");
+    /// println!("{}", json);
+    /// ```
+    pub fn to_json(&self) -> String {
+        Json::write(&self.to_value())
+    }
+
+    /// Serializes the metadata as the body of a TOML table, one `key = value` line per field.
+    ///
+    /// TOML has no `null`, so unset optional fields are omitted. Put a header such as
+    /// `[serde]` in front to embed several crates in one document.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, crate_data};
+    ///
+    /// let info = block_on(crate_data("serde")).expect("Failed to fetch crate data");
+    /// println!("[serde]\n{}", info.to_toml());
+    /// // [serde]
+    /// // latest = "1.0.210"
+    /// // ...
+    /// ```
+    pub fn to_toml(&self) -> String {
+        let Value::Object(fields) = self.to_value() else { unreachable!("to_value builds an object") };
+        fields.iter()
+            .filter(|(_, value)| !value.is_null())
+            // JSON strings, numbers, booleans and string arrays are valid TOML values as written
+            .map(|(key, value)| format!("{} = {}\n", key, Json::write(value)))
+            .collect()
+    }
+
+    /// Whole days since the crate was first published, or `None` if
+    /// [`created_at`](CrateInfo::created_at) can't be parsed.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, crate_data};
+    ///
+    /// let info = block_on(crate_data("serde")).expect("Failed to fetch crate data");
+    /// if let (Some(age), Some(idle)) = (info.age_days(), info.days_since_update()) {
+    ///     println!("{} days old, last updated {} days ago", age, idle);
+    ///     if idle > 365 { println!("⚠️ possibly unmaintained"); }
+    /// }
+    /// ```
+    pub fn age_days(&self) -> Option<u64> {
+        Self::days_since(&self.created_at)
+    }
+
+    /// Whole days since the crate was last updated, or `None` if
+    /// [`updated_at`](CrateInfo::updated_at) can't be parsed.
+    pub fn days_since_update(&self) -> Option<u64> {
+        Self::days_since(&self.updated_at)
+    }
+
+    pub(crate) fn days_since(timestamp: &str) -> Option<u64> {
+        let then = Timestamp::parse(timestamp)?.to_system_time();
+        // A timestamp slightly ahead of the local clock counts as today
+        Some(SystemTime::now().duration_since(then).map_or(0, |elapsed| elapsed.as_secs() / 86_400))
+    }
+
+    /// Parses [`latest`](CrateInfo::latest) into a comparable [`Version`].
+    ///
+    /// Returns `None` if the version is missing (`"N/A"`) or malformed.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, crate_data};
+    ///
+    /// let info = block_on(crate_data("serde")).expect("Failed to fetch crate data");
+    /// if info.latest_semver().is_some_and(|latest| latest >= (1, 0, 200)) {
+    ///     println!("serde v{} is recent enough", info.latest);
+    /// }
+    /// ```
+    pub fn latest_semver(&self) -> Option<Version> {
+        Version::parse(&self.latest)
+    }
+}
+
+impl fmt::Display for CrateInfo {
+    /// Prints one aligned `Label: value` line per field; unset optional fields are skipped.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            ("Latest:", format!("v{}{}", self.latest, if self.latest_yanked { " (yanked)" } else { "" })),
+        ];
+        // Only worth a line when they tell something `latest` doesn't
+        if let Some(stable) = self.max_stable_version.as_ref().filter(|stable| **stable != self.latest) {
+            lines.push(("Latest Stable:", format!("v{}", stable)));
+        }
+        if self.newest_version != self.latest && self.newest_version != "N/A" {
+            lines.push(("Newest:", format!("v{}", self.newest_version)));
+        }
+        lines.extend([
+            ("Downloads:", self.downloads.clone()),
+            ("Total Downloads:", self.total_downloads.to_string()),
+            ("Recent Downloads:", self.recent_downloads.clone()),
+            ("Versions:", self.versions.to_string()),
+            ("Created At:", self.created_at.clone()),
+            ("Updated At:", self.updated_at.clone()),
+            ("License:", self.license.clone()),
+        ]);
+        let links = [("Description:", &self.description), ("Repository:", &self.repository), ("Homepage:", &self.homepage), ("Documentation:", &self.documentation)];
+        // Multi-line descriptions are folded onto one line to keep the columns aligned
+        lines.extend(links.into_iter().filter_map(|(label, value)| Some((label, value.as_ref()?.split_whitespace().collect::<Vec<_>>().join(" ")))));
+        if !self.keywords.is_empty() { lines.push(("Keywords:", self.keywords.join(", "))); }
+        if !self.categories.is_empty() { lines.push(("Categories:", self.categories.join(", "))); }
+        for (i, (label, value)) in lines.iter().enumerate() {
+            if i > 0 { writeln!(f)?; }
+            write!(f, "{:<20}{}", label, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validators sent with a conditional request.
+///
+/// When the server determines the resource is unchanged it answers `304 Not Modified`
+/// without a body, and the fetch resolves to [`Fetched::NotModified`].
+///
+/// # Example
+/// ```rust
+/// use crator::Conditions;
+///
+/// let conditions = Conditions::new().if_none_match("\"a1b2c3\"");
+/// assert_eq!(conditions.etag(), Some("\"a1b2c3\""));
+///
+/// let since = std::time::UNIX_EPOCH + std::time::Duration::from_secs(784_111_777);
+/// let conditions = Conditions::new().if_modified_since(since);
+/// assert_eq!(conditions.modified_since(), Some("Sun, 06 Nov 1994 08:49:37 GMT"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Conditions {
+    etag: Option<String>,
+    modified_since: Option<String>,
+}
+
+impl Conditions {
+    /// Creates an empty set of conditions (an unconditional request).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds conditions from the validators recorded on a previous result.
+    pub fn from_info(info: &CrateInfo) -> Self {
+        Self { etag: info.etag.clone(), modified_since: info.last_modified.clone() }
+    }
+
+    /// Sends `If-None-Match` with the given `ETag` (as returned by the server, quotes included).
+    pub fn if_none_match(mut self, etag: &str) -> Self {
+        self.etag = Some(etag.to_string());
+        self
+    }
+
+    /// Sends `If-Modified-Since` with the given last-fetch time, for watch-mode use cases.
+    pub fn if_modified_since(mut self, time: SystemTime) -> Self {
+        self.modified_since = Some(http::http_date(time));
+        self
+    }
+
+    /// The `ETag` that will be sent, if any.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// The `If-Modified-Since` date that will be sent, if any.
+    pub fn modified_since(&self) -> Option<&str> {
+        self.modified_since.as_deref()
+    }
+
+    /// Attaches the validator headers to `request`.
+    pub fn apply(&self, mut request: Request) -> Request {
+        if let Some(etag) = &self.etag { request = request.header("If-None-Match", etag); }
+        if let Some(since) = &self.modified_since { request = request.header("If-Modified-Since", since); }
+        request
+    }
+}
+
+/// The outcome of a conditional fetch.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Fetched<T> {
+    /// The resource changed (or no validators were sent); holds the fresh value.
+    Modified(T),
+    /// The server answered `304 Not Modified`; the previously fetched value is still current.
+    NotModified,
 }
 
 /// Formats large numbers into human-readable strings.
@@ -276,6 +1535,10 @@ pub fn format_number(n: u64) -> String {
 /// # Returns
 /// * `Result<CrateInfo, Box<dyn Error>>` containing the crate's metadata.
 ///
+/// # Errors
+/// A non-2xx response (e.g. an unknown crate) yields a [`FetchError::Status`]
+/// carrying the HTTP status code.
+///
 /// # Examples
 ///
 /// ```rust
@@ -327,46 +1590,158 @@ pub fn format_number(n: u64) -> String {
 /// 
 ///     // 3. Match the result to display the metadata
 ///     match result {
-///         Ok(info) => {
-///             println!("Latest:             v{}", info.latest);
-///             println!("Downloads:          {}", info.downloads);
-///             println!("Total Downloads:    {}", info.total_downloads);
-///             println!("Versions:           {}", info.versions);
-///             println!("Created At:         {}", info.created_at);
-///             println!("Updated At:         {}", info.updated_at);
-///             println!("License:            {}", info.license);
-///         }
+///         Ok(info) => println!("{}", info),
 ///         Err(e) => eprintln!("❌ Error: {}", e),
 ///     }
 /// } 
 /// ```
 pub async fn crate_data(crate_name: &str) -> Result<CrateInfo, Box<dyn Error>> {
-    let host = "crates.io";
+    crate_data_with(crate_name, default_config()).await
+}
+
+/// The process-wide default configuration, so repeated [`crate_data`] calls share one `TlsConnector`.
+pub(crate) fn default_config() -> &'static Config {
+    static DEFAULT: OnceLock<Config> = OnceLock::new();
+    DEFAULT.get_or_init(Config::default)
+}
+
+/// Fetches crate data from the crates.io API using a custom [`Config`].
+///
+/// Behaves exactly like [`crate_data`], but builds the TLS connection from the
+/// given configuration, e.g. to trust the root CA of a TLS-intercepting proxy.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{crate_data_with, block_on, Certificate, Config};
+///
+/// let pem = std::fs::read("corporate-ca.pem").expect("CA file");
+/// let config = Config::new()
+///     .add_root_certificate(Certificate::from_pem(&pem).expect("valid PEM"));
+///
+/// let info = block_on(crate_data_with("mathlab", &config)).expect("Failed to fetch crate data");
+/// println!("Latest: v{}", info.latest);
+/// ```
+pub async fn crate_data_with(crate_name: &str, config: &Config) -> Result<CrateInfo, Box<dyn Error>> {
     let path = format!("/api/v1/crates/{}", crate_name);
+    let (head, body) = http::fetch_api(&Request::new("GET", &path), config).await?;
+    // Fail loudly on 404/403/5xx instead of extracting "N/A" from an error body
+    http::ensure_success(&head)?;
 
-    let connector = TlsConnector::new()?;
-    let stream = TcpStream::connect(format!("{}:443", host))?;
-    let mut tls_stream = connector.connect(host, stream)?;
+    Ok(CrateInfo::from_response(&head, body, config))
+}
 
-    let request = format!(
-        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: crator_safe/1.0\r\nConnection: close\r\n\r\n",
-        path, host
-    );
+/// Fetches the metadata of many crates concurrently, returning one result per name
+/// in input order.
+///
+/// At most 8 requests are in flight at once, which keeps large batches (e.g. every
+/// dependency of a project) polite to crates.io; see [`crates_data_with`] to tune it.
+/// One crate failing doesn't affect the others.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, crates_data};
+///
+/// let names = ["serde", "rand", "mathlab"];
+/// for (name, info) in names.iter().zip(block_on(crates_data(&names))) {
+///     match info {
+///         Ok(info) => println!("{}: v{}", name, info.latest),
+///         Err(e) => eprintln!("{}: {}", name, e),
+///     }
+/// }
+/// ```
+pub async fn crates_data(names: &[&str]) -> Vec<Result<CrateInfo, Box<dyn Error>>> {
+    crates_data_with(names, default_config()).await
+}
 
-    tls_stream.write_all(request.as_bytes())?;
-    let mut response = Vec::new();
-    tls_stream.read_to_end(&mut response)?;
+/// Same as [`crates_data`], with a custom [`Config`]; [`Config::max_concurrency`]
+/// sets how many requests are in flight at once.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, crates_data_with, Config};
+///
+/// let config = Config::new().max_concurrency(2);
+/// let results = block_on(crates_data_with(&["serde", "rand", "mathlab"], &config));
+/// assert_eq!(results.len(), 3);
+/// ```
+pub async fn crates_data_with(names: &[&str], config: &Config) -> Vec<Result<CrateInfo, Box<dyn Error>>> {
+    let fetched = executor::map_bounded(names, config.concurrency(), |name| async move {
+        crate_data_with(name, config).await.map_err(http::sendable)
+    }).await;
+    fetched.into_iter().map(|result| result.map_err(|e| e as Box<dyn Error>)).collect()
+}
 
-    let full_res = String::from_utf8_lossy(&response);
-    let body = full_res.split("\r\n\r\n").nth(1).unwrap_or("");
+/// Fetches crate data only if it changed since a previous fetch.
+///
+/// Sends the validators in `conditions` (e.g. the `ETag` or `Last-Modified` date
+/// recorded on an earlier [`CrateInfo`]) and resolves to [`Fetched::NotModified`] when crates.io answers
+/// `304`, so pollers don't re-download the full payload every time.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{crate_data, crate_data_conditional, block_on, Conditions, Config, Fetched};
+///
+/// let info = block_on(crate_data("mathlab")).expect("Failed to fetch crate data");
+///
+/// // Later: only download the payload again if it changed.
+/// let conditions = Conditions::from_info(&info);
+/// match block_on(crate_data_conditional("mathlab", &conditions, &Config::default())) {
+///     Ok(Fetched::Modified(fresh)) => println!("Updated: v{}", fresh.latest),
+///     Ok(Fetched::NotModified) => println!("Unchanged: v{}", info.latest),
+///     Err(e) => eprintln!("❌ Error: {}", e),
+/// }
+/// ```
+pub async fn crate_data_conditional(crate_name: &str, conditions: &Conditions, config: &Config) -> Result<Fetched<CrateInfo>, Box<dyn Error>> {
+    let path = format!("/api/v1/crates/{}", crate_name);
+    let (head, body) = http::fetch_api(&conditions.apply(Request::new("GET", &path)), config).await?;
+    if http::parse_status_line(&head)?.0 == 304 { return Ok(Fetched::NotModified); }
+    http::ensure_success(&head)?;
 
-    let latest = Json::extract(body, "max_version");
-    let total_downloads = Json::extract_u64(body, "downloads");
-    // Get total number of versions
-    let versions = Json::extract_u64(body, "num_versions");
-    let license = Json::extract(body, "license");
-    let created_at = Json::extract(body, "created_at");
-    let updated_at = Json::extract(body, "updated_at");
+    Ok(Fetched::Modified(CrateInfo::from_response(&head, body, config)))
+}
 
-    Ok(CrateInfo { latest, downloads: format_number(total_downloads), total_downloads: total_downloads, versions: versions, license, created_at, updated_at})
+/// Streams the body of a crates.io API endpoint, invoking `on_chunk` for each piece as it arrives.
+///
+/// This is a lower-level alternative to [`crate_data`] for large payloads (reverse
+/// dependencies, search pages): the response is never buffered as a whole. Chunked
+/// transfer encoding is decoded transparently, so `on_chunk` only ever sees body bytes.
+/// Chunks may split multi-byte UTF-8 sequences. The socket is read on a helper
+/// thread, while `on_chunk` runs in the awaiting task.
+///
+/// # Arguments
+/// * `path` - The API path, e.g. `"/api/v1/crates/serde/reverse_dependencies"`.
+/// * `config` - Connection settings.
+/// * `on_chunk` - Callback receiving each body chunk.
+///
+/// # Errors
+/// A non-2xx response yields a [`FetchError::Status`] and `on_chunk` is never called.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{fetch_stream, block_on, Config};
+///
+/// let mut total = 0;
+/// block_on(fetch_stream("/api/v1/crates/serde/reverse_dependencies", &Config::default(), |chunk| {
+///     total += chunk.len();
+/// })).expect("Failed to stream");
+/// println!("Received {} bytes", total);
+/// ```
+pub async fn fetch_stream<F: FnMut(&[u8])>(path: &str, config: &Config, mut on_chunk: F) -> Result<(), Box<dyn Error>> {
+    // The `fetch` API hands over the body in one piece
+    #[cfg(target_arch = "wasm32")]
+    {
+        let (head, body) = http::fetch_api(&Request::new("GET", path), config).await?;
+        http::ensure_success(&head)?;
+        on_chunk(body.as_bytes());
+        Ok(())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let (host, request) = http::api_request(&Request::new("GET", path), config);
+        let mut body = http::stream(host.to_string(), request, config.clone());
+        while let Some(chunk) = body.next().await? {
+            on_chunk(&chunk);
+        }
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,169 @@
+use crate::coalesce::duplicate;
+use crate::lockfile::{published_versions, versions_of};
+use crate::{Config, DepTree, Lockfile};
+use std::error::Error;
+use std::fmt;
+
+/// SPDX license identifiers common on crates.io, compared case-insensitively.
+const KNOWN_LICENSES: [&str; 40] = [
+    "0BSD", "AFL-3.0", "AGPL-3.0", "AGPL-3.0-only", "AGPL-3.0-or-later", "Apache-2.0", "Artistic-2.0", "BSD-1-Clause", "BSD-2-Clause",
+    "BSD-3-Clause", "BSL-1.0", "CC-BY-4.0", "CC0-1.0", "CDLA-Permissive-2.0", "EPL-2.0", "EUPL-1.2", "GPL-2.0", "GPL-2.0-only",
+    "GPL-2.0-or-later", "GPL-3.0", "GPL-3.0-only", "GPL-3.0-or-later", "ISC", "LGPL-2.1", "LGPL-2.1-only", "LGPL-2.1-or-later",
+    "LGPL-3.0", "LGPL-3.0-only", "LGPL-3.0-or-later", "MIT", "MIT-0", "MPL-2.0", "NCSA", "OpenSSL", "Unicode-3.0", "Unicode-DFS-2016",
+    "Unlicense", "WTFPL", "Zlib", "zlib-acknowledgement",
+];
+
+/// SPDX exceptions allowed after `WITH`.
+const KNOWN_EXCEPTIONS: [&str; 3] = ["LLVM-exception", "Classpath-exception-2.0", "GCC-exception-3.1"];
+
+/// Normalizes a `license` field: the legacy `/` separator becomes `OR` and
+/// whitespace is collapsed.
+fn normalize(license: &str) -> String {
+    license.replace('/', " OR ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether every identifier of an SPDX expression is a known license or exception.
+fn is_known(expression: &str) -> bool {
+    let spaced = expression.replace('(', " ( ").replace(')', " ) ");
+    let mut after_with = false;
+    let mut any = false;
+    for token in spaced.split_whitespace() {
+        match token {
+            "(" | ")" | "AND" | "OR" => after_with = false,
+            "WITH" => after_with = true,
+            _ if after_with => {
+                if !KNOWN_EXCEPTIONS.iter().any(|known| known.eq_ignore_ascii_case(token)) { return false; }
+                after_with = false;
+            }
+            _ => {
+                let id = token.strip_suffix('+').unwrap_or(token);
+                if !KNOWN_LICENSES.iter().any(|known| known.eq_ignore_ascii_case(id)) { return false; }
+                any = true;
+            }
+        }
+    }
+    any
+}
+
+/// The crates sharing one license expression in a [`LicenseReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LicenseGroup {
+    /// The SPDX expression, with `/` normalized to `OR` (e.g. `"MIT OR Apache-2.0"`).
+    pub expression: String,
+    /// Whether every identifier in the expression is a well-known SPDX license;
+    /// `false` for typos, `LicenseRef-*` and rare licenses worth a manual look.
+    pub known: bool,
+    /// The crates under it, as `name version`.
+    pub crates: Vec<String>,
+}
+
+/// The licenses of a dependency set, built by [`license_report`] or [`DepTree::licenses`].
+#[derive(Debug, Default)]
+pub struct LicenseReport {
+    /// The license expressions, most common first.
+    pub groups: Vec<LicenseGroup>,
+    /// The crates (`name version`) declaring no `license`, e.g. only a `license-file`.
+    pub missing: Vec<String>,
+    /// The crates whose versions couldn't be fetched, with the reason.
+    pub failed: Vec<(String, Box<dyn Error>)>,
+}
+
+impl LicenseReport {
+    /// Builds a report from `(name version, license)` pairs, counting each crate once.
+    fn collect<'a>(crates: impl IntoIterator<Item = (String, Option<&'a str>)>) -> Self {
+        let mut report = LicenseReport::default();
+        for (name, license) in crates {
+            let Some(expression) = license.map(normalize).filter(|expression| !expression.is_empty()) else {
+                if !report.missing.contains(&name) { report.missing.push(name); }
+                continue;
+            };
+            match report.groups.iter_mut().find(|group| group.expression == expression) {
+                Some(group) => if !group.crates.contains(&name) { group.crates.push(name); },
+                None => report.groups.push(LicenseGroup { known: is_known(&expression), expression, crates: vec![name] }),
+            }
+        }
+        report.groups.sort_by(|a, b| b.crates.len().cmp(&a.crates.len()).then_with(|| a.expression.cmp(&b.expression)));
+        report
+    }
+
+    /// The groups whose expression isn't made of well-known SPDX identifiers.
+    pub fn unknown(&self) -> impl Iterator<Item = &LicenseGroup> {
+        self.groups.iter().filter(|group| !group.known)
+    }
+}
+
+impl fmt::Display for LicenseReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for group in &self.groups {
+            write!(f, "{:>5}  {}", group.crates.len(), group.expression)?;
+            if !group.known { write!(f, "  (unknown: {})", group.crates.join(", "))?; }
+            writeln!(f)?;
+        }
+        if !self.missing.is_empty() { writeln!(f, "no license: {}", self.missing.join(", "))?; }
+        for (name, e) in &self.failed {
+            writeln!(f, "{:<24} error: {}", name, e)?;
+        }
+        Ok(())
+    }
+}
+
+impl DepTree {
+    /// The licenses of every resolved crate version in the tree.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, dependency_tree};
+    ///
+    /// let tree = block_on(dependency_tree("reqwest", None, 16)).expect("Failed to resolve tree");
+    /// let licenses = tree.licenses();
+    /// print!("{}", licenses);
+    /// assert!(licenses.unknown().next().is_none() && licenses.missing.is_empty(), "licenses need review");
+    /// ```
+    pub fn licenses(&self) -> LicenseReport {
+        LicenseReport::collect(self.iter().filter_map(|node| {
+            let version = node.version.as_ref()?;
+            Some((format!("{} {}", node.name, version.num), version.license.as_deref()))
+        }))
+    }
+}
+
+/// Collects the license of every locked crates.io package of `lockfile` and groups
+/// them by SPDX expression, flagging unknown and missing ones.
+///
+/// The license is the one published with the locked version; see
+/// [`license_report_with`] for a custom [`Config`].
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, license_report, Lockfile};
+///
+/// let lockfile = Lockfile::from_path("Cargo.lock").expect("Failed to read lockfile");
+/// let report = block_on(license_report(&lockfile));
+/// print!("{}", report);
+/// for group in report.unknown() {
+///     eprintln!("review {}: {}", group.expression, group.crates.join(", "));
+/// }
+/// ```
+pub async fn license_report(lockfile: &Lockfile) -> LicenseReport {
+    license_report_with(lockfile, crate::default_config()).await
+}
+
+/// Same as [`license_report`], but driven by a custom [`Config`]; requests run with
+/// [`Config::max_concurrency`] in flight.
+pub async fn license_report_with(lockfile: &Lockfile, config: &Config) -> LicenseReport {
+    let fetched = published_versions(lockfile, config).await;
+    let mut failed: Vec<(String, Box<dyn Error>)> = Vec::new();
+    let mut crates = Vec::new();
+    for package in lockfile.crates_io() {
+        match versions_of(&fetched, &package.name) {
+            Ok(versions) => {
+                let license = versions.iter().find(|version| version.num == package.version).and_then(|version| version.license.as_deref());
+                crates.push((format!("{} {}", package.name, package.version), license));
+            }
+            Err(_) if failed.iter().any(|(name, _)| *name == package.name) => {}
+            Err(e) => failed.push((package.name.clone(), duplicate(e.as_ref()))),
+        }
+    }
+    LicenseReport { failed, ..LicenseReport::collect(crates) }
+}
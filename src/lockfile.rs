@@ -0,0 +1,477 @@
+use crate::coalesce::duplicate;
+use crate::executor::map_bounded;
+use crate::query::crate_versions;
+use crate::tree::{duplicates, Duplicate, Listing};
+use crate::{http, Config, Json, Toml, TomlError, UpdateKind, Value, Version, VersionInfo};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The `source` of packages from crates.io, through the git and the sparse index.
+const CRATES_IO_SOURCES: [&str; 2] = ["registry+https://github.com/rust-lang/crates.io-index", "sparse+https://index.crates.io/"];
+
+/// One `[[package]]` entry of a `Cargo.lock`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LockedPackage {
+    /// The crate name.
+    pub name: String,
+    /// The exact locked version.
+    pub version: String,
+    /// Where the package comes from (e.g., `registry+https://...`); `None` for
+    /// workspace members and path dependencies.
+    pub source: Option<String>,
+    /// The SHA-256 checksum of the `.crate` archive, for registry packages.
+    pub checksum: Option<String>,
+    /// The packages it depends on, as `name` or `name version` when ambiguous.
+    pub dependencies: Vec<String>,
+}
+
+impl LockedPackage {
+    /// Returns `true` if the package was resolved from crates.io.
+    pub fn is_crates_io(&self) -> bool {
+        self.source.as_deref().is_some_and(|source| CRATES_IO_SOURCES.contains(&source))
+    }
+
+    fn parse(value: &Value) -> Option<Self> {
+        let field = |name: &str| value.get(name).and_then(Value::as_str).map(str::to_string);
+        Some(LockedPackage {
+            name: field("name")?,
+            version: field("version")?,
+            source: field("source"),
+            checksum: field("checksum"),
+            dependencies: value.get("dependencies").and_then(Value::as_array).unwrap_or_default().iter()
+                .filter_map(|dependency| dependency.as_str().map(str::to_string))
+                .collect(),
+        })
+    }
+}
+
+/// The resolved packages of a `Cargo.lock`.
+///
+/// # Example
+/// ```rust
+/// use crator::Lockfile;
+///
+/// let lockfile = Lockfile::parse(r#"
+/// version = 3
+///
+/// [[package]]
+/// name = "demo"
+/// version = "0.1.0"
+/// dependencies = ["serde"]
+///
+/// [[package]]
+/// name = "serde"
+/// version = "1.0.190"
+/// source = "registry+https://github.com/rust-lang/crates.io-index"
+/// checksum = "91d3c334ca1ee894a2c6f6ad698fe8c435b76d504b13d436f0685d648d6d96f7"
+/// "#).expect("Invalid lockfile");
+///
+/// assert_eq!(lockfile.version, Some(3));
+/// assert_eq!(lockfile.packages.len(), 2);
+/// assert!(!lockfile.packages[0].is_crates_io());
+/// assert_eq!(lockfile.crates_io().map(|package| package.version.as_str()).collect::<Vec<_>>(), ["1.0.190"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lockfile {
+    /// The lockfile format version, if declared.
+    pub version: Option<u64>,
+    /// Every locked package, in file order.
+    pub packages: Vec<LockedPackage>,
+}
+
+impl Lockfile {
+    /// Parses the text of a `Cargo.lock`.
+    ///
+    /// # Errors
+    /// Fails if the text isn't valid TOML.
+    pub fn parse(text: &str) -> Result<Self, TomlError> {
+        let document = Toml::parse(text)?;
+        Ok(Lockfile {
+            version: document.get("version").and_then(Value::as_u64),
+            packages: document.get("package").and_then(Value::as_array).unwrap_or_default().iter().filter_map(LockedPackage::parse).collect(),
+        })
+    }
+
+    /// Reads and parses a `Cargo.lock` file.
+    ///
+    /// # Errors
+    /// Fails if the file can't be read or isn't valid TOML.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::parse(&fs::read_to_string(path)?)?)
+    }
+
+    /// The packages resolved from crates.io.
+    pub fn crates_io(&self) -> impl Iterator<Item = &LockedPackage> {
+        self.packages.iter().filter(|package| package.is_crates_io())
+    }
+
+    /// Finds the package a `dependencies` entry (`name`, `name version` or
+    /// `name version (source)`) refers to.
+    pub fn resolve(&self, dependency: &str) -> Option<&LockedPackage> {
+        self.position(dependency).map(|index| &self.packages[index])
+    }
+
+    fn position(&self, dependency: &str) -> Option<usize> {
+        let mut parts = dependency.split_whitespace();
+        let (name, version) = (parts.next()?, parts.next());
+        self.packages.iter().position(|package| package.name == name && version.is_none_or(|version| package.version == version))
+    }
+
+    /// The local packages (workspace members and path dependencies, which have no
+    /// `source`) that pull in each package, directly or through other non-local
+    /// packages; indexed like [`packages`](Lockfile::packages).
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Lockfile;
+    ///
+    /// let lockfile = Lockfile::parse(r#"
+    /// [[package]]
+    /// name = "app"
+    /// version = "0.1.0"
+    /// dependencies = ["serde_json"]
+    ///
+    /// [[package]]
+    /// name = "serde_json"
+    /// version = "1.0.108"
+    /// source = "registry+https://github.com/rust-lang/crates.io-index"
+    /// dependencies = ["serde"]
+    ///
+    /// [[package]]
+    /// name = "serde"
+    /// version = "1.0.190"
+    /// source = "registry+https://github.com/rust-lang/crates.io-index"
+    /// "#).expect("Invalid lockfile");
+    ///
+    /// assert_eq!(lockfile.pulled_in_by()[2], ["app"]);
+    /// ```
+    pub fn pulled_in_by(&self) -> Vec<Vec<String>> {
+        let mut pulled_in_by = vec![Vec::new(); self.packages.len()];
+        for (root, package) in self.packages.iter().enumerate().filter(|(_, package)| package.source.is_none()) {
+            let mut seen = vec![false; self.packages.len()];
+            let mut pending = vec![root];
+            while let Some(index) = pending.pop() {
+                for dependency in &self.packages[index].dependencies {
+                    let Some(next) = self.position(dependency) else { continue };
+                    // Another local package is where its own dependencies get attributed
+                    if seen[next] || self.packages[next].source.is_none() { continue; }
+                    seen[next] = true;
+                    pulled_in_by[next].push(package.name.clone());
+                    pending.push(next);
+                }
+            }
+        }
+        pulled_in_by
+    }
+
+    /// The crates locked at several semver-incompatible versions, with a shortest
+    /// dependency chain from each local package reaching them: the local package's
+    /// name, then one `name version` step per package.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Lockfile;
+    ///
+    /// let lockfile = Lockfile::parse(r#"
+    /// [[package]]
+    /// name = "app"
+    /// version = "0.1.0"
+    /// dependencies = ["rand 0.8.5", "rand 0.9.0"]
+    ///
+    /// [[package]]
+    /// name = "rand"
+    /// version = "0.8.5"
+    /// source = "registry+https://github.com/rust-lang/crates.io-index"
+    ///
+    /// [[package]]
+    /// name = "rand"
+    /// version = "0.9.0"
+    /// source = "registry+https://github.com/rust-lang/crates.io-index"
+    /// "#).expect("Invalid lockfile");
+    ///
+    /// let duplicates = lockfile.duplicates();
+    /// assert_eq!(duplicates[0].name, "rand");
+    /// assert_eq!(duplicates[0].versions[0].version, "0.8.5");
+    /// assert_eq!(duplicates[0].versions[1].chains, [["app", "rand 0.9.0"]]);
+    /// ```
+    pub fn duplicates(&self) -> Vec<Duplicate> {
+        let mut occurrences = Vec::new();
+        for (root, package) in self.packages.iter().enumerate().filter(|(_, package)| package.source.is_none()) {
+            let mut previous: Vec<Option<usize>> = vec![None; self.packages.len()];
+            let mut pending = VecDeque::from([root]);
+            while let Some(index) = pending.pop_front() {
+                for dependency in &self.packages[index].dependencies {
+                    let Some(next) = self.position(dependency) else { continue };
+                    if previous[next].is_some() || self.packages[next].source.is_none() { continue; }
+                    previous[next] = Some(index);
+                    pending.push_back(next);
+
+                    let mut chain = Vec::new();
+                    let mut step = next;
+                    while step != root {
+                        chain.push(format!("{} {}", self.packages[step].name, self.packages[step].version));
+                        step = previous[step].expect("every reached package has a predecessor");
+                    }
+                    chain.push(package.name.clone());
+                    chain.reverse();
+                    occurrences.push((self.packages[next].name.clone(), self.packages[next].version.clone(), chain));
+                }
+            }
+        }
+        duplicates(occurrences)
+    }
+}
+
+/// A locked crate with newer versions on crates.io.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutdatedCrate {
+    /// The crate name.
+    pub name: String,
+    /// The locked version.
+    pub locked: String,
+    /// The newest non-yanked version (the newest stable one unless only
+    /// pre-releases exist or the locked version is a pre-release).
+    pub latest: String,
+    /// Whether the update to `latest` is a patch, minor, major, or pre-release one.
+    pub update: UpdateKind,
+    /// How many non-yanked versions are newer than the locked one, up to and including `latest`.
+    pub versions_behind: usize,
+    /// Whether `latest` is semver-incompatible with the locked version, i.e. the
+    /// update needs a requirement change in `Cargo.toml` and may break the build.
+    pub breaking: bool,
+    /// The workspace members (or other local packages) depending on it, directly or
+    /// transitively (see [`Lockfile::pulled_in_by`]).
+    pub pulled_in_by: Vec<String>,
+}
+
+impl OutdatedCrate {
+    /// Compares a locked version against the published ones; `None` if it is up to date.
+    fn check(name: &str, locked: &str, versions: &[VersionInfo]) -> Option<Self> {
+        let current = Version::parse(locked)?;
+        let available: Vec<Version> = versions.iter().filter(|version| !version.yanked).filter_map(|version| Version::parse(&version.num)).collect();
+        let latest = available.iter().filter(|version| !version.is_prerelease() || current.is_prerelease()).max()
+            .or_else(|| available.iter().max())?;
+        if *latest <= current { return None; }
+        Some(OutdatedCrate {
+            name: name.to_string(),
+            locked: locked.to_string(),
+            latest: latest.to_string(),
+            update: current.update_kind(latest),
+            versions_behind: available.iter().filter(|version| **version > current && *version <= latest).count(),
+            breaking: !compatible(&current, latest),
+            pulled_in_by: Vec::new(),
+        })
+    }
+
+    /// The dependency line requiring `latest`, ready for a `Cargo.toml`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{OutdatedCrate, UpdateKind};
+    ///
+    /// let outdated = OutdatedCrate {
+    ///     name: "serde".to_string(),
+    ///     locked: "1.0.200".to_string(),
+    ///     latest: "1.0.210".to_string(),
+    ///     update: UpdateKind::Patch,
+    ///     versions_behind: 10,
+    ///     breaking: false,
+    ///     pulled_in_by: vec!["app".to_string()],
+    /// };
+    /// assert_eq!(outdated.suggestion(), r#"serde = "1.0.210""#);
+    /// ```
+    pub fn suggestion(&self) -> String {
+        format!("{} = {}", self.name, Json::quote(&self.latest))
+    }
+}
+
+/// A locked version that has been yanked from crates.io.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YankedCrate {
+    /// The crate name.
+    pub name: String,
+    /// The locked (yanked) version.
+    pub version: String,
+    /// The workspace members (or other local packages) depending on it, directly or
+    /// transitively (see [`Lockfile::pulled_in_by`]).
+    pub pulled_in_by: Vec<String>,
+}
+
+/// Whether `newer` matches the default (caret) requirement of `current`: same
+/// major version, or same minor for `0.x`, or same patch for `0.0.x`.
+pub(crate) fn compatible(current: &Version, newer: &Version) -> bool {
+    match (current.major, current.minor) {
+        (0, 0) => newer.major == 0 && newer.minor == 0 && newer.patch == current.patch,
+        (0, minor) => newer.major == 0 && newer.minor == minor,
+        (major, _) => newer.major == major,
+    }
+}
+
+/// The result of [`outdated_report`]: which locked crates.io packages are behind.
+#[derive(Debug, Default)]
+pub struct OutdatedReport {
+    /// The packages with newer versions, in lockfile order.
+    pub outdated: Vec<OutdatedCrate>,
+    /// How many packages are on their latest version.
+    pub up_to_date: usize,
+    /// The packages locked to a yanked version, in lockfile order. These are also
+    /// counted in `outdated` or `up_to_date`.
+    pub yanked: Vec<YankedCrate>,
+    /// The crates whose versions couldn't be fetched, with the reason.
+    pub failed: Vec<(String, Box<dyn Error>)>,
+}
+
+impl OutdatedReport {
+    /// The outdated crates whose update is semver-incompatible.
+    pub fn breaking(&self) -> impl Iterator<Item = &OutdatedCrate> {
+        self.outdated.iter().filter(|outdated| outdated.breaking)
+    }
+
+    /// The outdated crates whose update is of the given kind, e.g. only patch releases.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, outdated_report, Lockfile, UpdateKind};
+    ///
+    /// let lockfile = Lockfile::from_path("Cargo.lock").expect("Failed to read lockfile");
+    /// let report = block_on(outdated_report(&lockfile));
+    /// for outdated in report.updates(UpdateKind::Patch) {
+    ///     println!("{} {} -> {}", outdated.name, outdated.locked, outdated.latest);
+    /// }
+    /// ```
+    pub fn updates(&self, kind: UpdateKind) -> impl Iterator<Item = &OutdatedCrate> {
+        self.outdated.iter().filter(move |outdated| outdated.update == kind)
+    }
+
+    /// One [`OutdatedCrate::suggestion`] per outdated crate, in lockfile order.
+    pub fn suggestions(&self) -> Vec<String> {
+        let mut suggestions: Vec<String> = Vec::new();
+        for suggestion in self.outdated.iter().map(OutdatedCrate::suggestion) {
+            if !suggestions.contains(&suggestion) { suggestions.push(suggestion); }
+        }
+        suggestions
+    }
+
+    /// The outdated crates as a JSON array for scripts and editors, one object per
+    /// locked version: `name`, `from`, `to`, `update`, `breaking`, and `pulled_in_by`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, outdated_report, Json, Lockfile};
+    ///
+    /// let lockfile = Lockfile::from_path("Cargo.lock").expect("Failed to read lockfile");
+    /// let report = block_on(outdated_report(&lockfile));
+    /// std::fs::write("updates.json", Json::write(&report.patch_list())).expect("Failed to write patch list");
+    /// for suggestion in report.suggestions() {
+    ///     println!("{}", suggestion);
+    /// }
+    /// ```
+    pub fn patch_list(&self) -> Value {
+        Value::Array(self.outdated.iter().map(|outdated| Value::Object(vec![
+            ("name".to_string(), Value::String(outdated.name.clone())),
+            ("from".to_string(), Value::String(outdated.locked.clone())),
+            ("to".to_string(), Value::String(outdated.latest.clone())),
+            ("update".to_string(), Value::String(outdated.update.to_string())),
+            ("breaking".to_string(), Value::Bool(outdated.breaking)),
+            ("pulled_in_by".to_string(), Value::Array(outdated.pulled_in_by.iter().cloned().map(Value::String).collect())),
+        ])).collect())
+    }
+}
+
+impl fmt::Display for OutdatedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for outdated in &self.outdated {
+            let breaking = if outdated.breaking { ", breaking" } else { "" };
+            write!(f, "{:<24} {:<12} -> {:<12} {} behind ({}{})", outdated.name, outdated.locked, outdated.latest, outdated.versions_behind, outdated.update, breaking)?;
+            if !outdated.pulled_in_by.is_empty() { write!(f, "  via {}", outdated.pulled_in_by.join(", "))?; }
+            writeln!(f)?;
+        }
+        for yanked in &self.yanked {
+            write!(f, "{:<24} {:<12} yanked", yanked.name, yanked.version)?;
+            if !yanked.pulled_in_by.is_empty() { write!(f, "  via {}", yanked.pulled_in_by.join(", "))?; }
+            writeln!(f)?;
+        }
+        for (name, e) in &self.failed {
+            writeln!(f, "{:<24} error: {}", name, e)?;
+        }
+        write!(f, "{} outdated, {} up to date", self.outdated.len(), self.up_to_date)?;
+        if !self.yanked.is_empty() { write!(f, ", {} yanked", self.yanked.len())?; }
+        writeln!(f)
+    }
+}
+
+/// Compares every crates.io package of `lockfile` against the versions published on
+/// crates.io.
+///
+/// Each crate's version list is fetched once, even if several versions of it are
+/// locked; see [`outdated_report_with`] for a custom [`Config`]. In a workspace
+/// lockfile, each finding names the members that pull the crate in. Locked versions
+/// that have since been yanked are listed in [`OutdatedReport::yanked`].
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, outdated_report, Lockfile};
+///
+/// let lockfile = Lockfile::from_path("Cargo.lock").expect("Failed to read lockfile");
+/// let report = block_on(outdated_report(&lockfile));
+/// print!("{}", report);
+/// for yanked in &report.yanked {
+///     eprintln!("warning: {} {} is yanked", yanked.name, yanked.version);
+/// }
+/// for outdated in report.outdated.iter().filter(|outdated| !outdated.breaking) {
+///     println!("safe to update: {} {} -> {}", outdated.name, outdated.locked, outdated.latest);
+/// }
+/// ```
+pub async fn outdated_report(lockfile: &Lockfile) -> OutdatedReport {
+    outdated_report_with(lockfile, crate::default_config()).await
+}
+
+/// Same as [`outdated_report`], but driven by a custom [`Config`]; requests run
+/// with [`Config::max_concurrency`] in flight.
+pub async fn outdated_report_with(lockfile: &Lockfile, config: &Config) -> OutdatedReport {
+    let fetched = published_versions(lockfile, config).await;
+    let mut pulled_in_by = lockfile.pulled_in_by();
+    let mut report = OutdatedReport::default();
+    for (position, package) in lockfile.packages.iter().enumerate().filter(|(_, package)| package.is_crates_io()) {
+        match versions_of(&fetched, &package.name) {
+            Ok(versions) => {
+                let pulled_in_by = std::mem::take(&mut pulled_in_by[position]);
+                if versions.iter().any(|version| version.yanked && version.num == package.version) {
+                    report.yanked.push(YankedCrate { name: package.name.clone(), version: package.version.clone(), pulled_in_by: pulled_in_by.clone() });
+                }
+                match OutdatedCrate::check(&package.name, &package.version, versions) {
+                    Some(outdated) => report.outdated.push(OutdatedCrate { pulled_in_by, ..outdated }),
+                    None => report.up_to_date += 1,
+                }
+            }
+            Err(_) if report.failed.iter().any(|(name, _)| *name == package.name) => {}
+            Err(e) => report.failed.push((package.name.clone(), duplicate(e.as_ref()))),
+        }
+    }
+    report
+}
+
+/// The published versions of every crates.io package in `lockfile`, fetched once per
+/// name with [`Config::max_concurrency`] requests in flight.
+pub(crate) async fn published_versions<'a>(lockfile: &'a Lockfile, config: &Config) -> Vec<(&'a str, Listing<VersionInfo>)> {
+    let mut names: Vec<&str> = Vec::new();
+    for package in lockfile.crates_io() {
+        if !names.contains(&package.name.as_str()) { names.push(&package.name); }
+    }
+    let fetched = map_bounded(&names, config.concurrency(), |name| async move {
+        crate_versions(name, config).await.map_err(http::sendable)
+    }).await;
+    names.into_iter().zip(fetched).collect()
+}
+
+/// Looks up one crate in the result of [`published_versions`].
+pub(crate) fn versions_of<'a>(fetched: &'a [(&str, Listing<VersionInfo>)], name: &str) -> &'a Listing<VersionInfo> {
+    &fetched.iter().find(|(fetched, _)| *fetched == name).expect("every crates.io name was fetched").1
+}
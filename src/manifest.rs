@@ -0,0 +1,434 @@
+use crate::coalesce::duplicate;
+use crate::{crates_data_with, Config, CrateInfo, DependencyKind, Toml, TomlError, Value};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A dependency declared in a `Cargo.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestDependency {
+    /// The name of the depended-on crate (the `package` of a renamed dependency).
+    pub name: String,
+    /// The key the dependency is declared under, if it differs from `name`.
+    pub rename: Option<String>,
+    /// The version requirement (e.g., "1.0"), if any.
+    pub req: Option<String>,
+    /// Whether it is a regular, dev-, or build-dependency.
+    pub kind: DependencyKind,
+    /// Whether the dependency is only enabled through a feature.
+    pub optional: bool,
+    /// Whether the dependency's default features are enabled.
+    pub default_features: bool,
+    /// The features explicitly enabled on the dependency.
+    pub features: Vec<String>,
+    /// The platform restriction (e.g., `cfg(windows)`), if any.
+    pub target: Option<String>,
+    /// The local path of a path dependency.
+    pub path: Option<String>,
+    /// The repository URL of a git dependency.
+    pub git: Option<String>,
+    /// The alternative registry, if not crates.io.
+    pub registry: Option<String>,
+    /// Whether the declaration is inherited from the workspace (`workspace = true`).
+    pub workspace: bool,
+}
+
+impl ManifestDependency {
+    /// Returns `true` if the dependency comes from crates.io (not a path, git, or
+    /// alternative registry dependency).
+    pub fn is_crates_io(&self) -> bool {
+        self.path.is_none() && self.git.is_none() && self.registry.is_none()
+    }
+
+    /// Reads one entry of a dependency table: a requirement string or a detailed table.
+    fn parse(key: &str, value: &Value, kind: DependencyKind, target: Option<&str>) -> Self {
+        let field = |name: &str| value.get(name).and_then(Value::as_str).map(str::to_string);
+        let flag = |name: &str| value.get(name).and_then(Value::as_bool);
+        let package = field("package");
+        ManifestDependency {
+            name: package.clone().unwrap_or_else(|| key.to_string()),
+            rename: package.map(|_| key.to_string()),
+            req: value.as_str().map(str::to_string).or_else(|| field("version")),
+            kind,
+            optional: flag("optional").unwrap_or(false),
+            default_features: flag("default-features").or_else(|| flag("default_features")).unwrap_or(true),
+            features: value.get("features").and_then(Value::as_array).unwrap_or_default().iter()
+                .filter_map(|feature| feature.as_str().map(str::to_string))
+                .collect(),
+            target: target.map(str::to_string),
+            path: field("path"),
+            git: field("git"),
+            registry: field("registry"),
+            workspace: flag("workspace").unwrap_or(false),
+        }
+    }
+
+    /// Fills in what a `workspace = true` declaration inherits from `[workspace.dependencies]`.
+    fn inherit(&mut self, declared: &ManifestDependency) {
+        self.name = declared.name.clone();
+        self.rename = declared.rename.clone();
+        self.req = declared.req.clone();
+        self.default_features = declared.default_features;
+        // Features add up; `optional` stays the member's own
+        for feature in &declared.features {
+            if !self.features.contains(feature) { self.features.push(feature.clone()); }
+        }
+        self.path = declared.path.clone();
+        self.git = declared.git.clone();
+        self.registry = declared.registry.clone();
+    }
+}
+
+/// The parts of a `Cargo.toml` crator analyzes: the package and its dependencies.
+///
+/// Dependencies are read from `[dependencies]`, `[dev-dependencies]`,
+/// `[build-dependencies]`, and their `[target.'cfg(...)'.*]` variants, in that
+/// order. Declarations inheriting from the workspace (`serde.workspace = true`) are
+/// resolved against the manifest's own `[workspace.dependencies]` when it has them.
+///
+/// # Example
+/// ```rust
+/// use crator::{DependencyKind, Manifest};
+///
+/// let manifest = Manifest::parse(r#"
+/// [package]
+/// name = "demo"
+/// version = "0.1.0"
+///
+/// [dependencies]
+/// serde = { version = "1.0", features = ["derive"] }
+/// json = { package = "serde_json", version = "1" }
+/// local = { path = "../local" }
+///
+/// [target.'cfg(windows)'.dependencies]
+/// winapi = "0.3"
+///
+/// [dev-dependencies]
+/// rand = "0.8"
+/// "#).expect("Invalid manifest");
+///
+/// assert_eq!(manifest.name.as_deref(), Some("demo"));
+/// let names: Vec<&str> = manifest.dependencies.iter().map(|d| d.name.as_str()).collect();
+/// assert_eq!(names, ["serde", "serde_json", "local", "rand", "winapi"]);
+/// assert_eq!(manifest.dependencies[1].rename.as_deref(), Some("json"));
+/// assert!(!manifest.dependencies[2].is_crates_io());
+/// assert_eq!(manifest.dependencies[3].kind, DependencyKind::Dev);
+/// assert_eq!(manifest.dependencies[4].target.as_deref(), Some("cfg(windows)"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Manifest {
+    /// The package name, absent for a virtual workspace manifest.
+    pub name: Option<String>,
+    /// The package version, if declared.
+    pub version: Option<String>,
+    /// Every declared dependency.
+    pub dependencies: Vec<ManifestDependency>,
+    /// The `members` of a `[workspace]` table: member directories, possibly with `*` globs.
+    pub workspace_members: Vec<String>,
+    /// The `exclude` list of a `[workspace]` table.
+    pub workspace_exclude: Vec<String>,
+    /// The `[workspace.dependencies]` members can inherit with `workspace = true`.
+    pub workspace_dependencies: Vec<ManifestDependency>,
+}
+
+impl Manifest {
+    /// Parses the text of a `Cargo.toml`.
+    ///
+    /// # Errors
+    /// Fails if the text isn't valid TOML.
+    pub fn parse(text: &str) -> Result<Self, TomlError> {
+        let document = Toml::parse(text)?;
+        let package = |field: &str| document.get("package").and_then(|package| package.get(field)).and_then(Value::as_str).map(str::to_string);
+        let tables = [("dependencies", DependencyKind::Normal), ("dev-dependencies", DependencyKind::Dev), ("build-dependencies", DependencyKind::Build)];
+
+        let mut dependencies = Vec::new();
+        let mut read = |table: &Value, target: Option<&str>| {
+            for (section, kind) in tables {
+                for (key, value) in table.get(section).and_then(Value::as_object).unwrap_or_default() {
+                    dependencies.push(ManifestDependency::parse(key, value, kind, target));
+                }
+            }
+        };
+        read(&document, None);
+        for (target, table) in document.get("target").and_then(Value::as_object).unwrap_or_default() {
+            read(table, Some(target));
+        }
+
+        let strings = |path: &str| -> Vec<String> {
+            document.at(path).and_then(Value::as_array).unwrap_or_default().iter().filter_map(|item| item.as_str().map(str::to_string)).collect()
+        };
+        let mut manifest = Manifest {
+            name: package("name"),
+            version: package("version"),
+            dependencies,
+            workspace_members: strings("workspace.members"),
+            workspace_exclude: strings("workspace.exclude"),
+            workspace_dependencies: workspace_dependencies(&document),
+        };
+        let declared = manifest.workspace_dependencies.clone();
+        manifest.inherit_from(&declared);
+        Ok(manifest)
+    }
+
+    /// Resolves `workspace = true` dependencies against the workspace's declarations.
+    fn inherit_from(&mut self, declared: &[ManifestDependency]) {
+        let key = |dependency: &ManifestDependency| dependency.rename.clone().unwrap_or_else(|| dependency.name.clone());
+        for dependency in self.dependencies.iter_mut().filter(|dependency| dependency.workspace) {
+            if let Some(declared) = declared.iter().find(|declared| key(declared) == key(dependency)) { dependency.inherit(declared); }
+        }
+    }
+
+    /// Returns `true` for a workspace root (a manifest with a `[workspace]` table listing members).
+    pub fn is_workspace_root(&self) -> bool {
+        !self.workspace_members.is_empty()
+    }
+
+    /// Reads and parses a `Cargo.toml` file.
+    ///
+    /// # Errors
+    /// Fails if the file can't be read or isn't valid TOML.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::parse(&fs::read_to_string(path)?)?)
+    }
+
+    /// The names of the crates.io dependencies, each listed once.
+    pub fn crates_io_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for dependency in self.dependencies.iter().filter(|dependency| dependency.is_crates_io()) {
+            if !names.contains(&dependency.name.as_str()) { names.push(&dependency.name); }
+        }
+        names
+    }
+}
+
+/// Reads the `[workspace.dependencies]` table of a manifest.
+fn workspace_dependencies(document: &Value) -> Vec<ManifestDependency> {
+    document.at("workspace.dependencies").and_then(Value::as_object).unwrap_or_default().iter()
+        .map(|(key, value)| ManifestDependency::parse(key, value, DependencyKind::Normal, None))
+        .collect()
+}
+
+/// The crates of a Cargo workspace, loaded by [`Workspace::load`].
+///
+/// Members come from the root's `[workspace] members` (with `*` globs expanded and
+/// `exclude` applied), plus the root package itself when it has one. Dependencies
+/// declared with `workspace = true` are resolved against the root's
+/// `[workspace.dependencies]`.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, workspace_report, Workspace};
+///
+/// let workspace = Workspace::load(".").expect("Failed to load workspace");
+/// for member in &workspace.members {
+///     println!("{}: {} dependencies", member.name(), member.manifest.dependencies.len());
+/// }
+/// // One line per unique dependency, with the members declaring it
+/// print!("{}", block_on(workspace_report(&workspace)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    /// The directory of the root manifest.
+    pub root: PathBuf,
+    /// The root manifest.
+    pub manifest: Manifest,
+    /// Every member crate, the root package first if there is one.
+    pub members: Vec<WorkspaceMember>,
+}
+
+/// One crate of a [`Workspace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// The member's directory.
+    pub path: PathBuf,
+    /// The member's manifest, with workspace-inherited dependencies resolved.
+    pub manifest: Manifest,
+}
+
+impl WorkspaceMember {
+    /// The package name, or the directory for a manifest without one.
+    pub fn name(&self) -> String {
+        self.manifest.name.clone().unwrap_or_else(|| self.path.display().to_string())
+    }
+}
+
+impl Workspace {
+    /// Loads the workspace rooted at `path`: a directory or its `Cargo.toml`.
+    ///
+    /// A manifest without a `[workspace]` table loads as a workspace of one.
+    ///
+    /// # Errors
+    /// Fails if the root or a member manifest can't be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let (root, manifest_path) = if path.is_dir() { (path.to_path_buf(), path.join("Cargo.toml")) } else {
+            (path.parent().map(Path::to_path_buf).unwrap_or_default(), path.to_path_buf())
+        };
+        let manifest = Manifest::from_path(&manifest_path)?;
+
+        let mut members = Vec::new();
+        if manifest.name.is_some() { members.push(WorkspaceMember { path: root.clone(), manifest: manifest.clone() }); }
+        let excluded: Vec<PathBuf> = manifest.workspace_exclude.iter().map(|exclude| root.join(exclude)).collect();
+        for pattern in &manifest.workspace_members {
+            for dir in expand(&root, pattern) {
+                if dir == root || excluded.contains(&dir) || members.iter().any(|member: &WorkspaceMember| member.path == dir) { continue; }
+                let mut member = Manifest::from_path(dir.join("Cargo.toml"))?;
+                member.inherit_from(&manifest.workspace_dependencies);
+                members.push(WorkspaceMember { path: dir, manifest: member });
+            }
+        }
+        Ok(Workspace { root, manifest, members })
+    }
+}
+
+/// Expands a member path whose segments may contain `*` wildcards into the matching
+/// directories holding a `Cargo.toml`, sorted.
+fn expand(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for segment in pattern.split('/').filter(|segment| !segment.is_empty() && *segment != ".") {
+        dirs = dirs.into_iter().flat_map(|dir| -> Vec<PathBuf> {
+            if !segment.contains('*') { return vec![dir.join(segment)]; }
+            let mut matches: Vec<PathBuf> = fs::read_dir(&dir).into_iter().flatten().flatten()
+                .filter(|entry| entry.file_name().to_str().is_some_and(|name| glob_match(segment, name)))
+                .map(|entry| entry.path())
+                .collect();
+            matches.sort();
+            matches
+        }).collect();
+    }
+    dirs.into_iter().filter(|dir| dir.join("Cargo.toml").is_file()).collect()
+}
+
+/// Matches a name against a pattern where `*` stands for any run of characters.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else { return false };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        if i == parts.len() - 1 { return rest.ends_with(part); }
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+/// One dependency of a [`DependencyReport`] with its crates.io metadata.
+#[derive(Debug)]
+pub struct DependencyStatus {
+    /// The declaration in the manifest (the first one, when aggregated over a workspace).
+    pub dependency: ManifestDependency,
+    /// The packages declaring it: the manifest's own name, or every workspace member
+    /// with the same requirement.
+    pub members: Vec<String>,
+    /// The crate's metadata, or why it couldn't be fetched.
+    pub info: Result<CrateInfo, Box<dyn Error>>,
+}
+
+/// Project-wide crates.io metadata for the dependencies of a [`Manifest`] or a
+/// [`Workspace`], built by [`dependency_report`] or [`workspace_report`].
+#[derive(Debug)]
+pub struct DependencyReport {
+    /// The crates.io dependencies, in manifest (then member) order.
+    pub dependencies: Vec<DependencyStatus>,
+    /// Path, git, and alternative-registry dependencies, which aren't looked up.
+    pub skipped: Vec<ManifestDependency>,
+}
+
+impl fmt::Display for DependencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for status in &self.dependencies {
+            let dependency = &status.dependency;
+            let req = dependency.req.as_deref().unwrap_or("*");
+            match &status.info {
+                Ok(info) => write!(f, "{:<24} {:<10} latest {:<12} {:>8} downloads  {}", dependency.name, req, info.latest, info.downloads, info.license)?,
+                Err(e) => write!(f, "{:<24} {:<10} error: {}", dependency.name, req, e)?,
+            }
+            if status.members.len() > 1 { write!(f, "  (used by {})", status.members.join(", "))?; }
+            writeln!(f)?;
+        }
+        if !self.skipped.is_empty() {
+            let names: Vec<&str> = self.skipped.iter().map(|dependency| dependency.name.as_str()).collect();
+            writeln!(f, "not on crates.io: {}", names.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Fetches the [`CrateInfo`] of every crates.io dependency in `manifest`.
+///
+/// Each crate is fetched once even if declared in several tables; see
+/// [`dependency_report_with`] for a custom [`Config`].
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, dependency_report, Manifest};
+///
+/// let manifest = Manifest::from_path("Cargo.toml").expect("Failed to read manifest");
+/// let report = block_on(dependency_report(&manifest));
+/// print!("{}", report);
+/// ```
+pub async fn dependency_report(manifest: &Manifest) -> DependencyReport {
+    dependency_report_with(manifest, crate::default_config()).await
+}
+
+/// Same as [`dependency_report`], but driven by a custom [`Config`]; requests run
+/// with [`Config::max_concurrency`] in flight.
+pub async fn dependency_report_with(manifest: &Manifest, config: &Config) -> DependencyReport {
+    let member: Vec<String> = manifest.name.iter().cloned().collect();
+    report(manifest.dependencies.iter().map(|dependency| (dependency.clone(), member.clone())).collect(), config).await
+}
+
+/// Fetches the [`CrateInfo`] of every crates.io dependency across the members of
+/// `workspace`.
+///
+/// Declarations of the same crate and requirement are aggregated into one entry,
+/// whose [`members`](DependencyStatus::members) lists every member declaring it.
+pub async fn workspace_report(workspace: &Workspace) -> DependencyReport {
+    workspace_report_with(workspace, crate::default_config()).await
+}
+
+/// Same as [`workspace_report`], but driven by a custom [`Config`].
+pub async fn workspace_report_with(workspace: &Workspace, config: &Config) -> DependencyReport {
+    let mut declarations: Vec<(ManifestDependency, Vec<String>)> = Vec::new();
+    for member in &workspace.members {
+        for dependency in &member.manifest.dependencies {
+            let name = member.name();
+            match declarations.iter_mut().find(|(declared, _)| declared.name == dependency.name && declared.req == dependency.req && declared.is_crates_io() == dependency.is_crates_io()) {
+                Some((_, members)) => if !members.contains(&name) { members.push(name); },
+                None => declarations.push((dependency.clone(), vec![name])),
+            }
+        }
+    }
+    report(declarations, config).await
+}
+
+/// Fetches each crates.io crate among `declarations` once and pairs the results up.
+async fn report(declarations: Vec<(ManifestDependency, Vec<String>)>, config: &Config) -> DependencyReport {
+    let mut names: Vec<&str> = Vec::new();
+    for (dependency, _) in declarations.iter().filter(|(dependency, _)| dependency.is_crates_io()) {
+        if !names.contains(&dependency.name.as_str()) { names.push(&dependency.name); }
+    }
+    let fetched = crates_data_with(&names, config).await;
+
+    let mut report = DependencyReport { dependencies: Vec::new(), skipped: Vec::new() };
+    for (dependency, members) in &declarations {
+        if !dependency.is_crates_io() {
+            report.skipped.push(dependency.clone());
+            continue;
+        }
+        let index = names.iter().position(|name| *name == dependency.name).expect("every crates.io name was fetched");
+        // A crate declared several times shares one result
+        let info = match &fetched[index] {
+            Ok(info) => Ok(info.clone()),
+            Err(e) => Err(duplicate(e.as_ref()) as Box<dyn Error>),
+        };
+        report.dependencies.push(DependencyStatus { dependency: dependency.clone(), members: members.clone(), info });
+    }
+    report
+}
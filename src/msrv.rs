@@ -0,0 +1,130 @@
+use crate::coalesce::duplicate;
+use crate::lockfile::{published_versions, versions_of};
+use crate::{Config, Lockfile, Version, VersionInfo};
+use std::error::Error;
+use std::fmt;
+
+/// A locked crate whose latest release declares a `rust-version` above the target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MsrvConflict {
+    /// The crate name.
+    pub name: String,
+    /// The locked version.
+    pub locked: String,
+    /// Whether the locked version itself builds with the target toolchain.
+    pub locked_supported: bool,
+    /// The newest non-yanked release.
+    pub latest: String,
+    /// The `rust-version` declared by `latest`.
+    pub rust_version: String,
+    /// The newest non-yanked release that builds with the target toolchain (declaring
+    /// an older `rust-version` or none), if any; pre-releases only count when no
+    /// release exists.
+    pub newest_supported: Option<String>,
+}
+
+/// The result of [`msrv_report`]: which crates' latest releases need a newer Rust.
+#[derive(Debug)]
+pub struct MsrvReport {
+    /// The toolchain the project is pinned to.
+    pub target: Version,
+    /// The packages whose latest release needs a newer toolchain, in lockfile order.
+    pub conflicts: Vec<MsrvConflict>,
+    /// How many packages can update to their latest release on the target toolchain.
+    pub compatible: usize,
+    /// The crates whose versions couldn't be fetched, with the reason.
+    pub failed: Vec<(String, Box<dyn Error>)>,
+}
+
+impl fmt::Display for MsrvReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for conflict in &self.conflicts {
+            write!(f, "{:<24} {:<12} latest {} needs Rust {}", conflict.name, conflict.locked, conflict.latest, conflict.rust_version)?;
+            match &conflict.newest_supported {
+                Some(version) => write!(f, ", newest for {}: {}", self.target, version)?,
+                None => write!(f, ", no release supports {}", self.target)?,
+            }
+            if !conflict.locked_supported { write!(f, " (locked version unsupported too)")?; }
+            writeln!(f)?;
+        }
+        for (name, e) in &self.failed {
+            writeln!(f, "{:<24} error: {}", name, e)?;
+        }
+        writeln!(f, "{} of {} crates need a newer Rust than {} in their latest release", self.conflicts.len(), self.conflicts.len() + self.compatible, self.target)
+    }
+}
+
+/// Parses a `rust-version` (`"1.70"` or `"1.70.0"`), which may leave out the patch.
+fn rust_version(s: &str) -> Option<Version> {
+    let mut parts = s.trim().split('.').map(|part| part.parse::<u64>().ok());
+    let (major, minor, patch) = (parts.next()??, parts.next().unwrap_or(Some(0))?, parts.next().unwrap_or(Some(0))?);
+    if parts.next().is_some() { return None; }
+    Some(Version::new(major, minor, patch))
+}
+
+/// Whether `version` builds with `target`; versions not declaring a `rust-version` are assumed to.
+fn supports(version: &VersionInfo, target: &Version) -> bool {
+    version.rust_version.as_deref().and_then(rust_version).is_none_or(|needed| needed <= *target)
+}
+
+/// Checks every crates.io package of `lockfile` for a latest release that needs a
+/// newer Rust than `target`, the toolchain the project is pinned to.
+///
+/// A version without a `rust-version` is assumed to build with any toolchain; see
+/// [`msrv_report_with`] for a custom [`Config`].
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, msrv_report, Lockfile, Version};
+///
+/// let lockfile = Lockfile::from_path("Cargo.lock").expect("Failed to read lockfile");
+/// let report = block_on(msrv_report(&lockfile, &Version::new(1, 70, 0)));
+/// print!("{}", report);
+/// for conflict in &report.conflicts {
+///     if let Some(version) = &conflict.newest_supported {
+///         println!("{} = \"{}\"", conflict.name, version);
+///     }
+/// }
+/// ```
+pub async fn msrv_report(lockfile: &Lockfile, target: &Version) -> MsrvReport {
+    msrv_report_with(lockfile, target, crate::default_config()).await
+}
+
+/// Same as [`msrv_report`], but driven by a custom [`Config`]; requests run with
+/// [`Config::max_concurrency`] in flight.
+pub async fn msrv_report_with(lockfile: &Lockfile, target: &Version, config: &Config) -> MsrvReport {
+    let fetched = published_versions(lockfile, config).await;
+    let mut report = MsrvReport { target: target.clone(), conflicts: Vec::new(), compatible: 0, failed: Vec::new() };
+    for package in lockfile.crates_io() {
+        let versions = match versions_of(&fetched, &package.name) {
+            Ok(versions) => versions,
+            Err(_) if report.failed.iter().any(|(name, _)| *name == package.name) => continue,
+            Err(e) => {
+                report.failed.push((package.name.clone(), duplicate(e.as_ref())));
+                continue;
+            }
+        };
+        // Newest first, preferring releases over pre-releases
+        let mut available: Vec<(Version, &VersionInfo)> = versions.iter().filter(|version| !version.yanked)
+            .filter_map(|version| Some((Version::parse(&version.num)?, version)))
+            .collect();
+        available.sort_by(|(a, _), (b, _)| (!b.is_prerelease(), b).cmp(&(!a.is_prerelease(), a)));
+        let Some((newest, latest)) = available.first() else { continue };
+        if supports(latest, target) {
+            report.compatible += 1;
+            continue;
+        }
+        report.conflicts.push(MsrvConflict {
+            name: package.name.clone(),
+            locked: package.version.clone(),
+            locked_supported: versions.iter().find(|version| version.num == package.version).is_none_or(|locked| supports(locked, target)),
+            latest: latest.num.clone(),
+            rust_version: latest.rust_version.clone().unwrap_or_default(),
+            newest_supported: available.iter()
+                .find(|(parsed, version)| (!parsed.is_prerelease() || newest.is_prerelease()) && supports(version, target))
+                .map(|(_, version)| version.num.clone()),
+        });
+    }
+    report
+}
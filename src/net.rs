@@ -0,0 +1,142 @@
+use crate::Config;
+use std::io;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+#[cfg(feature = "local-address")]
+use std::net::IpAddr;
+use std::collections::HashMap;
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Delay between connection attempts recommended by RFC 8305 ("Happy Eyeballs v2").
+pub(crate) const DEFAULT_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// How long resolved addresses are reused when [`Config::dns_cache_ttl`] is not set.
+pub(crate) const DEFAULT_DNS_TTL: Duration = Duration::from_secs(60);
+
+/// Resolved addresses keyed by `(host, port)`, with the time they were resolved.
+type DnsCache = HashMap<(String, u16), (Instant, Vec<SocketAddr>)>;
+
+/// Process-wide cache of resolved addresses.
+static DNS_CACHE: OnceLock<Mutex<DnsCache>> = OnceLock::new();
+
+/// Resolves `host:port` through the configured [`Resolver`](crate::Resolver), or the system
+/// resolver with a cached answer younger than the configured TTL.
+///
+/// Batch fetches and watch mode thus don't hit the system resolver on every request.
+/// A TTL of zero disables caching.
+pub(crate) fn resolve(host: &str, port: u16, config: &Config) -> io::Result<Vec<SocketAddr>> {
+    if let Some(resolver) = &config.resolver { return resolver(host, port); }
+    let ttl = config.dns_ttl.unwrap_or(DEFAULT_DNS_TTL);
+    if ttl.is_zero() { return Ok((host, port).to_socket_addrs()?.collect()); }
+
+    let cache = DNS_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let key = (host.to_string(), port);
+    if let Some((resolved_at, addrs)) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(&key)
+        && resolved_at.elapsed() < ttl
+    {
+        return Ok(addrs.clone());
+    }
+    let addrs: Vec<SocketAddr> = (host, port).to_socket_addrs()?.collect();
+    cache.lock().unwrap_or_else(|e| e.into_inner()).insert(key, (Instant::now(), addrs.clone()));
+    Ok(addrs)
+}
+
+/// Splits an explicit port off `host` (`"registry.example.com:8443"`), falling back
+/// to `port`.
+pub(crate) fn split_port(host: &str, port: u16) -> (&str, u16) {
+    match host.rsplit_once(':') {
+        Some((name, explicit)) if !name.contains(':') => explicit.parse().map_or((host, port), |explicit| (name, explicit)),
+        _ => (host, port),
+    }
+}
+
+/// Opens a TCP connection to `host:port` using a Happy Eyeballs strategy.
+///
+/// All addresses are resolved up front and ordered to alternate between IPv6 and
+/// IPv4. Attempts are launched one after another, each given a short head start
+/// before the next begins; the first connection to succeed wins and the rest are
+/// dropped. Users on IPv6-only or broken dual-stack networks therefore connect as
+/// fast as their working address family allows.
+pub(crate) fn connect(host: &str, port: u16, config: &Config) -> io::Result<TcpStream> {
+    let mut addrs = resolve(host, port, config)?;
+    config.bind.retain_reachable(&mut addrs);
+    let addrs = interleave(addrs);
+    let delay = config.attempt_delay.unwrap_or(DEFAULT_ATTEMPT_DELAY);
+    match addrs.as_slice() {
+        [] => return Err(io::Error::new(io::ErrorKind::NotFound, format!("no addresses found for {}", host))),
+        [addr] => return config.bind.connect(*addr),
+        _ => {}
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut pending = 0;
+    let mut last_err = None;
+    for addr in addrs {
+        let (tx, bind) = (tx.clone(), config.bind.clone());
+        // Losing attempts fail to send once the receiver is gone and drop their stream
+        thread::spawn(move || { let _ = tx.send(bind.connect(addr)); });
+        pending += 1;
+        match rx.recv_timeout(delay) {
+            Ok(Ok(stream)) => return Ok(stream),
+            Ok(Err(e)) => { pending -= 1; last_err = Some(e); }
+            Err(_) => {} // Still connecting: give the next address a chance in parallel
+        }
+    }
+    while pending > 0 {
+        match rx.recv() {
+            Ok(Ok(stream)) => return Ok(stream),
+            Ok(Err(e)) => { pending -= 1; last_err = Some(e); }
+            Err(_) => break,
+        }
+    }
+    Err(last_err.unwrap_or_else(|| io::Error::other("connection failed")))
+}
+
+/// Where outgoing sockets are bound before connecting (multi-homed hosts, VPN split tunnels).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Bind {
+    #[cfg(feature = "local-address")]
+    pub(crate) address: Option<IpAddr>,
+    #[cfg(all(feature = "local-address", any(target_os = "android", target_os = "fuchsia", target_os = "linux")))]
+    pub(crate) interface: Option<String>,
+}
+
+impl Bind {
+    /// Drops addresses whose family can't be reached from the bound local address.
+    fn retain_reachable(&self, _addrs: &mut Vec<SocketAddr>) {
+        #[cfg(feature = "local-address")]
+        if let Some(local) = self.address { _addrs.retain(|addr| addr.is_ipv4() == local.is_ipv4()); }
+    }
+
+    /// Connects to `addr`, binding the socket to the configured local address/interface first.
+    #[cfg(feature = "local-address")]
+    fn connect(&self, addr: SocketAddr) -> io::Result<TcpStream> {
+        use socket2::{Domain, Protocol, Socket, Type};
+
+        let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+        if let Some(local) = self.address { socket.bind(&SocketAddr::new(local, 0).into())?; }
+        #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+        if let Some(interface) = &self.interface { socket.bind_device(Some(interface.as_bytes()))?; }
+        socket.connect(&addr.into())?;
+        Ok(socket.into())
+    }
+
+    #[cfg(not(feature = "local-address"))]
+    fn connect(&self, addr: SocketAddr) -> io::Result<TcpStream> {
+        TcpStream::connect(addr)
+    }
+}
+
+/// Orders addresses so that IPv6 and IPv4 alternate, starting with IPv6.
+fn interleave(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let (v6, v4): (Vec<_>, Vec<_>) = addrs.into_iter().partition(SocketAddr::is_ipv6);
+    let mut out = Vec::with_capacity(v6.len() + v4.len());
+    let (mut a, mut b) = (v6.into_iter(), v4.into_iter());
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return out,
+            (x, y) => out.extend(x.into_iter().chain(y)),
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+
+/// The output of a task (or its panic) and the waker of whoever awaits it.
+struct Slot<T> {
+    outcome: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// An owned permission to await the output of a spawned task.
+///
+/// `JoinHandle` is a future resolving to the task's output; if the task panicked,
+/// the panic resumes in the awaiting task. Dropping the handle detaches the task,
+/// which keeps running to completion.
+///
+/// # Example
+/// ```rust
+/// use crator::{block_on, spawn};
+///
+/// let handle = spawn(async { 6 * 7 });
+/// assert_eq!(block_on(handle), 42);
+/// ```
+#[must_use = "dropping a JoinHandle detaches the task"]
+pub struct JoinHandle<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+/// The sending half of a [`JoinHandle`], held by whatever runs the task.
+pub(crate) struct Completion<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> JoinHandle<T> {
+    pub(crate) fn new() -> (Self, Completion<T>) {
+        let slot = Arc::new(Mutex::new(Slot { outcome: None, waker: None }));
+        (JoinHandle { slot: Arc::clone(&slot) }, Completion { slot })
+    }
+
+    /// Returns `true` once the task has finished (or panicked).
+    pub fn is_finished(&self) -> bool {
+        lock(&self.slot).outcome.is_some()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut slot = lock(&self.slot);
+        match slot.outcome.take() {
+            Some(Ok(output)) => Poll::Ready(output),
+            // Surface a panic of the task in the awaiting task
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => { slot.waker = Some(cx.waker().clone()); Poll::Pending }
+        }
+    }
+}
+
+impl<T> Completion<T> {
+    /// Hands the task's outcome to its [`JoinHandle`], waking the awaiting task.
+    pub(crate) fn complete(self, outcome: thread::Result<T>) {
+        let mut slot = lock(&self.slot);
+        slot.outcome = Some(outcome);
+        if let Some(waker) = slot.waker.take() { drop(slot); waker.wake(); }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A spawned future, re-queued on its pool whenever it is woken.
+struct Task {
+    future: Mutex<Option<BoxFuture>>,
+    queued: AtomicBool,
+    pool: Arc<Shared>,
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // A task already waiting in the queue will see the wakeup when it is polled
+        if !self.queued.swap(true, Ordering::SeqCst) { self.pool.schedule(Arc::clone(self)); }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    queue: VecDeque<Arc<Task>>,
+    /// Spawned tasks that have not completed yet.
+    live: usize,
+    shutdown: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    work: Condvar,
+}
+
+impl Shared {
+    fn schedule(&self, task: Arc<Task>) {
+        lock(&self.state).queue.push_back(task);
+        self.work.notify_one();
+    }
+
+    fn run_worker(&self) {
+        loop {
+            let task = {
+                let mut state = lock(&self.state);
+                loop {
+                    if let Some(task) = state.queue.pop_front() { break task; }
+                    if state.shutdown && state.live == 0 { return; }
+                    state = self.work.wait(state).unwrap_or_else(|e| e.into_inner());
+                }
+            };
+            task.queued.store(false, Ordering::SeqCst);
+            let waker = Waker::from(Arc::clone(&task));
+            let mut future = lock(&task.future);
+            let Some(pending) = future.as_mut() else { continue };
+            if pending.as_mut().poll(&mut Context::from_waker(&waker)).is_ready() {
+                *future = None;
+                let mut state = lock(&self.state);
+                state.live -= 1;
+                // Let idle workers of a shut-down pool notice that the last task finished
+                if state.shutdown && state.live == 0 { self.work.notify_all(); }
+            }
+        }
+    }
+}
+
+/// A fixed-size pool of worker threads running spawned futures.
+///
+/// Each worker polls whichever task was woken next, so dozens of fetches can be
+/// fanned out across cores with crator alone. Dropping the pool waits for every
+/// spawned task to finish.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, crate_data, join_all, ThreadPool};
+///
+/// let pool = ThreadPool::new(4);
+/// let handles: Vec<_> = ["serde", "rand", "mathlab"].into_iter()
+///     .map(|name| pool.spawn(async move { (name, crate_data(name).await.map(|info| info.latest).map_err(|e| e.to_string())) }))
+///     .collect();
+/// for (name, latest) in block_on(join_all(handles)) {
+///     println!("{}: {:?}", name, latest);
+/// }
+/// ```
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    /// Starts a pool with `threads` workers (at least one).
+    pub fn new(threads: usize) -> Self {
+        let shared = Arc::new(Shared::default());
+        let workers = (0..threads.max(1))
+            .map(|i| {
+                let shared = Arc::clone(&shared);
+                thread::Builder::new()
+                    .name(format!("crator-worker-{}", i))
+                    .spawn(move || shared.run_worker())
+                    .expect("failed to spawn a worker thread")
+            })
+            .collect();
+        ThreadPool { shared, workers }
+    }
+
+    /// Runs `future` on the pool, returning a handle to await its output.
+    ///
+    /// A panic inside the future is caught and resumed wherever the handle is awaited.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (handle, completion) = JoinHandle::new();
+        let mut future = Box::pin(future);
+        let mut completion = Some(completion);
+        let task = Arc::new(Task {
+            future: Mutex::new(Some(Box::pin(std::future::poll_fn(move |cx| {
+                let outcome = match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+                    Ok(Poll::Pending) => return Poll::Pending,
+                    Ok(Poll::Ready(output)) => Ok(output),
+                    Err(payload) => Err(payload),
+                };
+                if let Some(completion) = completion.take() { completion.complete(outcome); }
+                Poll::Ready(())
+            })))),
+            queued: AtomicBool::new(true),
+            pool: Arc::clone(&self.shared),
+        });
+        lock(&self.shared.state).live += 1;
+        self.shared.schedule(task);
+        handle
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        lock(&self.shared.state).shutdown = true;
+        self.shared.work.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Runs `future` on a process-wide [`ThreadPool`] with one worker per available core.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, crate_data, spawn};
+///
+/// let handle = spawn(async { crate_data("serde").await.map(|info| info.latest).map_err(|e| e.to_string()) });
+/// println!("serde: {:?}", block_on(handle));
+/// ```
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    static POOL: OnceLock<ThreadPool> = OnceLock::new();
+    POOL.get_or_init(|| ThreadPool::new(thread::available_parallelism().map_or(4, |n| n.get())))
+        .spawn(future)
+}
@@ -0,0 +1,313 @@
+use crate::{http, join, ArrayIter, Config, CrateInfo, Json, Request};
+use std::error::Error;
+
+/// One published version of a crate, as listed in the `versions` array of the crate endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VersionInfo {
+    /// The version number (e.g., "1.0.210").
+    pub num: String,
+    /// Whether the version was withdrawn with `cargo yank`.
+    pub yanked: bool,
+    /// The software license of this version, if declared.
+    pub license: Option<String>,
+    /// ISO 8601 formatted publication timestamp.
+    pub created_at: String,
+    /// The number of downloads of this version.
+    pub downloads: u64,
+    /// The minimum supported Rust version (`rust-version`), if declared.
+    pub rust_version: Option<String>,
+    /// The size of the `.crate` archive in bytes, if known.
+    pub crate_size: Option<u64>,
+}
+
+/// Returns the raw top-level `versions` array of a crate response.
+///
+/// The crate object also has a `versions` key (a list of ids), so the array is
+/// looked up among the top-level entries rather than by a path search.
+pub(crate) fn versions_array(body: &str) -> Option<&str> {
+    Json::object_entries(body)?.into_iter().find(|(key, _)| *key == "versions").map(|(_, raw)| raw)
+}
+
+impl VersionInfo {
+    /// Parses the top-level `versions` array of a crate response, newest first.
+    fn parse_all(body: &str) -> Vec<Self> {
+        versions_array(body).and_then(ArrayIter::new).into_iter().flatten().map(|version| Self::parse(&version)).collect()
+    }
+
+    fn parse(raw: &str) -> Self {
+        let mut fields = Json::multi_extract(raw, &["num", "yanked", "license", "created_at", "downloads", "rust_version", "crate_size"]);
+        let mut take = |path: &str| fields.remove(path).filter(|value| value != "null");
+        VersionInfo {
+            num: take("num").unwrap_or_else(|| "N/A".to_string()),
+            yanked: take("yanked").is_some_and(|yanked| yanked == "true"),
+            license: take("license"),
+            created_at: take("created_at").unwrap_or_else(|| "N/A".to_string()),
+            downloads: take("downloads").and_then(|downloads| Json::parse_u64(&downloads)).unwrap_or(0),
+            rust_version: take("rust_version"),
+            crate_size: take("crate_size").and_then(|size| Json::parse_u64(&size)),
+        }
+    }
+}
+
+/// Whether a crate owner is an individual account or a GitHub team.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum OwnerKind {
+    /// A crates.io user account.
+    User,
+    /// A GitHub team (login `github:org:team`).
+    Team,
+}
+
+/// An owner of a crate, as returned by the crates.io owners endpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnerInfo {
+    /// The login name (e.g., "dtolnay" or "github:rust-lang:libs").
+    pub login: String,
+    /// Whether the owner is a user or a team.
+    pub kind: OwnerKind,
+    /// The display name, if set.
+    pub name: Option<String>,
+    /// The owner's profile URL (e.g., on GitHub), if known.
+    pub url: Option<String>,
+    /// The avatar image URL, if known.
+    pub avatar: Option<String>,
+}
+
+impl OwnerInfo {
+    /// Parses the `users` array of an owners response.
+    fn parse_all(body: &str) -> Vec<Self> {
+        Json::iter_array(body, "users").map(|owner| Self::parse(&owner)).collect()
+    }
+
+    fn parse(raw: &str) -> Self {
+        let mut fields = Json::multi_extract(raw, &["login", "kind", "name", "url", "avatar"]);
+        let mut take = |path: &str| fields.remove(path).filter(|value| value != "null");
+        OwnerInfo {
+            login: take("login").unwrap_or_default(),
+            kind: if take("kind").as_deref() == Some("team") { OwnerKind::Team } else { OwnerKind::User },
+            name: take("name"),
+            url: take("url"),
+            avatar: take("avatar"),
+        }
+    }
+}
+
+/// Fetches the owners (users and teams) of a crate.
+///
+/// # Errors
+/// A non-2xx response (e.g. an unknown crate) yields a [`FetchError::Status`](crate::FetchError::Status).
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, crate_owners, OwnerKind};
+///
+/// for owner in block_on(crate_owners("serde")).expect("Failed to fetch owners") {
+///     let kind = if owner.kind == OwnerKind::Team { "team" } else { "user" };
+///     println!("{} ({}): {}", owner.login, kind, owner.name.unwrap_or_default());
+/// }
+/// ```
+pub async fn crate_owners(crate_name: &str) -> Result<Vec<OwnerInfo>, Box<dyn Error>> {
+    crate_owners_with(crate_name, crate::default_config()).await
+}
+
+/// Same as [`crate_owners`], but driven by a custom [`Config`].
+pub async fn crate_owners_with(crate_name: &str, config: &Config) -> Result<Vec<OwnerInfo>, Box<dyn Error>> {
+    let (_, body) = get(&format!("/api/v1/crates/{}/owners", crate_name), config).await?;
+    Ok(OwnerInfo::parse_all(&body))
+}
+
+/// The kind of a dependency: a regular, dev-, or build-dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum DependencyKind {
+    /// Listed under `[dependencies]`.
+    Normal,
+    /// Listed under `[dev-dependencies]`.
+    Dev,
+    /// Listed under `[build-dependencies]`.
+    Build,
+}
+
+/// A dependency declared by one version of a crate, following crates.io's schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DependencyInfo {
+    /// The name of the depended-on crate on crates.io (e.g., "serde_derive").
+    pub name: String,
+    /// The version requirement (e.g., "^1.0").
+    pub req: String,
+    /// Whether it is a regular, dev-, or build-dependency.
+    pub kind: DependencyKind,
+    /// Whether the dependency is only enabled through a feature.
+    pub optional: bool,
+    /// Whether the dependency's default features are enabled.
+    pub default_features: bool,
+    /// The features explicitly enabled on the dependency.
+    pub features: Vec<String>,
+    /// The platform restriction (e.g., `cfg(windows)`), if any.
+    pub target: Option<String>,
+}
+
+impl DependencyInfo {
+    /// Parses the `dependencies` array of a version's dependencies response.
+    fn parse_all(body: &str) -> Vec<Self> {
+        Json::iter_array(body, "dependencies").map(|dependency| Self::parse(&dependency)).collect()
+    }
+
+    fn parse(raw: &str) -> Self {
+        let mut fields = Json::multi_extract(raw, &["crate_id", "req", "kind", "optional", "default_features", "target"]);
+        let mut take = |path: &str| fields.remove(path).filter(|value| value != "null");
+        DependencyInfo {
+            name: take("crate_id").unwrap_or_default(),
+            req: take("req").unwrap_or_else(|| "*".to_string()),
+            kind: match take("kind").as_deref() {
+                Some("dev") => DependencyKind::Dev,
+                Some("build") => DependencyKind::Build,
+                _ => DependencyKind::Normal,
+            },
+            optional: take("optional").is_some_and(|optional| optional == "true"),
+            default_features: take("default_features").is_none_or(|default| default == "true"),
+            features: Json::extract_array(raw, "features"),
+            target: take("target"),
+        }
+    }
+}
+
+/// Fetches the dependencies declared by one published version of a crate.
+///
+/// # Errors
+/// A non-2xx response (e.g. an unknown crate or version) yields a [`FetchError::Status`](crate::FetchError::Status).
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, crate_dependencies, DependencyKind};
+///
+/// let dependencies = block_on(crate_dependencies("serde", "1.0.210")).expect("Failed to fetch dependencies");
+/// for dependency in dependencies.iter().filter(|d| d.kind == DependencyKind::Normal) {
+///     let optional = if dependency.optional { " (optional)" } else { "" };
+///     println!("{} {}{}", dependency.name, dependency.req, optional);
+/// }
+/// ```
+pub async fn crate_dependencies(crate_name: &str, version: &str) -> Result<Vec<DependencyInfo>, Box<dyn Error>> {
+    crate_dependencies_with(crate_name, version, crate::default_config()).await
+}
+
+/// Same as [`crate_dependencies`], but driven by a custom [`Config`]; with a
+/// [`Config::sparse_index`], [`Config::local_registry`] or [`Config::directory_source`],
+/// the dependencies are read from there.
+pub async fn crate_dependencies_with(crate_name: &str, version: &str, config: &Config) -> Result<Vec<DependencyInfo>, Box<dyn Error>> {
+    if config.index_source.is_some() { return crate::index::source_dependencies(crate_name, version, config).await; }
+    let (_, body) = get(&format!("/api/v1/crates/{}/{}/dependencies", crate_name, version), config).await?;
+    Ok(DependencyInfo::parse_all(&body))
+}
+
+/// The composite result of a [`CrateQuery`]: the crate's metadata plus whichever
+/// extra sections were requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrateDetails {
+    /// The metadata returned by [`crate_data`](crate::crate_data).
+    pub info: CrateInfo,
+    /// Every published version, newest first; `None` unless requested with [`CrateQuery::with_versions`].
+    pub versions: Option<Vec<VersionInfo>>,
+    /// The crate's owners (users and teams); `None` unless requested with [`CrateQuery::with_owners`].
+    pub owners: Option<Vec<OwnerInfo>>,
+}
+
+/// A builder selecting which parts of a crate's data to retrieve.
+///
+/// Only the endpoints needed for the requested sections are called, and they are
+/// fetched concurrently: the version list comes with the crate metadata itself,
+/// while owners need a second request.
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, CrateQuery};
+///
+/// let details = block_on(CrateQuery::new("serde").with_versions().with_owners().fetch()).expect("Query failed");
+/// let owners: Vec<_> = details.owners.unwrap_or_default().into_iter().map(|owner| owner.login).collect();
+/// println!("serde v{} by {}", details.info.latest, owners.join(", "));
+/// for version in details.versions.unwrap_or_default().iter().filter(|v| !v.yanked).take(3) {
+///     println!("  {} ({} downloads)", version.num, version.downloads);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateQuery {
+    name: String,
+    versions: bool,
+    owners: bool,
+}
+
+impl CrateQuery {
+    /// Starts a query for the crate named `name`, retrieving only its [`CrateInfo`].
+    pub fn new(name: &str) -> Self {
+        CrateQuery { name: name.to_string(), versions: false, owners: false }
+    }
+
+    /// Also lists every published version ([`CrateDetails::versions`]).
+    pub fn with_versions(mut self) -> Self {
+        self.versions = true;
+        self
+    }
+
+    /// Also retrieves the crate's owners ([`CrateDetails::owners`]) from the owners endpoint.
+    pub fn with_owners(mut self) -> Self {
+        self.owners = true;
+        self
+    }
+
+    /// The name of the queried crate.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Runs the query with the process-wide default [`Config`].
+    ///
+    /// # Errors
+    /// Fails if any of the requested endpoints fails; a non-2xx response yields a
+    /// [`FetchError::Status`](crate::FetchError::Status).
+    pub async fn fetch(&self) -> Result<CrateDetails, Box<dyn Error>> {
+        self.fetch_with(crate::default_config()).await
+    }
+
+    /// Runs the query with a custom [`Config`].
+    ///
+    /// # Errors
+    /// Fails if any of the requested endpoints fails; a non-2xx response yields a
+    /// [`FetchError::Status`](crate::FetchError::Status).
+    pub async fn fetch_with(&self, config: &Config) -> Result<CrateDetails, Box<dyn Error>> {
+        let path = format!("/api/v1/crates/{}", self.name);
+        // Sendable errors, as `join` holds one output while awaiting the other
+        let metadata = async { get(&path, config).await.map_err(http::sendable) };
+        let owners = async {
+            if !self.owners { return Ok(None); }
+            get(&format!("{}/owners", path), config).await.map(Some).map_err(http::sendable)
+        };
+        let (metadata, owners) = join(metadata, owners).await;
+        let (head, body) = metadata.map_err(|e| e as Box<dyn Error>)?;
+
+        let versions = self.versions.then(|| VersionInfo::parse_all(&body));
+        Ok(CrateDetails {
+            info: CrateInfo::from_response(&head, body, config),
+            versions,
+            owners: owners.map_err(|e| e as Box<dyn Error>)?.map(|(_, body)| OwnerInfo::parse_all(&body)),
+        })
+    }
+}
+
+/// Every published version of a crate, newest first, from the web API or the
+/// configured index source.
+pub(crate) async fn crate_versions(crate_name: &str, config: &Config) -> Result<Vec<VersionInfo>, Box<dyn Error>> {
+    if config.index_source.is_some() { return crate::index::source_versions(crate_name, config).await; }
+    Ok(CrateQuery::new(crate_name).with_versions().fetch_with(config).await?.versions.unwrap_or_default())
+}
+
+/// Fetches a crates.io API path, failing on non-2xx responses.
+async fn get(path: &str, config: &Config) -> Result<(String, String), Box<dyn Error>> {
+    let (head, body) = http::fetch_api(&Request::new("GET", path), config).await?;
+    http::ensure_success(&head)?;
+    Ok((head, body))
+}
@@ -0,0 +1,57 @@
+use crate::timer;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A token bucket shared by clones of a [`Config`](crate::Config) with
+/// [`Config::rate_limit`](crate::Config::rate_limit) set.
+///
+/// Each request takes a token; tokens refill at a steady rate up to the burst size.
+/// A request finding the bucket empty reserves the next token anyway and waits for
+/// it, so waiting requests go out in the order they arrived.
+#[derive(Clone)]
+pub(crate) struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+struct Bucket {
+    /// Tokens available now; negative when requests are already waiting.
+    tokens: f64,
+    capacity: f64,
+    /// Tokens added per second.
+    rate: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Allows `requests` requests per `period`, in bursts of up to `requests`.
+    pub(crate) fn new(requests: u32, period: Duration) -> Self {
+        let capacity = f64::from(requests.max(1));
+        let bucket = Bucket { tokens: capacity, capacity, rate: capacity / period.as_secs_f64(), updated: Instant::now() };
+        RateLimiter { bucket: Arc::new(Mutex::new(bucket)) }
+    }
+
+    /// Takes a token and returns how long to wait before using it.
+    fn reserve(&self) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        bucket.tokens = (bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * bucket.rate).min(bucket.capacity);
+        bucket.updated = now;
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 { return Duration::ZERO; }
+        // A huge period makes the wait too long for a `Duration`
+        Duration::try_from_secs_f64(-bucket.tokens / bucket.rate).unwrap_or(Duration::MAX)
+    }
+
+    /// Waits for a token without blocking the executor thread.
+    pub(crate) async fn acquire(&self) {
+        let delay = self.reserve();
+        if !delay.is_zero() { timer::sleep(delay).await; }
+    }
+
+    /// Waits for a token on the current thread.
+    pub(crate) fn acquire_blocking(&self) {
+        let delay = self.reserve();
+        if !delay.is_zero() { thread::sleep(delay); }
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::os::raw::{c_int, c_short};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::net::UnixStream;
+use std::pin::Pin;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+#[repr(C)]
+struct PollFd {
+    fd: c_int,
+    events: c_short,
+    revents: c_short,
+}
+
+#[cfg(target_os = "linux")]
+type NFds = std::os::raw::c_ulong;
+#[cfg(not(target_os = "linux"))]
+type NFds = std::os::raw::c_uint;
+
+const POLLIN: c_short = 0x1;
+const POLLOUT: c_short = 0x4;
+const POLLERR: c_short = 0x8;
+const POLLHUP: c_short = 0x10;
+const POLLNVAL: c_short = 0x20;
+
+unsafe extern "C" {
+    fn poll(fds: *mut PollFd, nfds: NFds, timeout: c_int) -> c_int;
+}
+
+/// Blocks until one of `fds` is ready, filling in their `revents`.
+fn wait(fds: &mut [PollFd]) -> io::Result<()> {
+    // SAFETY: `fds` is a valid, exclusively borrowed array of `fds.len()` `pollfd` structs.
+    let ready = unsafe { poll(fds.as_mut_ptr(), fds.len() as NFds, -1) };
+    if ready < 0 { return Err(io::Error::last_os_error()); }
+    Ok(())
+}
+
+/// The tasks waiting for one socket.
+#[derive(Default)]
+struct Interest {
+    read: Option<Waker>,
+    write: Option<Waker>,
+}
+
+/// A minimal readiness reactor over `poll(2)`.
+///
+/// One background thread waits on every socket a task is blocked on and wakes the
+/// task once the socket becomes readable or writable, so fetches driven by a single
+/// executor thread overlap instead of running one after another.
+struct Reactor {
+    interests: Mutex<HashMap<RawFd, Interest>>,
+    /// Written to whenever `interests` changes, to interrupt a pending `poll`.
+    notify: UnixStream,
+}
+
+fn reactor() -> &'static Reactor {
+    static REACTOR: OnceLock<Reactor> = OnceLock::new();
+    REACTOR.get_or_init(|| {
+        let (notify, wakeup) = UnixStream::pair().expect("failed to create the reactor socket pair");
+        notify.set_nonblocking(true).expect("failed to configure the reactor socket pair");
+        wakeup.set_nonblocking(true).expect("failed to configure the reactor socket pair");
+        thread::Builder::new()
+            .name("crator-reactor".into())
+            .spawn(move || reactor().run(wakeup))
+            .expect("failed to spawn the reactor thread");
+        Reactor { interests: Mutex::new(HashMap::new()), notify }
+    })
+}
+
+impl Reactor {
+    fn lock(&self) -> MutexGuard<'_, HashMap<RawFd, Interest>> {
+        self.interests.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn run(&self, mut wakeup: UnixStream) {
+        let mut fds = Vec::new();
+        loop {
+            fds.clear();
+            fds.push(PollFd { fd: wakeup.as_raw_fd(), events: POLLIN, revents: 0 });
+            for (&fd, interest) in self.lock().iter() {
+                let events = if interest.read.is_some() { POLLIN } else { 0 } | if interest.write.is_some() { POLLOUT } else { 0 };
+                if events != 0 { fds.push(PollFd { fd, events, revents: 0 }); }
+            }
+            if wait(&mut fds).is_err() { continue; }
+            // Drain the notifications that interrupted this round
+            while wakeup.read(&mut [0; 64]).is_ok_and(|n| n > 0) {}
+
+            let mut ready = Vec::new();
+            let mut interests = self.lock();
+            for pollfd in &fds[1..] {
+                let Some(interest) = interests.get_mut(&pollfd.fd) else { continue };
+                // Errors and hang-ups wake both sides; the next I/O call reports them
+                if pollfd.revents & (POLLIN | POLLERR | POLLHUP | POLLNVAL) != 0 { ready.extend(interest.read.take()); }
+                if pollfd.revents & (POLLOUT | POLLERR | POLLHUP | POLLNVAL) != 0 { ready.extend(interest.write.take()); }
+            }
+            drop(interests);
+            ready.into_iter().for_each(Waker::wake);
+        }
+    }
+
+    fn register(&self, fd: RawFd, write: bool, waker: &Waker) {
+        let mut interests = self.lock();
+        let interest = interests.entry(fd).or_default();
+        let slot = if write { &mut interest.write } else { &mut interest.read };
+        match slot {
+            Some(current) => current.clone_from(waker),
+            None => *slot = Some(waker.clone()),
+        }
+        drop(interests);
+        // A full notification buffer already guarantees a pending wakeup
+        let _ = (&self.notify).write(&[1]);
+    }
+
+    fn deregister(&self, fd: RawFd) {
+        self.lock().remove(&fd);
+    }
+}
+
+/// A non-blocking socket whose readiness can be awaited.
+///
+/// The source polls its own duplicate of the socket, so the descriptor it is
+/// registered under cannot be reused by another socket before the source is dropped.
+pub(crate) struct Source {
+    socket: TcpStream,
+}
+
+impl Source {
+    pub(crate) fn new(socket: &TcpStream) -> io::Result<Self> {
+        Ok(Source { socket: socket.try_clone()? })
+    }
+
+    /// Resolves once the socket may be readable (or has failed).
+    pub(crate) fn readable(&self) -> Readiness {
+        Readiness { fd: self.socket.as_raw_fd(), write: false, registered: false }
+    }
+
+    /// Resolves once the socket may be writable (or has failed).
+    pub(crate) fn writable(&self) -> Readiness {
+        Readiness { fd: self.socket.as_raw_fd(), write: true, registered: false }
+    }
+}
+
+impl Drop for Source {
+    fn drop(&mut self) {
+        reactor().deregister(self.socket.as_raw_fd());
+    }
+}
+
+/// Future returned by [`Source::readable`] and [`Source::writable`].
+///
+/// Readiness is only a hint: the caller retries its I/O and awaits again on `WouldBlock`.
+#[must_use = "futures do nothing unless polled"]
+pub(crate) struct Readiness {
+    fd: RawFd,
+    write: bool,
+    registered: bool,
+}
+
+impl Future for Readiness {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.registered { return Poll::Ready(()); }
+        reactor().register(self.fd, self.write, cx.waker());
+        self.registered = true;
+        Poll::Pending
+    }
+}
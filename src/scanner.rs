@@ -0,0 +1,254 @@
+use crate::value::number_len;
+use crate::{Json, JsonError};
+
+/// An event pulled from a [`JsonScanner`].
+///
+/// Every event carries the dot-notation path of the value it belongs to, anchored
+/// at the document root (e.g. `crate.max_version` or `versions.0.num`); the root
+/// itself has an empty path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonEvent {
+    /// An object starts at `path`.
+    StartObject {
+        /// The path of the object.
+        path: String,
+    },
+    /// The object at `path` is complete.
+    EndObject {
+        /// The path of the object.
+        path: String,
+    },
+    /// An array starts at `path`.
+    StartArray {
+        /// The path of the array.
+        path: String,
+    },
+    /// The array at `path` is complete.
+    EndArray {
+        /// The path of the array.
+        path: String,
+    },
+    /// A scalar value, formatted like [`Json::extract`] (strings unquoted and unescaped).
+    Value {
+        /// The path of the value.
+        path: String,
+        /// The value itself.
+        value: String,
+    },
+}
+
+/// What the scanner expects next inside a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    Key,
+    Colon,
+    Value,
+    CommaOrEnd,
+}
+
+/// An open object or array, with the path segment of its current child.
+#[derive(Debug, Clone)]
+struct Frame {
+    is_object: bool,
+    key: String,
+    /// Position of the current child; also counts entries of objects.
+    index: usize,
+    expect: Expect,
+}
+
+impl Frame {
+    fn segment(&self) -> String {
+        if self.is_object { self.key.clone() } else { self.index.to_string() }
+    }
+}
+
+/// An incremental, pull-based JSON scanner.
+///
+/// Feed it the body as bytes arrive from the socket (e.g. from a
+/// [`fetch_stream`](crate::fetch_stream) callback) and pull [`JsonEvent`]s as soon
+/// as enough input is buffered. Values can thus be acted upon before the full
+/// response has arrived, and only the unconsumed tail of the input is kept in memory.
+///
+/// # Example
+/// ```rust
+/// use crator::{JsonEvent, JsonScanner};
+///
+/// let mut scanner = JsonScanner::new();
+/// let mut latest = None;
+/// // The body arrives in arbitrary chunks
+/// for chunk in [&br#"{"crate": {"name": "cra"#[..], br#"tor", "max_version": "1.2.0", "#, br#""downloads": 56000}}"#] {
+///     scanner.feed(chunk);
+///     while let Some(event) = scanner.next_event().expect("Malformed JSON") {
+///         if let JsonEvent::Value { path, value } = event
+///             && path == "crate.max_version"
+///         {
+///             latest = Some(value);
+///         }
+///     }
+/// }
+/// assert_eq!(latest.as_deref(), Some("1.2.0"));
+/// assert!(scanner.is_done());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JsonScanner {
+    buf: Vec<u8>,
+    pos: usize,
+    stack: Vec<Frame>,
+    finished: bool,
+    eof: bool,
+}
+
+impl JsonScanner {
+    /// Creates a scanner waiting for the first bytes of a document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends newly received bytes.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        // Drop what has already been consumed before growing the buffer
+        if self.pos > 0 {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Signals the end of the input, so a trailing top-level number or literal completes.
+    pub fn finish(&mut self) {
+        self.eof = true;
+    }
+
+    /// Returns `true` once the whole top-level value has been scanned.
+    pub fn is_done(&self) -> bool {
+        self.finished
+    }
+
+    /// Pulls the next event.
+    ///
+    /// Returns `Ok(None)` when more input is needed (or the document is complete).
+    ///
+    /// # Errors
+    /// Returns [`JsonError::Malformed`] with the path where scanning stopped if the
+    /// input is not valid JSON.
+    pub fn next_event(&mut self) -> Result<Option<JsonEvent>, JsonError> {
+        loop {
+            if self.finished { return Ok(None); }
+            self.skip_whitespace();
+            let Some(&byte) = self.buf.get(self.pos) else { return Ok(None) };
+            let expect = self.stack.last().map_or(Expect::Value, |frame| frame.expect);
+            match (expect, byte) {
+                (Expect::Key, b'"') => {
+                    let Some(key) = self.string()? else { return Ok(None) };
+                    let frame = self.stack.last_mut().expect("key outside of an object");
+                    frame.key = key;
+                    frame.expect = Expect::Colon;
+                }
+                (Expect::Colon, b':') => {
+                    self.pos += 1;
+                    self.set_expect(Expect::Value);
+                }
+                (Expect::CommaOrEnd, b',') => {
+                    self.pos += 1;
+                    let frame = self.stack.last_mut().expect("comma outside of a container");
+                    frame.index += 1;
+                    frame.expect = if frame.is_object { Expect::Key } else { Expect::Value };
+                }
+                (Expect::Key | Expect::Value | Expect::CommaOrEnd, b'}' | b']') => {
+                    let is_object = byte == b'}';
+                    let Some(frame) = self.stack.last() else { return Err(self.malformed()) };
+                    // Only an empty container may close right after opening (no trailing commas)
+                    let opening = if is_object { Expect::Key } else { Expect::Value };
+                    let closable = expect == Expect::CommaOrEnd || (expect == opening && frame.index == 0);
+                    if frame.is_object != is_object || !closable { return Err(self.malformed()); }
+                    self.pos += 1;
+                    self.stack.pop();
+                    let path = self.path();
+                    self.value_done();
+                    return Ok(Some(if is_object { JsonEvent::EndObject { path } } else { JsonEvent::EndArray { path } }));
+                }
+                (Expect::Value, _) => return self.value(byte),
+                _ => return Err(self.malformed()),
+            }
+        }
+    }
+
+    /// Scans the value starting with `byte`.
+    fn value(&mut self, byte: u8) -> Result<Option<JsonEvent>, JsonError> {
+        let path = self.path();
+        match byte {
+            b'{' | b'[' => {
+                self.pos += 1;
+                let is_object = byte == b'{';
+                let expect = if is_object { Expect::Key } else { Expect::Value };
+                self.set_expect(Expect::CommaOrEnd);
+                self.stack.push(Frame { is_object, key: String::new(), index: 0, expect });
+                Ok(Some(if is_object { JsonEvent::StartObject { path } } else { JsonEvent::StartArray { path } }))
+            }
+            b'"' => {
+                let Some(value) = self.string()? else { return Ok(None) };
+                self.value_done();
+                Ok(Some(JsonEvent::Value { path, value }))
+            }
+            _ => {
+                let rest = &self.buf[self.pos..];
+                let Some(len) = rest.iter().position(|b| matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r'))
+                    .or(self.eof.then_some(rest.len()))
+                else {
+                    return Ok(None);
+                };
+                let value = std::str::from_utf8(&rest[..len]).map_err(|_| self.malformed())?.to_string();
+                let is_number = number_len(value.as_bytes()) == Some(value.len());
+                if !is_number && !matches!(value.as_str(), "true" | "false" | "null") {
+                    return Err(self.malformed());
+                }
+                self.pos += len;
+                self.value_done();
+                Ok(Some(JsonEvent::Value { path, value }))
+            }
+        }
+    }
+
+    /// Reads a complete string at the cursor, or `None` if its closing quote hasn't arrived yet.
+    fn string(&mut self) -> Result<Option<String>, JsonError> {
+        let content = &self.buf[self.pos + 1..];
+        let mut escaped = false;
+        let end = content.iter().position(|&b| match b {
+            _ if escaped => { escaped = false; false }
+            b'\\' => { escaped = true; false }
+            b => b == b'"',
+        });
+        let Some(end) = end else { return Ok(None) };
+        let raw = std::str::from_utf8(&content[..end]).map_err(|_| self.malformed())?;
+        let string = Json::unescape(raw).into_owned();
+        self.pos += end + 2;
+        Ok(Some(string))
+    }
+
+    /// Records that a complete value was read at the current position.
+    fn value_done(&mut self) {
+        match self.stack.last_mut() {
+            Some(frame) => frame.expect = Expect::CommaOrEnd,
+            None => self.finished = true,
+        }
+    }
+
+    fn set_expect(&mut self, expect: Expect) {
+        if let Some(frame) = self.stack.last_mut() { frame.expect = expect; }
+    }
+
+    /// The path of the value at the cursor.
+    fn path(&self) -> String {
+        self.stack.iter().map(Frame::segment).collect::<Vec<_>>().join(".")
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.buf.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn malformed(&self) -> JsonError {
+        JsonError::Malformed { path: self.path() }
+    }
+}
@@ -0,0 +1,160 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Pending deadlines, ordered by time, and the wakers to notify when they pass.
+#[derive(Default)]
+struct Timers {
+    deadlines: BinaryHeap<Reverse<(Instant, u64)>>,
+    wakers: HashMap<u64, Waker>,
+    next_id: u64,
+}
+
+/// The process-wide timer: a heap of deadlines served by one background thread.
+struct TimerThread {
+    timers: Mutex<Timers>,
+    changed: Condvar,
+}
+
+fn timer() -> &'static TimerThread {
+    static TIMER: OnceLock<TimerThread> = OnceLock::new();
+    TIMER.get_or_init(|| {
+        thread::Builder::new()
+            .name("crator-timer".into())
+            .spawn(|| timer().run())
+            .expect("failed to spawn the timer thread");
+        TimerThread { timers: Mutex::new(Timers::default()), changed: Condvar::new() }
+    })
+}
+
+impl TimerThread {
+    fn lock(&self) -> MutexGuard<'_, Timers> {
+        self.timers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wakes every task whose deadline passed, then sleeps until the next deadline.
+    fn run(&self) {
+        let mut timers = self.lock();
+        loop {
+            let now = Instant::now();
+            let mut due = Vec::new();
+            while let Some(&Reverse((deadline, id))) = timers.deadlines.peek() {
+                if deadline > now { break; }
+                timers.deadlines.pop();
+                // Cancelled timers have no waker left
+                due.extend(timers.wakers.remove(&id));
+            }
+            if !due.is_empty() {
+                drop(timers);
+                due.into_iter().for_each(Waker::wake);
+                timers = self.lock();
+                continue;
+            }
+            timers = match timers.deadlines.peek() {
+                Some(&Reverse((deadline, _))) => self.changed.wait_timeout(timers, deadline - now).unwrap_or_else(|e| e.into_inner()).0,
+                None => self.changed.wait(timers).unwrap_or_else(|e| e.into_inner()),
+            };
+        }
+    }
+
+    fn register(&self, deadline: Instant, waker: Waker) -> u64 {
+        let mut timers = self.lock();
+        let id = timers.next_id;
+        timers.next_id += 1;
+        timers.deadlines.push(Reverse((deadline, id)));
+        timers.wakers.insert(id, waker);
+        self.changed.notify_one();
+        id
+    }
+
+    /// Replaces the waker of a pending timer; returns `false` if it already fired.
+    fn update(&self, id: u64, waker: &Waker) -> bool {
+        match self.lock().wakers.get_mut(&id) {
+            Some(current) => { current.clone_from(waker); true }
+            None => false,
+        }
+    }
+
+    fn cancel(&self, id: u64) {
+        self.lock().wakers.remove(&id);
+    }
+}
+
+/// Future returned by [`sleep`]; completes once its deadline has passed.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Sleep {
+    deadline: Instant,
+    id: Option<u64>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            if let Some(id) = self.id.take() { timer().cancel(id); }
+            return Poll::Ready(());
+        }
+        match self.id {
+            Some(id) if timer().update(id, cx.waker()) => {}
+            _ => self.id = Some(timer().register(self.deadline, cx.waker().clone())),
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(id) = self.id { timer().cancel(id); }
+    }
+}
+
+/// Waits for `duration` without blocking the thread.
+///
+/// Deadlines are kept in a sorted heap served by one background timer thread,
+/// which wakes the task when its deadline passes. Unlike [`std::thread::sleep`],
+/// other futures driven by the same executor (e.g. inside [`join_all`](crate::join_all))
+/// keep making progress in the meantime, which suits watch-mode polling and backoff delays.
+///
+/// # Example
+/// ```rust
+/// use crator::{block_on, join, sleep, Instant};
+/// use std::time::Duration;
+///
+/// let start = Instant::now();
+/// block_on(join(sleep(Duration::from_millis(50)), sleep(Duration::from_millis(50))));
+/// // The sleeps run concurrently: about 50ms in total, not 100ms
+/// let elapsed = start.elapsed();
+/// assert!(elapsed >= Duration::from_millis(50));
+/// ```
+///
+/// ```rust,no_run
+/// use crator::{block_on, crate_data, sleep};
+/// use std::time::Duration;
+///
+/// // Watch mode: report new releases every five minutes
+/// block_on(async {
+///     loop {
+///         match crate_data("serde").await {
+///             Ok(info) => println!("serde v{}", info.latest),
+///             Err(e) => eprintln!("❌ Error: {}", e),
+///         }
+///         sleep(Duration::from_secs(300)).await;
+///     }
+/// });
+/// ```
+pub fn sleep(duration: Duration) -> Sleep {
+    // Deadlines past what `Instant` can hold (e.g. `Duration::MAX`) are cut to decades
+    let now = Instant::now();
+    let deadline = now.checked_add(duration).unwrap_or_else(|| now + FAR_FUTURE);
+    Sleep { deadline, id: None }
+}
+
+/// Stands in for sleeps too long for an [`Instant`] deadline.
+const FAR_FUTURE: Duration = Duration::from_secs(30 * 365 * 86_400);
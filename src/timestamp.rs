@@ -0,0 +1,139 @@
+use crate::http::{civil_from_days, days_from_civil};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A point in time parsed from an ISO 8601 / RFC 3339 string, normalized to UTC.
+///
+/// crates.io returns timestamps such as `created_at` as
+/// `"2024-01-15T10:20:30.123456+00:00"`; this type exposes them as Unix seconds plus
+/// calendar components without pulling in a date library. Timestamps order chronologically.
+///
+/// # Example
+/// ```rust
+/// use crator::Timestamp;
+///
+/// let ts = Timestamp::parse("2024-01-15T12:20:30.5+02:00").unwrap();
+/// assert_eq!((ts.year, ts.month, ts.day, ts.hour, ts.minute, ts.second), (2024, 1, 15, 10, 20, 30));
+/// assert_eq!(ts.unix, 1_705_314_030);
+/// assert_eq!(ts.nanos, 500_000_000);
+/// assert_eq!(ts.to_string(), "2024-01-15T10:20:30Z");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timestamp {
+    /// Seconds since the Unix epoch (negative before 1970).
+    pub unix: i64,
+    /// Sub-second part in nanoseconds.
+    pub nanos: u32,
+    /// Calendar year (UTC).
+    pub year: i32,
+    /// Month of the year, `1..=12` (UTC).
+    pub month: u8,
+    /// Day of the month, `1..=31` (UTC).
+    pub day: u8,
+    /// Hour of the day, `0..=23` (UTC).
+    pub hour: u8,
+    /// Minute of the hour, `0..=59`.
+    pub minute: u8,
+    /// Second of the minute, `0..=59`.
+    pub second: u8,
+}
+
+impl Timestamp {
+    /// Builds a timestamp from Unix seconds and nanoseconds.
+    pub fn from_unix(unix: i64, nanos: u32) -> Self {
+        let (year, month, day) = civil_from_days(unix.div_euclid(86_400));
+        let secs = unix.rem_euclid(86_400);
+        Timestamp {
+            unix,
+            nanos,
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+            hour: (secs / 3600) as u8,
+            minute: (secs / 60 % 60) as u8,
+            second: (secs % 60) as u8,
+        }
+    }
+
+    /// Parses `YYYY-MM-DD[(T| )HH:MM:SS[.fraction]][Z|±HH:MM]`.
+    ///
+    /// A missing offset is taken as UTC and a missing time as midnight. Returns
+    /// `None` for anything else, including dates that don't exist and years past
+    /// 9999, the RFC 3339 range.
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::Timestamp;
+    ///
+    /// assert!(Timestamp::parse("2024-02-29").is_some());
+    /// assert_eq!(Timestamp::parse("2024-02-31"), None);
+    /// assert_eq!(Timestamp::parse("2023-02-29"), None);
+    /// assert_eq!(Timestamp::parse("3000000000-01-01"), None);
+    /// assert_eq!(Timestamp::parse("2024-01-15T10:20:30+0é"), None);
+    /// ```
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let number = |part: &str| -> Option<u32> {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) { return None; }
+            part.parse().ok()
+        };
+        let (date, time) = match s.find(['T', 't', ' ']) {
+            Some(idx) => (&s[..idx], &s[idx + 1..]),
+            None => (s, ""),
+        };
+        let mut fields = date.split('-');
+        let (year, month, day) = (number(fields.next()?)?, number(fields.next()?)?, number(fields.next()?)?);
+        if fields.next().is_some() || year > 9999 || !(1..=12).contains(&month) || !(1..=31).contains(&day) { return None; }
+
+        let (mut clock, mut nanos, mut offset) = (0i64, 0u32, 0i64);
+        if !time.is_empty() {
+            let (time, zone) = match time.find(['Z', 'z', '+', '-']) {
+                Some(idx) => time.split_at(idx),
+                None => (time, ""),
+            };
+            let (hms, fraction) = time.split_once('.').unwrap_or((time, ""));
+            let mut parts = hms.split(':');
+            let (hour, minute, second) = (number(parts.next()?)?, number(parts.next()?)?, number(parts.next()?)?);
+            if parts.next().is_some() || hour > 23 || minute > 59 || second > 60 { return None; }
+            clock = i64::from(hour * 3600 + minute * 60 + second.min(59));
+            if !fraction.is_empty() {
+                number(fraction)?;
+                // Keep nanosecond precision, padding or cutting the fraction to nine digits
+                let digits: String = fraction.chars().chain(std::iter::repeat('0')).take(9).collect();
+                nanos = digits.parse().ok()?;
+            }
+            offset = match zone {
+                "" | "Z" | "z" => 0,
+                _ => {
+                    let sign = if zone.starts_with('-') { -1 } else { 1 };
+                    let (h, m) = zone[1..].split_once(':').unwrap_or((zone.get(1..3)?, zone.get(3..)?));
+                    let (h, m) = (number(h)?, number(m)?);
+                    if h > 23 || m > 59 { return None; }
+                    sign * i64::from(h * 3600 + m * 60)
+                }
+            };
+        }
+        let days = days_from_civil(i64::from(year), month, day);
+        // Days past the end of the month (e.g. February 31) would roll over
+        if civil_from_days(days) != (i64::from(year), month, day) { return None; }
+        Some(Self::from_unix(days * 86_400 + clock - offset, nanos))
+    }
+
+    /// Converts the timestamp into a [`SystemTime`].
+    pub fn to_system_time(&self) -> SystemTime {
+        let nanos = Duration::from_nanos(u64::from(self.nanos));
+        if self.unix >= 0 {
+            UNIX_EPOCH + Duration::from_secs(self.unix as u64) + nanos
+        } else {
+            UNIX_EPOCH - Duration::from_secs(self.unix.unsigned_abs()) + nanos
+        }
+    }
+}
+
+/// Formats the timestamp as RFC 3339 in UTC, e.g. `2024-01-15T10:20:30Z`.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", self.year, self.month, self.day, self.hour, self.minute, self.second)
+    }
+}
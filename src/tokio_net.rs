@@ -0,0 +1,34 @@
+use crate::http::{check_buffered, request_head};
+use crate::{net, spawn_blocking, Config, FetchError, Request};
+use std::error::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_native_tls::TlsConnector;
+
+/// Sends `request` over a `tokio::net::TcpStream` and returns the raw response.
+///
+/// Must be awaited inside a tokio runtime. The response is read until the server
+/// closes the connection (`Connection: close`).
+pub(crate) async fn exchange(host: &str, request: &Request, config: &Config) -> Result<Vec<u8>, Box<dyn Error>> {
+    let connector = TlsConnector::from(config.tls_connector()?);
+    // Connecting honors the resolver, DNS cache, and local address of `config`; it blocks, so it runs on a helper thread
+    let (name, port) = net::split_port(host, 443);
+    let stream = {
+        let (name, config) = (name.to_string(), config.clone());
+        spawn_blocking(move || net::connect(&name, port, &config)).await?
+    };
+    let registration = config.abort.as_ref().map(|handle| handle.register(&stream)).transpose()?;
+    stream.set_nonblocking(true)?;
+    let mut tls_stream = connector.connect(name, tokio::net::TcpStream::from_std(stream)?).await?;
+    tls_stream.write_all(request_head(host, request, config).as_bytes()).await?;
+
+    let (mut response, mut buf) = (Vec::new(), [0u8; 8192]);
+    loop {
+        let n = tls_stream.read(&mut buf).await?;
+        if n == 0 { break; }
+        response.extend_from_slice(&buf[..n]);
+        check_buffered(response.len(), config)?;
+    }
+    // An abort shuts the socket down, which reads like a regular (truncated) end of the body
+    if registration.is_some_and(|registration| registration.is_aborted()) { return Err(Box::new(FetchError::Aborted)); }
+    Ok(response)
+}
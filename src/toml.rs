@@ -0,0 +1,352 @@
+use crate::{TomlError, Value};
+
+/// Nesting depth of arrays and inline tables at which [`Toml::parse`] gives up.
+const MAX_DEPTH: usize = 128;
+
+/// A minimal TOML reader, enough for `Cargo.toml` and `Cargo.lock`.
+///
+/// Documents are parsed into the same [`Value`] tree as JSON: tables become objects
+/// (keys in document order), arrays of tables become arrays of objects, and integers
+/// and floats become numbers. Dates and times are kept as strings. It doesn't check
+/// every rule of the specification (e.g. redefining a table is accepted), but
+/// rejects what it can't read rather than guessing.
+///
+/// # Example
+/// ```rust
+/// use crator::{Toml, Value};
+///
+/// let manifest = r#"
+/// [package]
+/// name = "demo"
+/// version = "0.1.0"
+///
+/// [dependencies]
+/// serde = { version = "1.0", features = ["derive"] }
+/// rand = "0.8"
+///
+/// [[bin]]
+/// name = "demo-cli"
+/// "#;
+/// let value = Toml::parse(manifest).expect("Invalid TOML");
+///
+/// assert_eq!(value.at("package.name").and_then(Value::as_str), Some("demo"));
+/// assert_eq!(value.at("dependencies.serde.features.0").and_then(Value::as_str), Some("derive"));
+/// assert_eq!(value.at("dependencies.rand").and_then(Value::as_str), Some("0.8"));
+/// assert_eq!(value.at("bin.0.name").and_then(Value::as_str), Some("demo-cli"));
+/// ```
+pub struct Toml;
+
+impl Toml {
+    /// Parses a TOML document into a [`Value::Object`].
+    ///
+    /// # Errors
+    /// Returns a [`TomlError`] with the line of the first construct that can't be read.
+    pub fn parse(text: &str) -> Result<Value, TomlError> {
+        let mut parser = Parser { chars: text.chars().collect(), pos: 0 };
+        let mut root = Vec::new();
+        // The key path of the table that key/value lines currently go to
+        let mut current: Vec<String> = Vec::new();
+        loop {
+            parser.skip_blank_lines();
+            let Some(c) = parser.peek() else { break };
+            if c == '[' {
+                let array = parser.peek_at(1) == Some('[');
+                parser.pos += if array { 2 } else { 1 };
+                parser.skip_spaces();
+                let path = parser.key()?;
+                parser.skip_spaces();
+                if !parser.eat(']') || (array && !parser.eat(']')) { return Err(parser.error("expected `]` after table name")); }
+                let (last, parents) = path.split_last().expect("keys have at least one part");
+                let parent = table_mut(&mut root, parents).ok_or_else(|| parser.error("table name conflicts with a value"))?;
+                if array {
+                    match entry_mut(parent, last) {
+                        Some(Value::Array(tables)) => tables.push(Value::Object(Vec::new())),
+                        Some(_) => return Err(parser.error("table name conflicts with a value")),
+                        None => parent.push((last.clone(), Value::Array(vec![Value::Object(Vec::new())]))),
+                    }
+                } else {
+                    table_mut(parent, std::slice::from_ref(last)).ok_or_else(|| parser.error("table name conflicts with a value"))?;
+                }
+                current = path;
+            } else {
+                let path = parser.key()?;
+                parser.skip_spaces();
+                if !parser.eat('=') { return Err(parser.error("expected `=` after key")); }
+                parser.skip_spaces();
+                let value = parser.value(0)?;
+                let table = table_mut(&mut root, &current).expect("the current table exists");
+                insert(table, &path, value).map_err(|reason| parser.error(reason))?;
+            }
+            parser.end_of_line()?;
+        }
+        Ok(Value::Object(root))
+    }
+}
+
+/// Returns the existing entry for `key`.
+fn entry_mut<'a>(table: &'a mut [(String, Value)], key: &str) -> Option<&'a mut Value> {
+    table.iter_mut().find(|(k, _)| k == key).map(|(_, value)| value)
+}
+
+/// Walks (creating as needed) the tables along `path`; the last element of an array
+/// of tables stands for the array. `None` if a plain value is in the way.
+fn table_mut<'a>(table: &'a mut Vec<(String, Value)>, path: &[String]) -> Option<&'a mut Vec<(String, Value)>> {
+    let Some((first, rest)) = path.split_first() else { return Some(table) };
+    if entry_mut(table, first).is_none() { table.push((first.clone(), Value::Object(Vec::new()))); }
+    let next = match entry_mut(table, first)? {
+        Value::Object(entries) => entries,
+        Value::Array(tables) => match tables.last_mut()? {
+            Value::Object(entries) => entries,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    table_mut(next, rest)
+}
+
+/// Inserts a (possibly dotted) key into `table`.
+fn insert(table: &mut Vec<(String, Value)>, path: &[String], value: Value) -> Result<(), &'static str> {
+    let (last, parents) = path.split_last().expect("keys have at least one part");
+    let table = table_mut(table, parents).ok_or("dotted key conflicts with a value")?;
+    if entry_mut(table, last).is_some() { return Err("duplicate key"); }
+    table.push((last.clone(), value));
+    Ok(())
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() != Some(c) { return false; }
+        self.pos += 1;
+        true
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars().enumerate().all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    fn error(&self, reason: &'static str) -> TomlError {
+        let line = 1 + self.chars[..self.pos.min(self.chars.len())].iter().filter(|&&c| c == '\n').count();
+        TomlError { line, reason }
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) { self.pos += 1; }
+    }
+
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while self.peek().is_some_and(|c| c != '\n') { self.pos += 1; }
+        }
+    }
+
+    /// Skips whitespace, newlines and comments.
+    fn skip_blank_lines(&mut self) {
+        loop {
+            self.skip_spaces();
+            self.skip_comment();
+            if self.starts_with("\r\n") { self.pos += 1; }
+            if !self.eat('\n') { return; }
+        }
+    }
+
+    fn end_of_line(&mut self) -> Result<(), TomlError> {
+        self.skip_spaces();
+        self.skip_comment();
+        if self.starts_with("\r\n") { self.pos += 1; }
+        if self.peek().is_none() || self.eat('\n') { return Ok(()); }
+        Err(self.error("expected a new line after the value"))
+    }
+
+    /// Reads a key, splitting dotted keys into their parts.
+    fn key(&mut self) -> Result<Vec<String>, TomlError> {
+        let mut parts = Vec::new();
+        loop {
+            let part = match self.peek() {
+                Some('"') => { self.pos += 1; self.basic_string()? }
+                Some('\'') => { self.pos += 1; self.literal_string()? }
+                _ => {
+                    let start = self.pos;
+                    while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') { self.pos += 1; }
+                    if start == self.pos { return Err(self.error("expected a key")); }
+                    self.chars[start..self.pos].iter().collect()
+                }
+            };
+            parts.push(part);
+            self.skip_spaces();
+            if !self.eat('.') { return Ok(parts); }
+            self.skip_spaces();
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, TomlError> {
+        if depth > MAX_DEPTH { return Err(self.error("nested too deeply")); }
+        match self.peek() {
+            Some('"') if self.starts_with("\"\"\"") => { self.pos += 3; self.multiline_string('"').map(Value::String) }
+            Some('"') => { self.pos += 1; self.basic_string().map(Value::String) }
+            Some('\'') if self.starts_with("'''") => { self.pos += 3; self.multiline_string('\'').map(Value::String) }
+            Some('\'') => { self.pos += 1; self.literal_string().map(Value::String) }
+            Some('[') => { self.pos += 1; self.array(depth) }
+            Some('{') => { self.pos += 1; self.inline_table(depth) }
+            Some(_) => self.scalar(),
+            None => Err(self.error("expected a value")),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, TomlError> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank_lines();
+            if self.eat(']') { return Ok(Value::Array(items)); }
+            items.push(self.value(depth + 1)?);
+            self.skip_blank_lines();
+            if self.eat(']') { return Ok(Value::Array(items)); }
+            if !self.eat(',') { return Err(self.error("expected `,` or `]` in array")); }
+        }
+    }
+
+    fn inline_table(&mut self, depth: usize) -> Result<Value, TomlError> {
+        let mut entries = Vec::new();
+        self.skip_spaces();
+        if self.eat('}') { return Ok(Value::Object(entries)); }
+        loop {
+            self.skip_spaces();
+            let path = self.key()?;
+            if !self.eat('=') { return Err(self.error("expected `=` after key")); }
+            self.skip_spaces();
+            let value = self.value(depth + 1)?;
+            insert(&mut entries, &path, value).map_err(|reason| self.error(reason))?;
+            self.skip_spaces();
+            if self.eat('}') { return Ok(Value::Object(entries)); }
+            if !self.eat(',') { return Err(self.error("expected `,` or `}` in inline table")); }
+        }
+    }
+
+    /// Reads a `"..."` string after its opening quote.
+    fn basic_string(&mut self) -> Result<String, TomlError> {
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => { self.pos += 1; return Ok(out); }
+                Some('\\') => { self.pos += 1; out.push(self.escape()?); }
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => { self.pos += 1; out.push(c); }
+            }
+        }
+    }
+
+    /// Reads a `'...'` string after its opening quote.
+    fn literal_string(&mut self) -> Result<String, TomlError> {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some('\'') => { self.pos += 1; return Ok(self.chars[start..self.pos - 1].iter().collect()); }
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+
+    /// Reads a `"""` or `'''` string after its opening delimiter.
+    fn multiline_string(&mut self, quote: char) -> Result<String, TomlError> {
+        // A newline right after the opening delimiter is trimmed
+        if self.starts_with("\r\n") { self.pos += 1; }
+        self.eat('\n');
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote && self.peek_at(1) == Some(quote) && self.peek_at(2) == Some(quote) => {
+                    self.pos += 3;
+                    // Up to two more quotes belong to the content (`""""` ends with one quote inside)
+                    for _ in 0..2 {
+                        if self.peek() == Some(quote) { self.pos += 1; out.push(quote); }
+                    }
+                    return Ok(out);
+                }
+                Some('\\') if quote == '"' => {
+                    self.pos += 1;
+                    let rest = self.pos;
+                    self.skip_spaces();
+                    if matches!(self.peek(), Some('\n' | '\r')) {
+                        // A line-ending backslash trims the newline and leading whitespace
+                        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) { self.pos += 1; }
+                    } else {
+                        self.pos = rest;
+                        out.push(self.escape()?);
+                    }
+                }
+                Some(c) => { self.pos += 1; out.push(c); }
+            }
+        }
+    }
+
+    /// Decodes an escape sequence after its backslash.
+    fn escape(&mut self) -> Result<char, TomlError> {
+        let c = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+        self.pos += 1;
+        let hex_digits = match c {
+            'b' => return Ok('\u{8}'),
+            't' => return Ok('\t'),
+            'n' => return Ok('\n'),
+            'f' => return Ok('\u{c}'),
+            'r' => return Ok('\r'),
+            'e' => return Ok('\u{1b}'),
+            '"' => return Ok('"'),
+            '\\' => return Ok('\\'),
+            'u' => 4,
+            'U' => 8,
+            _ => return Err(self.error("invalid escape sequence")),
+        };
+        let digits: String = self.chars.get(self.pos..self.pos + hex_digits).unwrap_or_default().iter().collect();
+        self.pos += hex_digits;
+        u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    /// Reads a boolean, number, or date/time (kept as a string).
+    fn scalar(&mut self) -> Result<Value, TomlError> {
+        let start = self.pos;
+        // Local date-times may contain one space between the date and the time
+        while let Some(c) = self.peek() {
+            let date_time_space = c == ' ' && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) && self.pos > start
+                && self.chars[start..self.pos].iter().filter(|&&c| c == '-').count() == 2;
+            if !(c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '_' | '.' | ':') || date_time_space) { break; }
+            self.pos += 1;
+        }
+        let token: String = self.chars[start..self.pos].iter().collect();
+        let value = match token.as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            "inf" | "+inf" => Some(Value::Number(f64::INFINITY)),
+            "-inf" => Some(Value::Number(f64::NEG_INFINITY)),
+            "nan" | "+nan" | "-nan" => Some(Value::Number(f64::NAN)),
+            _ if token.len() >= 8 && token.as_bytes()[..4].iter().all(u8::is_ascii_digit) && token.as_bytes()[4] == b'-' => Some(Value::String(token.clone())),
+            _ if token.len() >= 8 && token.as_bytes()[..2].iter().all(u8::is_ascii_digit) && token.as_bytes()[2] == b':' => Some(Value::String(token.clone())),
+            _ => number(&token).map(Value::Number),
+        };
+        value.ok_or_else(|| { self.pos = start; self.error("invalid value") })
+    }
+}
+
+/// Parses a TOML integer (decimal, `0x`, `0o`, `0b`) or float, with `_` separators.
+fn number(token: &str) -> Option<f64> {
+    if token.is_empty() || token.starts_with('_') || token.ends_with('_') || token.contains("__") { return None; }
+    let digits = token.replace('_', "");
+    for (prefix, radix) in [("0x", 16), ("0o", 8), ("0b", 2)] {
+        if let Some(rest) = digits.strip_prefix(prefix) { return i64::from_str_radix(rest, radix).ok().map(|n| n as f64); }
+    }
+    if !digits.bytes().all(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.' | b'e' | b'E')) { return None; }
+    digits.parse().ok()
+}
@@ -0,0 +1,394 @@
+use crate::coalesce::duplicate;
+use crate::executor::map_bounded;
+use crate::lockfile::compatible;
+use crate::query::crate_versions;
+use crate::{crate_dependencies_with, http, Config, DependencyInfo, DependencyKind, Version, VersionInfo, VersionReq};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// One crate of a [`DepTree`], resolved to a published version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepNode {
+    /// The crate name.
+    pub name: String,
+    /// The requirement it was resolved from (`"=VERSION"` or `"*"` for the root).
+    pub req: String,
+    /// Whether the parent depends on it as a regular or a build-dependency.
+    pub kind: DependencyKind,
+    /// The newest non-yanked version matching `req`; `None` if no published version
+    /// matches or the versions couldn't be fetched.
+    pub version: Option<VersionInfo>,
+    /// Its own regular and build-dependencies, in declaration order.
+    pub dependencies: Vec<DepNode>,
+    /// Whether its dependencies were left out because of the depth limit.
+    pub truncated: bool,
+    /// Whether its dependencies were left out because this crate version is already
+    /// expanded earlier in the tree, as `cargo tree` marks with `(*)`.
+    pub repeated: bool,
+}
+
+/// The transitive dependencies of a crate version, built by [`dependency_tree`].
+///
+/// A crate reached along several paths appears under each of its parents, but its
+/// own dependencies are listed only once, the first time it is reached; later
+/// occurrences are marked [`DepNode::repeated`].
+#[derive(Debug)]
+pub struct DepTree {
+    /// The requested crate.
+    pub root: DepNode,
+    /// The lookups that failed (`"name"` for a version list, `"name version"` for a
+    /// dependency list), with the reason.
+    pub failed: Vec<(String, Box<dyn Error>)>,
+}
+
+impl DepTree {
+    /// Every node of the tree, depth-first, starting with the root.
+    pub fn iter(&self) -> impl Iterator<Item = &DepNode> {
+        let mut stack = vec![&self.root];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.dependencies.iter().rev());
+            Some(node)
+        })
+    }
+
+    /// The crates resolved at several semver-incompatible versions, with the
+    /// requirement chains leading to each: the root as `name version`, then one
+    /// `name req` step per dependency. Chains don't continue below
+    /// [`repeated`](DepNode::repeated) nodes.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, dependency_tree};
+    ///
+    /// let tree = block_on(dependency_tree("reqwest", None, 8)).expect("Failed to resolve tree");
+    /// for duplicate in tree.duplicates() {
+    ///     print!("{}", duplicate);
+    /// }
+    /// ```
+    pub fn duplicates(&self) -> Vec<Duplicate> {
+        let mut occurrences = Vec::new();
+        let root = match &self.root.version {
+            Some(version) => format!("{} {}", self.root.name, version.num),
+            None => self.root.name.clone(),
+        };
+        let mut stack = vec![(&self.root, vec![root])];
+        while let Some((node, chain)) = stack.pop() {
+            for dependency in node.dependencies.iter().rev() {
+                let mut chain = chain.clone();
+                chain.push(format!("{} {}", dependency.name, dependency.req));
+                stack.push((dependency, chain));
+            }
+            if let Some(version) = &node.version { occurrences.push((node.name.clone(), version.num.clone(), chain)); }
+        }
+        duplicates(occurrences)
+    }
+
+    /// Sums the `.crate` archive sizes of every crate version in the tree, counting
+    /// each once: roughly what a fresh `cargo build` downloads, before extraction.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use crator::{block_on, dependency_tree};
+    ///
+    /// let tree = block_on(dependency_tree("reqwest", None, 16)).expect("Failed to resolve tree");
+    /// let size = tree.download_size();
+    /// println!("{}", size);
+    /// for (name, bytes) in size.crates.iter().take(5) {
+    ///     println!("{:>10} {}", bytes, name);
+    /// }
+    /// ```
+    pub fn download_size(&self) -> DownloadSize {
+        let mut size = DownloadSize::default();
+        for node in self.iter() {
+            size.truncated |= node.truncated;
+            let Some(version) = &node.version else { continue };
+            let name = format!("{} {}", node.name, version.num);
+            if size.crates.iter().any(|(seen, _)| *seen == name) || size.unknown.contains(&name) { continue; }
+            match version.crate_size {
+                Some(bytes) => {
+                    size.bytes += bytes;
+                    size.crates.push((name, bytes));
+                }
+                None => size.unknown.push(name),
+            }
+        }
+        size.crates.sort_by(|(_, a), (_, b)| b.cmp(a));
+        size
+    }
+}
+
+/// The estimated download size of a [`DepTree`], from [`DepTree::download_size`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DownloadSize {
+    /// The total size of the `.crate` archives, in bytes.
+    pub bytes: u64,
+    /// Each crate version (`name version`) with its archive size, largest first.
+    pub crates: Vec<(String, u64)>,
+    /// The crate versions whose size crates.io doesn't report.
+    pub unknown: Vec<String>,
+    /// Whether the tree was cut off by its depth limit, making `bytes` a lower bound.
+    pub truncated: bool,
+}
+
+impl fmt::Display for DownloadSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let units = ["B", "KiB", "MiB", "GiB"];
+        let (mut size, mut unit) = (self.bytes as f64, 0);
+        while size >= 1024.0 && unit + 1 < units.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        if self.truncated { write!(f, "at least ")?; }
+        match unit {
+            0 => write!(f, "{} B", self.bytes)?,
+            _ => write!(f, "{:.1} {}", size, units[unit])?,
+        }
+        write!(f, " across {} crates", self.crates.len())?;
+        if !self.unknown.is_empty() { write!(f, " ({} more of unknown size)", self.unknown.len())?; }
+        Ok(())
+    }
+}
+
+/// A crate present at several semver-incompatible versions, as found by
+/// [`DepTree::duplicates`] or [`Lockfile::duplicates`](crate::Lockfile::duplicates).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Duplicate {
+    /// The crate name.
+    pub name: String,
+    /// Every version present, oldest first.
+    pub versions: Vec<DuplicateVersion>,
+}
+
+/// One version of a [`Duplicate`] and how it is reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DuplicateVersion {
+    /// The version number.
+    pub version: String,
+    /// The dependency chains leading to this version, each from a root to the
+    /// crate itself (e.g. `["app 0.1.0", "rand ^0.8", "getrandom ^0.2"]`).
+    pub chains: Vec<Vec<String>>,
+}
+
+impl fmt::Display for Duplicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.name)?;
+        for version in &self.versions {
+            for chain in &version.chains {
+                writeln!(f, "  {:<12} {}", version.version, chain.join(" > "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Groups `(name, version, chain)` occurrences by crate and keeps the crates with
+/// semver-incompatible versions, in order of first occurrence.
+pub(crate) fn duplicates(occurrences: Vec<(String, String, Vec<String>)>) -> Vec<Duplicate> {
+    let mut found: Vec<Duplicate> = Vec::new();
+    for (name, version, chain) in occurrences {
+        let index = match found.iter().position(|duplicate| duplicate.name == name) {
+            Some(index) => index,
+            None => {
+                found.push(Duplicate { name, versions: Vec::new() });
+                found.len() - 1
+            }
+        };
+        let versions = &mut found[index].versions;
+        match versions.iter_mut().find(|existing| existing.version == version) {
+            Some(existing) => if !existing.chains.contains(&chain) { existing.chains.push(chain); },
+            None => versions.push(DuplicateVersion { version, chains: vec![chain] }),
+        }
+    }
+    found.retain_mut(|duplicate| {
+        duplicate.versions.sort_by_key(|version| Version::parse(&version.version));
+        let parsed: Vec<Version> = duplicate.versions.iter().filter_map(|version| Version::parse(&version.version)).collect();
+        parsed.iter().any(|a| parsed.iter().any(|b| !compatible(a, b)))
+    });
+    found
+}
+
+impl fmt::Display for DepTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_node(f: &mut fmt::Formatter<'_>, node: &DepNode, prefix: &str, branch: &str) -> fmt::Result {
+            write!(f, "{}{}{}", prefix, branch, node.name)?;
+            match &node.version {
+                Some(version) => write!(f, " v{}", version.num)?,
+                None => write!(f, " {} (unresolved)", node.req)?,
+            }
+            if node.kind == DependencyKind::Build { write!(f, " (build)")?; }
+            if node.truncated { write!(f, " (truncated)")?; }
+            if node.repeated { write!(f, " (*)")?; }
+            writeln!(f)?;
+            let prefix = match branch {
+                "├── " => format!("{}│   ", prefix),
+                "└── " => format!("{}    ", prefix),
+                _ => prefix.to_string(),
+            };
+            for (i, dependency) in node.dependencies.iter().enumerate() {
+                let branch = if i + 1 == node.dependencies.len() { "└── " } else { "├── " };
+                write_node(f, dependency, &prefix, branch)?;
+            }
+            Ok(())
+        }
+        write_node(f, &self.root, "", "")?;
+        for (name, e) in &self.failed {
+            writeln!(f, "error: {}: {}", name, e)?;
+        }
+        Ok(())
+    }
+}
+
+/// The regular and build-dependencies followed when expanding a tree.
+fn followed(dependencies: &[DependencyInfo]) -> impl Iterator<Item = &DependencyInfo> {
+    dependencies.iter().filter(|dependency| dependency.kind != DependencyKind::Dev && !dependency.optional)
+}
+
+/// The newest non-yanked version matching `req`.
+fn resolve(versions: &[VersionInfo], req: &str) -> Option<VersionInfo> {
+    let req = VersionReq::parse(req)?;
+    versions.iter().filter(|version| !version.yanked)
+        .filter_map(|version| Some((Version::parse(&version.num)?, version)))
+        .filter(|(parsed, _)| req.matches(parsed))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, version)| version.clone())
+}
+
+/// A version or dependency list, or why it couldn't be fetched; the error is
+/// `Send` so batch futures holding listings across awaits stay `Send`.
+pub(crate) type Listing<T> = Result<Vec<T>, Box<dyn Error + Send + Sync>>;
+
+/// The version lists and dependency lists fetched while expanding a tree.
+#[derive(Default)]
+struct Index {
+    versions: HashMap<String, Listing<VersionInfo>>,
+    /// Keyed by crate name and version.
+    dependencies: HashMap<(String, String), Listing<DependencyInfo>>,
+    /// How many levels below the root are expanded.
+    max_depth: usize,
+}
+
+impl Index {
+    fn resolve(&self, name: &str, req: &str) -> Option<VersionInfo> {
+        resolve(self.versions.get(name)?.as_ref().ok()?, req)
+    }
+
+    /// Builds the subtree of one crate version. `expanded` records the depth each
+    /// crate version was first expanded at; it is expanded again only when reached
+    /// higher up, where the depth limit leaves more of it to show.
+    fn node(&self, name: &str, req: &str, kind: DependencyKind, version: Option<VersionInfo>, depth: usize, expanded: &mut HashMap<(String, String), usize>) -> DepNode {
+        let mut node = DepNode { name: name.to_string(), req: req.to_string(), kind, version, dependencies: Vec::new(), truncated: false, repeated: false };
+        let Some(version) = &node.version else { return node };
+        let key = (node.name.clone(), version.num.clone());
+        let Some(Ok(dependencies)) = self.dependencies.get(&key) else {
+            node.truncated = depth == self.max_depth;
+            return node;
+        };
+        if followed(dependencies).next().is_none() { return node; }
+        if depth == self.max_depth {
+            node.truncated = true;
+            return node;
+        }
+        if expanded.get(&key).is_some_and(|&first| first <= depth) {
+            node.repeated = true;
+            return node;
+        }
+        expanded.insert(key, depth);
+        node.dependencies = followed(dependencies)
+            .map(|dependency| self.node(&dependency.name, &dependency.req, dependency.kind, self.resolve(&dependency.name, &dependency.req), depth + 1, expanded))
+            .collect();
+        node
+    }
+}
+
+/// Resolves the transitive dependency tree of a crate version, `max_depth` levels
+/// deep (`0` for the crate alone).
+///
+/// Without a `version`, the newest stable release is used. Each dependency resolves
+/// to the newest non-yanked version matching its requirement, the way a fresh
+/// `cargo update` would if nothing else constrained it; optional and
+/// dev-dependencies are left out, since which features are enabled isn't known.
+/// Every crate version is looked up once however often it appears; see
+/// [`dependency_tree_with`] for a custom [`Config`].
+///
+/// # Errors
+/// Fails only if the root crate can't be fetched or has no such version; lookups
+/// deeper in the tree that fail are listed in [`DepTree::failed`].
+///
+/// # Example
+/// ```rust,no_run
+/// use crator::{block_on, dependency_tree};
+///
+/// let tree = block_on(dependency_tree("reqwest", None, 3)).expect("Failed to resolve tree");
+/// print!("{}", tree);
+/// println!("{} nodes", tree.iter().count());
+/// ```
+pub async fn dependency_tree(crate_name: &str, version: Option<&str>, max_depth: usize) -> Result<DepTree, Box<dyn Error>> {
+    dependency_tree_with(crate_name, version, max_depth, crate::default_config()).await
+}
+
+/// Same as [`dependency_tree`], but driven by a custom [`Config`]; each level of
+/// the tree is fetched with [`Config::max_concurrency`] requests in flight.
+pub async fn dependency_tree_with(crate_name: &str, version: Option<&str>, max_depth: usize, config: &Config) -> Result<DepTree, Box<dyn Error>> {
+    let versions = crate_versions(crate_name, config).await?;
+    let (req, root) = match version {
+        Some(version) => (format!("={}", version), versions.iter().find(|published| published.num == version).cloned()),
+        // A crate with only pre-releases resolves to the newest of them
+        None => ("*".to_string(), resolve(&versions, "*").or_else(|| {
+            versions.iter().filter(|version| !version.yanked).max_by_key(|version| Version::parse(&version.num)).cloned()
+        })),
+    };
+    let root = root.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no published version of {} matches {}", crate_name, req)))?;
+
+    let mut index = Index { max_depth, ..Index::default() };
+    let mut failed: Vec<(String, Box<dyn Error + Send + Sync>)> = Vec::new();
+    index.versions.insert(crate_name.to_string(), Ok(versions));
+    let mut level = vec![(crate_name.to_string(), root.num.clone())];
+    for _ in 0..max_depth {
+        let pending: Vec<(String, String)> = level.iter().filter(|key| !index.dependencies.contains_key(*key)).cloned().collect();
+        let fetched = map_bounded(&pending, config.concurrency(), |(name, version)| async move {
+            crate_dependencies_with(name, version, config).await.map_err(http::sendable)
+        }).await;
+        for ((name, version), result) in pending.into_iter().zip(fetched) {
+            if let Err(e) = &result { failed.push((format!("{} {}", name, version), duplicate(e.as_ref()))); }
+            index.dependencies.insert((name, version), result);
+        }
+
+        let mut names: Vec<String> = Vec::new();
+        for key in &level {
+            let Some(Ok(dependencies)) = index.dependencies.get(key) else { continue };
+            for dependency in followed(dependencies) {
+                if !index.versions.contains_key(&dependency.name) && !names.contains(&dependency.name) { names.push(dependency.name.clone()); }
+            }
+        }
+        let fetched = map_bounded(&names, config.concurrency(), |name| async move {
+            crate_versions(name, config).await.map_err(http::sendable)
+        }).await;
+        for (name, result) in names.into_iter().zip(fetched) {
+            if let Err(e) = &result { failed.push((name.clone(), duplicate(e.as_ref()))); }
+            index.versions.insert(name, result);
+        }
+
+        let mut next: Vec<(String, String)> = Vec::new();
+        for key in &level {
+            let Some(Ok(dependencies)) = index.dependencies.get(key) else { continue };
+            for dependency in followed(dependencies) {
+                let Some(version) = index.resolve(&dependency.name, &dependency.req) else { continue };
+                let key = (dependency.name.clone(), version.num);
+                if !index.dependencies.contains_key(&key) && !next.contains(&key) { next.push(key); }
+            }
+        }
+        if next.is_empty() { break; }
+        level = next;
+    }
+
+    let root = index.node(crate_name, &req, DependencyKind::Normal, Some(root), 0, &mut HashMap::new());
+    let failed = failed.into_iter().map(|(name, e)| (name, e as Box<dyn Error>)).collect();
+    Ok(DepTree { root, failed })
+}
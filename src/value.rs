@@ -0,0 +1,398 @@
+use crate::{Json, JsonError};
+use std::fmt;
+
+/// Nesting depth at which [`Json::parse`] gives up, so hostile input can't overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+/// A fully parsed JSON document, produced by [`Json::parse`].
+///
+/// Object entries keep their document order. Numbers are stored as `f64`, which
+/// represents every integer crates.io returns (IDs, download counts) exactly.
+///
+/// # Example
+/// ```rust
+/// use crator::{Json, Value};
+///
+/// let body = r#"{"crate": {"name": "crator", "downloads": 5600, "keywords": ["json", "tls"]}}"#;
+/// let value = Json::parse(body).expect("Invalid JSON");
+///
+/// assert_eq!(value.at("crate.name").and_then(Value::as_str), Some("crator"));
+/// assert_eq!(value.at("crate.downloads").and_then(Value::as_u64), Some(5600));
+/// if let Some(Value::Object(entries)) = value.get("crate") {
+///     let keys: Vec<&str> = entries.iter().map(|(key, _)| key.as_str()).collect();
+///     assert_eq!(keys, ["name", "downloads", "keywords"]);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// Any JSON number.
+    Number(f64),
+    /// A string, with its escape sequences decoded.
+    String(String),
+    /// An array of values.
+    Array(Vec<Value>),
+    /// An object as `(key, value)` pairs in document order.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Starts an empty object, to be filled with [`Value::field`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use crator::{Json, Value};
+    ///
+    /// let body = Value::object()
+    ///     .field("name", "crator")
+    ///     .field("yanked", false)
+    ///     .field("keywords", vec!["json", "tls"])
+    ///     .field("owner", Value::object().field("id", 42u64).field("note", "say \"hi\""));
+    /// assert_eq!(
+    ///     Json::write(&body),
+    ///     r#"{"name":"crator","yanked":false,"keywords":["json","tls"],"owner":{"id":42,"note":"say \"hi\""}}"#
+    /// );
+    /// ```
+    pub fn object() -> Self {
+        Value::Object(Vec::new())
+    }
+
+    /// Starts an empty array, to be filled with [`Value::item`].
+    pub fn array() -> Self {
+        Value::Array(Vec::new())
+    }
+
+    /// Appends an entry to an object (builder style); has no effect on other values.
+    pub fn field(mut self, key: &str, value: impl Into<Value>) -> Self {
+        if let Value::Object(entries) = &mut self { entries.push((key.to_string(), value.into())); }
+        self
+    }
+
+    /// Appends an element to an array (builder style); has no effect on other values.
+    pub fn item(mut self, value: impl Into<Value>) -> Self {
+        if let Value::Array(elements) = &mut self { elements.push(value.into()); }
+        self
+    }
+
+    /// Returns the value of `key` if this is an object containing it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns the element at `index` if this is an array long enough.
+    pub fn index(&self, index: usize) -> Option<&Value> {
+        match self {
+            Value::Array(elements) => elements.get(index),
+            _ => None,
+        }
+    }
+
+    /// Walks a dot-notation path (same syntax as [`Json::extract`], without wildcards or filters).
+    pub fn at(&self, path: &str) -> Option<&Value> {
+        Json::segments(path).into_iter().try_fold(self, |value, part| match (value, part.parse::<usize>()) {
+            (Value::Array(_), Ok(idx)) => value.index(idx),
+            _ => value.get(&Json::segment_key(part)),
+        })
+    }
+
+    /// Returns the string if this is a `String`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if this is a `Number`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if this is a `Number` holding a non-negative integer.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_f64().filter(|n| n.fract() == 0.0 && *n >= 0.0 && *n <= u64::MAX as f64).map(|n| n as u64)
+    }
+
+    /// Returns the number if this is a `Number` holding an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_f64().filter(|n| n.fract() == 0.0 && n.abs() <= i64::MAX as f64).map(|n| n as i64)
+    }
+
+    /// Returns the boolean if this is a `Bool`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if this is an `Array`.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries if this is an `Object`.
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+}
+
+/// Serializes the value as compact JSON; non-finite numbers are written as `null`.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Value::Number(_) => f.write_str("null"),
+            Value::String(s) => write_string(f, s),
+            Value::Array(elements) => {
+                f.write_str("[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 { f.write_str(",")?; }
+                    write!(f, "{}", element)?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 { f.write_str(",")?; }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Writes `s` as a quoted JSON string, escaping quotes, backslashes, and control characters.
+pub(crate) fn write_string(out: &mut impl fmt::Write, s: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            '\u{8}' => out.write_str("\\b")?,
+            '\u{c}' => out.write_str("\\f")?,
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+macro_rules! number_from {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Value {
+            fn from(n: $t) -> Self {
+                Value::Number(n as f64)
+            }
+        }
+    )*};
+}
+number_from!(f64, f32, i64, i32, u64, u32, usize);
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(elements: Vec<T>) -> Self {
+        Value::Array(elements.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+/// Strict recursive-descent parser behind [`Json::parse`] and [`Json::validate`].
+pub(crate) struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    /// Parses the whole input as a single JSON value.
+    pub(crate) fn parse_document(mut self) -> Result<Value, JsonError> {
+        let value = self.parse_value(0)?;
+        self.skip_whitespace();
+        if self.pos != self.src.len() { return Err(self.error("trailing characters after the document")); }
+        Ok(value)
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<Value, JsonError> {
+        if depth > MAX_DEPTH { return Err(self.error("nesting too deep")); }
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.parse_object(depth),
+            Some(b'[') => self.parse_array(depth),
+            Some(b'"') => self.parse_string().map(Value::String),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<Value, JsonError> {
+        self.pos += 1;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.eat(b'}') { return Ok(Value::Object(entries)); }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') { return Err(self.error("expected a string key")); }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            if !self.eat(b':') { return Err(self.error("expected `:` after key")); }
+            entries.push((key, self.parse_value(depth + 1)?));
+            self.skip_whitespace();
+            if self.eat(b'}') { return Ok(Value::Object(entries)); }
+            if !self.eat(b',') { return Err(self.error("expected `,` or `}` in object")); }
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<Value, JsonError> {
+        self.pos += 1;
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.eat(b']') { return Ok(Value::Array(elements)); }
+        loop {
+            elements.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+            if self.eat(b']') { return Ok(Value::Array(elements)); }
+            if !self.eat(b',') { return Err(self.error("expected `,` or `]` in array")); }
+        }
+    }
+
+    /// Parses a string, rejecting unknown escapes and raw control characters.
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        let start = self.pos + 1;
+        self.pos = start;
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    let hex = self.src.get(self.pos + 2..self.pos + 6).is_some_and(|h| h.bytes().all(|b| b.is_ascii_hexdigit()));
+                    match self.src.as_bytes().get(self.pos + 1) {
+                        Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => self.pos += 2,
+                        Some(b'u') if hex => self.pos += 6,
+                        _ => return Err(self.error("invalid escape sequence")),
+                    }
+                }
+                Some(0..=0x1f) => return Err(self.error("control character in string")),
+                Some(_) => self.pos += 1,
+            }
+        }
+        let content = &self.src[start..self.pos];
+        self.pos += 1;
+        Ok(Json::unescape(content).into_owned())
+    }
+
+    /// Parses a number following the JSON grammar (`-?int frac? exp?`).
+    fn parse_number(&mut self) -> Result<Value, JsonError> {
+        let start = self.pos;
+        let invalid = || JsonError::Syntax { offset: start, reason: "invalid number" };
+        self.pos += number_len(&self.src.as_bytes()[start..]).ok_or_else(invalid)?;
+        self.src[start..self.pos].parse::<f64>().map(Value::Number).map_err(|_| invalid())
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, JsonError> {
+        if !self.src[self.pos..].starts_with(word) { return Err(self.error("invalid literal")); }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() != Some(byte) { return false; }
+        self.pos += 1;
+        true
+    }
+
+    fn error(&self, reason: &'static str) -> JsonError {
+        // Truncated bodies are the common case, so name them as such
+        let reason = if self.pos >= self.src.len() { "unexpected end of input" } else { reason };
+        JsonError::Syntax { offset: self.pos, reason }
+    }
+}
+
+/// The length of the JSON number (`-?int frac? exp?`) at the start of `bytes`, or
+/// `None` if it doesn't start with a valid one.
+pub(crate) fn number_len(bytes: &[u8]) -> Option<usize> {
+    let digits = |from: usize| bytes[from..].iter().take_while(|b| b.is_ascii_digit()).count();
+    let mut pos = usize::from(bytes.first() == Some(&b'-'));
+    let int_len = digits(pos);
+    if int_len == 0 || (bytes[pos] == b'0' && int_len > 1) { return None; }
+    pos += int_len;
+    if bytes.get(pos) == Some(&b'.') {
+        let frac_len = digits(pos + 1);
+        if frac_len == 0 { return None; }
+        pos += 1 + frac_len;
+    }
+    if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+        pos += 1;
+        if matches!(bytes.get(pos), Some(b'+' | b'-')) { pos += 1; }
+        let exp_len = digits(pos);
+        if exp_len == 0 { return None; }
+        pos += exp_len;
+    }
+    Some(pos)
+}
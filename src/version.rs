@@ -0,0 +1,307 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A semantic version (`MAJOR.MINOR.PATCH[-PRE]`), parsed without the `semver` crate.
+///
+/// Versions order by SemVer precedence: numeric components first, then a
+/// pre-release sorts below the corresponding release (`1.0.0-rc.1 < 1.0.0`), with
+/// pre-release identifiers compared field by field. Build metadata (`+...`) is
+/// ignored. Versions also compare against `(major, minor, patch)` tuples.
+///
+/// # Example
+/// ```rust
+/// use crator::Version;
+///
+/// let version = Version::parse("1.5.0-beta.2").unwrap();
+/// assert_eq!((version.major, version.minor, version.patch), (1, 5, 0));
+/// assert_eq!(version.pre, "beta.2");
+/// assert!(version < Version::parse("1.5.0").unwrap());
+/// assert!(version < (1, 5, 0) && version > (1, 4, 9));
+/// assert!(Version::parse("1.0.0-alpha.10").unwrap() > Version::parse("1.0.0-alpha.9").unwrap());
+/// assert_eq!(version.to_string(), "1.5.0-beta.2");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Version {
+    /// Incremented for incompatible API changes.
+    pub major: u64,
+    /// Incremented for backwards-compatible additions.
+    pub minor: u64,
+    /// Incremented for backwards-compatible fixes.
+    pub patch: u64,
+    /// The pre-release identifiers (e.g., "rc.1"); empty for a release.
+    pub pre: String,
+}
+
+impl Version {
+    /// Creates a release version.
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Version { major, minor, patch, pre: String::new() }
+    }
+
+    /// Parses a version such as `"1.2.3"`, `"1.0.0-rc.1"` or `"0.4.2+build.5"`.
+    ///
+    /// Returns `None` unless all three numeric components are present.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        // Build metadata doesn't take part in precedence
+        let s = s.split_once('+').map_or(s, |(version, _)| version);
+        let (core, pre) = s.split_once('-').unwrap_or((s, ""));
+        let mut parts = core.split('.').map(|part| {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) { return None; }
+            part.parse::<u64>().ok()
+        });
+        let (major, minor, patch) = (parts.next()??, parts.next()??, parts.next()??);
+        if parts.next().is_some() || (s.contains('-') && pre.split('.').any(str::is_empty)) { return None; }
+        Some(Version { major, minor, patch, pre: pre.to_string() })
+    }
+
+    /// Returns `true` for pre-releases such as `1.0.0-beta.1`.
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Classifies the update from this version to `newer`.
+    pub fn update_kind(&self, newer: &Version) -> UpdateKind {
+        if newer.is_prerelease() { return UpdateKind::Prerelease; }
+        if newer.major != self.major { return UpdateKind::Major; }
+        if newer.minor != self.minor { return UpdateKind::Minor; }
+        UpdateKind::Patch
+    }
+
+    /// Compares pre-release identifiers by SemVer precedence; a release sorts last.
+    fn cmp_pre(&self, other: &str) -> Ordering {
+        match (self.pre.is_empty(), other.is_empty()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Greater,
+            (false, true) => return Ordering::Less,
+            (false, false) => {}
+        }
+        let (mut left, mut right) = (self.pre.split('.'), other.split('.'));
+        loop {
+            let ordering = match (left.next(), right.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                // Numeric identifiers compare numerically and sort below alphanumeric ones
+                (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                    (Ok(a), Ok(b)) => a.cmp(&b),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => a.cmp(b),
+                },
+            };
+            if ordering != Ordering::Equal { return ordering; }
+        }
+    }
+}
+
+/// How far an update moves a version, by the first component that changes.
+///
+/// Under Cargo's default (caret) requirements, a `Minor` update of a `0.x` crate is
+/// as breaking as a `Major` one; see [`OutdatedCrate::breaking`](crate::OutdatedCrate::breaking)
+/// for that judgment.
+///
+/// # Example
+/// ```rust
+/// use crator::{UpdateKind, Version};
+///
+/// let current = Version::parse("1.4.2").unwrap();
+/// assert_eq!(current.update_kind(&Version::parse("1.4.9").unwrap()), UpdateKind::Patch);
+/// assert_eq!(current.update_kind(&Version::parse("1.6.0").unwrap()), UpdateKind::Minor);
+/// assert_eq!(current.update_kind(&Version::parse("2.0.0").unwrap()), UpdateKind::Major);
+/// assert_eq!(current.update_kind(&Version::parse("2.0.0-rc.1").unwrap()), UpdateKind::Prerelease);
+/// assert_eq!(UpdateKind::Minor.to_string(), "minor");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum UpdateKind {
+    /// Only the patch number changes (`1.4.2` → `1.4.9`).
+    Patch,
+    /// The minor number changes (`1.4.2` → `1.6.0`).
+    Minor,
+    /// The major number changes (`1.4.2` → `2.0.0`).
+    Major,
+    /// The new version is a pre-release (`1.4.2` → `2.0.0-rc.1`).
+    Prerelease,
+}
+
+impl fmt::Display for UpdateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            UpdateKind::Patch => "patch",
+            UpdateKind::Minor => "minor",
+            UpdateKind::Major => "major",
+            UpdateKind::Prerelease => "pre-release",
+        })
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch)).then_with(|| self.cmp_pre(&other.pre))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<(u64, u64, u64)> for Version {
+    fn eq(&self, other: &(u64, u64, u64)) -> bool {
+        (self.major, self.minor, self.patch) == *other && self.pre.is_empty()
+    }
+}
+
+impl PartialOrd<(u64, u64, u64)> for Version {
+    fn partial_cmp(&self, other: &(u64, u64, u64)) -> Option<Ordering> {
+        Some((self.major, self.minor, self.patch).cmp(other).then_with(|| self.cmp_pre("")))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() { write!(f, "-{}", self.pre)?; }
+        Ok(())
+    }
+}
+
+/// A Cargo version requirement such as `"^1.2"`, `"~0.4.3"`, `">=1.0, <1.5"` or
+/// `"1.*"`, matching [`Version`]s the way Cargo does.
+///
+/// A bare version is a caret requirement. A pre-release only matches when one of
+/// the comparators names a pre-release of the same `MAJOR.MINOR.PATCH`.
+///
+/// # Example
+/// ```rust
+/// use crator::{Version, VersionReq};
+///
+/// let version = |s| Version::parse(s).unwrap();
+/// let req = VersionReq::parse("1.2").unwrap();
+/// assert!(req.matches(&version("1.9.0")) && !req.matches(&version("2.0.0")));
+/// assert!(VersionReq::parse("^0.4.3").unwrap().matches(&version("0.4.9")));
+/// assert!(!VersionReq::parse("^0.4.3").unwrap().matches(&version("0.5.0")));
+/// assert!(VersionReq::parse(">=1.0, <1.5").unwrap().matches(&version("1.4.2")));
+/// assert!(!VersionReq::parse("^1.0").unwrap().matches(&version("1.5.0-rc.1")));
+/// assert!(VersionReq::parse("^1.5.0-rc.1").unwrap().matches(&version("1.5.0-rc.2")));
+/// assert!(VersionReq::parse("not a requirement").is_none());
+/// assert!(!VersionReq::parse("^0.0.18446744073709551615").unwrap().matches(&version("0.1.0")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VersionReq {
+    text: String,
+    comparators: Vec<Comparator>,
+}
+
+/// One comparator of a requirement, as the range of versions it admits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Comparator {
+    /// The lowest matching version, and whether it matches itself.
+    lower: Option<(Version, bool)>,
+    /// The highest matching version, and whether it matches itself.
+    upper: Option<(Version, bool)>,
+    /// The `MAJOR.MINOR.PATCH` whose pre-releases this comparator lets through.
+    pre: Option<(u64, u64, u64)>,
+}
+
+impl VersionReq {
+    /// Parses a comma-separated list of comparators; an empty string means `*`.
+    ///
+    /// Returns `None` for anything Cargo wouldn't accept as a requirement.
+    pub fn parse(s: &str) -> Option<Self> {
+        let text = s.trim();
+        let mut comparators = Vec::new();
+        if !text.is_empty() {
+            for part in text.split(',') {
+                comparators.push(Comparator::parse(part.trim())?);
+            }
+        }
+        Some(VersionReq { text: text.to_string(), comparators })
+    }
+
+    /// Whether `version` satisfies every comparator of the requirement.
+    pub fn matches(&self, version: &Version) -> bool {
+        if version.is_prerelease() && !self.comparators.iter().any(|comparator| comparator.pre == Some((version.major, version.minor, version.patch))) {
+            return false;
+        }
+        self.comparators.iter().all(|comparator| comparator.matches(version))
+    }
+}
+
+impl Comparator {
+    fn parse(s: &str) -> Option<Self> {
+        let (op, rest) = ["=", ">=", "<=", ">", "<", "~", "^"].iter()
+            .find_map(|op| s.strip_prefix(op).map(|rest| (*op, rest.trim_start())))
+            .unwrap_or(("^", s));
+        let rest = rest.split_once('+').map_or(rest, |(version, _)| version);
+        let (core, pre) = rest.split_once('-').unwrap_or((rest, ""));
+        let mut parts = Vec::new();
+        for part in core.split('.') {
+            if matches!(part, "*" | "x" | "X") {
+                parts.push(None);
+            } else {
+                if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) { return None; }
+                parts.push(Some(part.parse::<u64>().ok()?));
+            }
+        }
+        // Once a component is a wildcard, the rest must be left out or wildcards too
+        let given: Vec<u64> = parts.iter().map_while(|part| *part).collect();
+        if parts.len() > 3 || parts[given.len()..].iter().any(Option::is_some) { return None; }
+        if given.len() < parts.len() && !matches!(op, "^" | "=") { return None; }
+        if (!pre.is_empty() && given.len() < 3) || (rest.contains('-') && pre.split('.').any(str::is_empty)) { return None; }
+        let op = if given.len() < parts.len() { "=" } else { op };
+
+        let version = |major, minor, patch| Version::new(major, minor, patch);
+        let full = given.len() == 3;
+        let exact = Version { major: given.first().copied().unwrap_or(0), minor: given.get(1).copied().unwrap_or(0), patch: given.get(2).copied().unwrap_or(0), pre: pre.to_string() };
+        // The first version past a major, minor or patch release, carrying into the
+        // next component when one is already `u64::MAX`; `None` when there is none
+        let next_major = |major: u64| major.checked_add(1).map(|major| version(major, 0, 0));
+        let next_minor = |major, minor: u64| minor.checked_add(1).map_or_else(|| next_major(major), |minor| Some(version(major, minor, 0)));
+        let next_patch = |major, minor, patch: u64| patch.checked_add(1).map_or_else(|| next_minor(major, minor), |patch| Some(version(major, minor, patch)));
+        // The first version past the range a partial version (`1`, `1.2`) stands for
+        let past = match *given.as_slice() {
+            [] => None,
+            [major] => next_major(major),
+            [major, minor] => next_minor(major, minor),
+            _ => next_patch(exact.major, exact.minor, exact.patch),
+        };
+        let (lower, upper) = match op {
+            "=" if full => (Some((exact.clone(), true)), Some((exact.clone(), true))),
+            "=" => (Some((exact.clone(), true)), past.map(|past| (past, false))),
+            ">" if full => (Some((exact.clone(), false)), None),
+            ">" => (Some((past?, true)), None),
+            ">=" => (Some((exact.clone(), true)), None),
+            "<" => (None, Some((exact.clone(), false))),
+            "<=" if full => (None, Some((exact.clone(), true))),
+            "<=" => (None, past.map(|past| (past, false))),
+            "~" if given.len() >= 2 => (Some((exact.clone(), true)), next_minor(exact.major, exact.minor).map(|upper| (upper, false))),
+            "~" => (Some((exact.clone(), true)), past.map(|past| (past, false))),
+            _ => {
+                let upper = match (exact.major, exact.minor, given.len()) {
+                    (0, 0, 3) => next_patch(0, 0, exact.patch),
+                    (0, minor, len) if len >= 2 => next_minor(0, minor),
+                    (major, _, _) => next_major(major),
+                };
+                (Some((exact.clone(), true)), upper.map(|upper| (upper, false)))
+            }
+        };
+        let pre = (!exact.pre.is_empty()).then_some((exact.major, exact.minor, exact.patch));
+        Some(Comparator { lower, upper, pre })
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        let above = self.lower.as_ref().is_none_or(|(lower, inclusive)| version > lower || *inclusive && version == lower);
+        let below = self.upper.as_ref().is_none_or(|(upper, inclusive)| version < upper || *inclusive && version == upper);
+        above && below
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.text.is_empty() { "*" } else { &self.text })
+    }
+}
@@ -0,0 +1,58 @@
+use crate::http::outgoing_headers;
+use crate::{Config, FetchError, Request};
+use js_sys::{Array, Function, Promise, Reflect};
+use std::error::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Headers, RequestInit, Response, Window, WorkerGlobalScope};
+
+/// Sends `request` through the JavaScript `fetch` API and returns an HTTP/1-style
+/// response head (status line plus headers) and the body, like the native transports.
+///
+/// The host environment owns the connection, so TLS settings, DNS options, and
+/// rate-limit retries of `config` don't apply; browsers also drop headers scripts
+/// may not set, such as `User-Agent`.
+pub(crate) async fn fetch(host: &str, request: &Request, config: &Config) -> Result<(String, String), Box<dyn Error>> {
+    let headers = Headers::new().map_err(js_error)?;
+    for (name, value) in outgoing_headers(request, config) {
+        headers.set(name, value).map_err(js_error)?;
+    }
+    let init = RequestInit::new();
+    init.set_method(request.method());
+    init.set_headers(&headers);
+    let url = format!("https://{}{}", host, request.target());
+    let response: Response = JsFuture::from(global_fetch(&url, &init)?).await
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(js_error)?;
+
+    let mut head = format!("HTTP/1.1 {} {}", response.status(), response.status_text());
+    for entry in js_sys::try_iter(&response.headers()).map_err(js_error)?.ok_or("response headers are not iterable")? {
+        let pair: Array = entry.map_err(js_error)?.unchecked_into();
+        head.push_str(&format!("\r\n{}: {}", pair.get(0).as_string().unwrap_or_default(), pair.get(1).as_string().unwrap_or_default()));
+    }
+    let body = JsFuture::from(response.text().map_err(js_error)?).await.map_err(js_error)?.as_string().unwrap_or_default();
+    if let Some(limit) = config.max_response_size && body.len() > limit {
+        return Err(Box::new(FetchError::ResponseTooLarge { limit }));
+    }
+    Ok((head, body))
+}
+
+/// Calls `fetch` on whichever global object is available: a window, a worker, or Node.js.
+fn global_fetch(url: &str, init: &RequestInit) -> Result<Promise, Box<dyn Error>> {
+    let global = js_sys::global();
+    if let Some(window) = global.dyn_ref::<Window>() { return Ok(window.fetch_with_str_and_init(url, init)); }
+    if let Some(worker) = global.dyn_ref::<WorkerGlobalScope>() { return Ok(worker.fetch_with_str_and_init(url, init)); }
+    // Node.js 18+ exposes `fetch` directly on the global object
+    let fetch: Function = Reflect::get(&global, &JsValue::from_str("fetch")).map_err(js_error)?.dyn_into().map_err(js_error)?;
+    let promise = fetch.call2(&global, &JsValue::from_str(url), init).map_err(js_error)?;
+    promise.dyn_into().map_err(js_error)
+}
+
+/// Converts a thrown JavaScript value into an error carrying its message.
+fn js_error(value: JsValue) -> Box<dyn Error> {
+    match value.dyn_ref::<js_sys::Error>() {
+        Some(error) => String::from(error.message()).into(),
+        None => value.as_string().unwrap_or_else(|| format!("{:?}", value)).into(),
+    }
+}